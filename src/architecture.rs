@@ -0,0 +1,448 @@
+use crate::device::GPUCharacteristics;
+use crate::vendor::Vendor;
+
+/// Represents a GPU microarchitecture family.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Architecture {
+    // AMD
+    Gcn,
+    Cdna,
+    Rdna1,
+    Rdna2,
+    Rdna3,
+    Rdna4,
+    // NVIDIA
+    Kepler,
+    Maxwell,
+    Pascal,
+    Volta,
+    Turing,
+    Ampere,
+    Hopper,
+    AdaLovelace,
+    Blackwell,
+    // Intel
+    Gen9,
+    Gen11,
+    XeLp,
+    XeLpg,
+    XeHpg,
+    Xe2,
+    // ARM
+    Midgard,
+    Bifrost,
+    Valhall,
+    Mali5thGen,
+    // Qualcomm
+    Adreno5xx,
+    Adreno6xx,
+    Adreno7xx,
+    Adreno8xx,
+}
+
+/// Inclusive device ID ranges and the architecture they belong to.
+type IdTable = &'static [(u32, u32, Architecture)];
+
+const AMD_IDS: IdTable = &[
+    (0x1435, 0x1435, Architecture::Rdna2),
+    (0x1506, 0x1506, Architecture::Rdna2),
+    (0x13C0, 0x13C0, Architecture::Rdna2),
+    (0x150E, 0x150E, Architecture::Rdna3),
+    (0x1586, 0x1586, Architecture::Rdna3),
+    (0x15BF, 0x15BF, Architecture::Rdna3),
+    (0x15C8, 0x15C8, Architecture::Rdna3),
+    (0x15D8, 0x15DD, Architecture::Gcn),
+    (0x15E7, 0x15E7, Architecture::Gcn),
+    (0x1636, 0x1638, Architecture::Gcn),
+    (0x163F, 0x163F, Architecture::Rdna2),
+    (0x164C, 0x164C, Architecture::Gcn),
+    (0x164E, 0x164E, Architecture::Rdna2),
+    (0x1681, 0x1681, Architecture::Rdna2),
+    (0x1900, 0x1901, Architecture::Rdna3),
+    (0x6600, 0x66AF, Architecture::Gcn),
+    (0x6780, 0x683F, Architecture::Gcn),
+    (0x6860, 0x687F, Architecture::Gcn),
+    (0x6900, 0x699F, Architecture::Gcn),
+    (0x6FDF, 0x6FDF, Architecture::Gcn),
+    (0x7300, 0x730F, Architecture::Gcn),
+    (0x7310, 0x736F, Architecture::Rdna1),
+    (0x7388, 0x738F, Architecture::Cdna),
+    (0x73A0, 0x73FF, Architecture::Rdna2),
+    (0x7408, 0x740F, Architecture::Cdna),
+    (0x7420, 0x743F, Architecture::Rdna2),
+    (0x7440, 0x749F, Architecture::Rdna3),
+    (0x74A0, 0x74BF, Architecture::Cdna),
+    (0x7550, 0x755F, Architecture::Rdna4),
+    (0x7590, 0x759F, Architecture::Rdna4),
+    (0x9830, 0x98EF, Architecture::Gcn),
+];
+
+const NVIDIA_IDS: IdTable = &[
+    (0x0FC0, 0x103F, Architecture::Kepler),
+    (0x1180, 0x12FF, Architecture::Kepler),
+    (0x1340, 0x143F, Architecture::Maxwell),
+    (0x15F0, 0x15FF, Architecture::Pascal),
+    (0x1600, 0x17FF, Architecture::Maxwell),
+    (0x1B00, 0x1D7F, Architecture::Pascal),
+    (0x1D80, 0x1DFF, Architecture::Volta),
+    (0x1E00, 0x1FFF, Architecture::Turing),
+    (0x2080, 0x20FF, Architecture::Ampere),
+    (0x2180, 0x21FF, Architecture::Turing),
+    (0x2200, 0x22FF, Architecture::Ampere),
+    (0x2300, 0x233F, Architecture::Hopper),
+    (0x2400, 0x25FF, Architecture::Ampere),
+    (0x2600, 0x28FF, Architecture::AdaLovelace),
+    (0x2900, 0x2FFF, Architecture::Blackwell),
+];
+
+const INTEL_IDS: IdTable = &[
+    (0x0A84, 0x0A84, Architecture::Gen9),
+    (0x1900, 0x193F, Architecture::Gen9),
+    (0x3180, 0x318F, Architecture::Gen9),
+    (0x3E90, 0x3EBF, Architecture::Gen9),
+    (0x4500, 0x457F, Architecture::Gen11),
+    (0x4600, 0x46FF, Architecture::XeLp),
+    (0x4900, 0x490F, Architecture::XeLp),
+    (0x4C80, 0x4C9F, Architecture::XeLp),
+    (0x4E50, 0x4E7F, Architecture::Gen11),
+    (0x4F80, 0x4F8F, Architecture::XeHpg),
+    (0x5690, 0x56FF, Architecture::XeHpg),
+    (0x5900, 0x593F, Architecture::Gen9),
+    (0x5A80, 0x5A8F, Architecture::Gen9),
+    (0x6420, 0x64FF, Architecture::Xe2),
+    (0x7D40, 0x7DFF, Architecture::XeLpg),
+    (0x87C0, 0x87CF, Architecture::Gen9),
+    (0x8A50, 0x8A5F, Architecture::Gen11),
+    (0x9A40, 0x9AFF, Architecture::XeLp),
+    (0x9B00, 0x9BFF, Architecture::Gen9),
+    (0xA700, 0xA7FF, Architecture::XeLp),
+    (0xE200, 0xE2FF, Architecture::Xe2),
+];
+
+/// Execution unit and Xe-core counts for known Intel device IDs.
+/// Xe-core counts are only listed for the generations Intel markets them for.
+const INTEL_EXECUTION_UNITS: &[(u32, u32, Option<u32>)] = &[
+    (0x1912, 24, None),
+    (0x1916, 24, None),
+    (0x191B, 24, None),
+    (0x3E92, 24, None),
+    (0x3E98, 24, None),
+    (0x3E9B, 24, None),
+    (0x4680, 32, None),
+    (0x4690, 24, None),
+    (0x4692, 24, None),
+    (0x4693, 16, None),
+    (0x46A6, 96, None),
+    (0x46A8, 80, None),
+    (0x46D1, 24, None),
+    (0x4905, 96, None),
+    (0x4C8A, 32, None),
+    (0x4C8B, 24, None),
+    (0x5690, 512, Some(32)),
+    (0x5691, 384, Some(24)),
+    (0x5692, 256, Some(16)),
+    (0x5693, 128, Some(8)),
+    (0x5694, 96, Some(6)),
+    (0x56A0, 512, Some(32)),
+    (0x56A1, 448, Some(28)),
+    (0x56A2, 384, Some(24)),
+    (0x56A5, 128, Some(8)),
+    (0x56A6, 96, Some(6)),
+    (0x5912, 24, None),
+    (0x5916, 24, None),
+    (0x591B, 24, None),
+    (0x5926, 48, None),
+    (0x5927, 48, None),
+    (0x64A0, 64, Some(8)),
+    (0x7D55, 128, Some(8)),
+    (0x9A40, 96, None),
+    (0x9A49, 96, None),
+    (0x9A60, 32, None),
+    (0x9A68, 32, None),
+    (0x9A78, 48, None),
+    (0xA780, 32, None),
+    (0xA7A0, 96, None),
+    (0xE20B, 160, Some(20)),
+    (0xE20C, 144, Some(18)),
+];
+
+/// Returns the execution unit count and, where applicable, the Xe-core
+/// count for an Intel device ID.
+pub fn intel_execution_units(device_id: u32) -> Option<(u32, Option<u32>)> {
+    INTEL_EXECUTION_UNITS
+        .iter()
+        .find(|(id, _, _)| *id == device_id)
+        .map(|(_, execution_units, xe_cores)| (*execution_units, *xe_cores))
+}
+
+impl Architecture {
+    /// Infers the architecture from the vendor, the PCI device ID, and any
+    /// vendor-specific properties that were read from the driver.
+    pub fn detect(
+        vendor: Vendor,
+        device_id: u32,
+        device_name: &str,
+        characteristics: &GPUCharacteristics,
+    ) -> Option<Self> {
+        match vendor {
+            // GCN exposes four SIMDs per compute unit, RDNA exposes two.
+            Vendor::AMD => {
+                lookup(AMD_IDS, device_id).or(match characteristics.simd_per_compute_unit {
+                    Some(4) => Some(Architecture::Gcn),
+                    _ => None,
+                })
+            }
+            // Turing is the only recent generation with 32 warps per SM.
+            Vendor::Nvidia => {
+                lookup(NVIDIA_IDS, device_id).or(match characteristics.warps_per_sm {
+                    Some(32) => Some(Architecture::Turing),
+                    _ => None,
+                })
+            }
+            Vendor::Intel => lookup(INTEL_IDS, device_id),
+            Vendor::ARM => mali_architecture(device_id),
+            Vendor::Qualcomm | Vendor::QualcommPartner => {
+                adreno_architecture(device_id).or_else(|| adreno_from_name(device_name))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns a human-readable name.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Architecture::Gcn => "GCN",
+            Architecture::Cdna => "CDNA",
+            Architecture::Rdna1 => "RDNA 1",
+            Architecture::Rdna2 => "RDNA 2",
+            Architecture::Rdna3 => "RDNA 3",
+            Architecture::Rdna4 => "RDNA 4",
+            Architecture::Kepler => "Kepler",
+            Architecture::Maxwell => "Maxwell",
+            Architecture::Pascal => "Pascal",
+            Architecture::Volta => "Volta",
+            Architecture::Turing => "Turing",
+            Architecture::Ampere => "Ampere",
+            Architecture::Hopper => "Hopper",
+            Architecture::AdaLovelace => "Ada Lovelace",
+            Architecture::Blackwell => "Blackwell",
+            Architecture::Gen9 => "Gen9",
+            Architecture::Gen11 => "Gen11",
+            Architecture::XeLp => "Xe-LP",
+            Architecture::XeLpg => "Xe-LPG",
+            Architecture::XeHpg => "Xe-HPG",
+            Architecture::Xe2 => "Xe2",
+            Architecture::Midgard => "Midgard",
+            Architecture::Bifrost => "Bifrost",
+            Architecture::Valhall => "Valhall",
+            Architecture::Mali5thGen => "5th Gen",
+            Architecture::Adreno5xx => "Adreno 5xx",
+            Architecture::Adreno6xx => "Adreno 6xx",
+            Architecture::Adreno7xx => "Adreno 7xx",
+            Architecture::Adreno8xx => "Adreno 8xx",
+        }
+    }
+}
+
+/// Allows an architecture to be printed using its human-readable name.
+impl std::fmt::Display for Architecture {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+fn lookup(table: IdTable, device_id: u32) -> Option<Architecture> {
+    table
+        .iter()
+        .find(|(start, end, _)| (*start..=*end).contains(&device_id))
+        .map(|(_, _, architecture)| *architecture)
+}
+
+/// Mali drivers report either a 16-bit product ID or the full 32-bit
+/// `GPU_ID` register; both carry the architecture major in the top nibble.
+fn mali_architecture(device_id: u32) -> Option<Architecture> {
+    let arch_major = match device_id {
+        0x0600..=0x0FFF => return Some(Architecture::Midgard),
+        0x1000..=0xFFFF => device_id >> 12,
+        0x10000.. => device_id >> 28,
+        _ => return None,
+    };
+
+    match arch_major {
+        4 | 5 => Some(Architecture::Midgard),
+        6 | 7 => Some(Architecture::Bifrost),
+        9..=11 => Some(Architecture::Valhall),
+        12.. => Some(Architecture::Mali5thGen),
+        _ => None,
+    }
+}
+
+/// Older Adreno drivers encode the series in the top byte of the chip ID,
+/// newer ones use a `0x43` ("C") prefix followed by the generation.
+fn adreno_architecture(device_id: u32) -> Option<Architecture> {
+    match device_id >> 24 {
+        0x05 => Some(Architecture::Adreno5xx),
+        0x06 => Some(Architecture::Adreno6xx),
+        0x07 => Some(Architecture::Adreno7xx),
+        0x43 => Some(Architecture::Adreno7xx),
+        0x44 => Some(Architecture::Adreno8xx),
+        _ => None,
+    }
+}
+
+fn adreno_from_name(device_name: &str) -> Option<Architecture> {
+    let (_, model) = device_name.split_once("Adreno")?;
+    let series = model
+        .trim_start_matches(|c: char| !c.is_ascii_digit())
+        .chars()
+        .next()?;
+
+    match series {
+        '5' => Some(Architecture::Adreno5xx),
+        '6' => Some(Architecture::Adreno6xx),
+        '7' => Some(Architecture::Adreno7xx),
+        '8' => Some(Architecture::Adreno8xx),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_characteristics() -> GPUCharacteristics {
+        GPUCharacteristics {
+            memory_pressure: None,
+            compute_units: None,
+            active_compute_units: None,
+            shader_engines: None,
+            shader_arrays_per_engine_count: None,
+            compute_units_per_shader_array: None,
+            simd_per_compute_unit: None,
+            wavefronts_per_simd: None,
+            wavefront_size: None,
+            streaming_multiprocessors: None,
+            warps_per_sm: None,
+            shader_cores: None,
+            warps_per_core: None,
+            pixel_rate: None,
+            texel_rate: None,
+            fma_rate: None,
+            execution_units: None,
+            xe_cores: None,
+            min_subgroup_size: None,
+            max_subgroup_size: None,
+            tile_memory_size: None,
+            supports_tile_properties: None,
+            unsupported_portability_features: None,
+            min_vertex_input_binding_stride_alignment: None,
+            max_image_dimension_2d: 0,
+            max_compute_shared_memory_size: 0,
+            max_compute_work_group_invocations: 0,
+            dedicated_transfer_queue: false,
+            dedicated_async_compute_queue: false,
+            supports_ray_tracing: false,
+        }
+    }
+
+    fn detect(vendor: Vendor, device_id: u32) -> Option<Architecture> {
+        Architecture::detect(vendor, device_id, "", &empty_characteristics())
+    }
+
+    #[test]
+    fn test_amd_device_ids() {
+        assert_eq!(detect(Vendor::AMD, 0x67DF), Some(Architecture::Gcn));
+        assert_eq!(detect(Vendor::AMD, 0x731F), Some(Architecture::Rdna1));
+        assert_eq!(detect(Vendor::AMD, 0x73BF), Some(Architecture::Rdna2));
+        assert_eq!(detect(Vendor::AMD, 0x163F), Some(Architecture::Rdna2));
+        assert_eq!(detect(Vendor::AMD, 0x744C), Some(Architecture::Rdna3));
+        assert_eq!(detect(Vendor::AMD, 0x7550), Some(Architecture::Rdna4));
+        assert_eq!(detect(Vendor::AMD, 0x740F), Some(Architecture::Cdna));
+    }
+
+    #[test]
+    fn test_nvidia_device_ids() {
+        assert_eq!(detect(Vendor::Nvidia, 0x1E87), Some(Architecture::Turing));
+        assert_eq!(detect(Vendor::Nvidia, 0x2204), Some(Architecture::Ampere));
+        assert_eq!(
+            detect(Vendor::Nvidia, 0x2684),
+            Some(Architecture::AdaLovelace)
+        );
+        assert_eq!(
+            detect(Vendor::Nvidia, 0x2B85),
+            Some(Architecture::Blackwell)
+        );
+    }
+
+    #[test]
+    fn test_intel_device_ids() {
+        assert_eq!(detect(Vendor::Intel, 0x5917), Some(Architecture::Gen9));
+        assert_eq!(detect(Vendor::Intel, 0x9A49), Some(Architecture::XeLp));
+        assert_eq!(detect(Vendor::Intel, 0x56A0), Some(Architecture::XeHpg));
+        assert_eq!(detect(Vendor::Intel, 0xE20B), Some(Architecture::Xe2));
+        assert_eq!(detect(Vendor::Intel, 0x64A0), Some(Architecture::Xe2));
+    }
+
+    #[test]
+    fn test_mali_device_ids() {
+        assert_eq!(detect(Vendor::ARM, 0x0860), Some(Architecture::Midgard));
+        assert_eq!(detect(Vendor::ARM, 0x7212), Some(Architecture::Bifrost));
+        assert_eq!(detect(Vendor::ARM, 0x9093), Some(Architecture::Valhall));
+        assert_eq!(detect(Vendor::ARM, 0x92020010), Some(Architecture::Valhall));
+        assert_eq!(
+            detect(Vendor::ARM, 0xC8740010),
+            Some(Architecture::Mali5thGen)
+        );
+    }
+
+    #[test]
+    fn test_adreno() {
+        assert_eq!(
+            detect(Vendor::Qualcomm, 0x06060001),
+            Some(Architecture::Adreno6xx)
+        );
+        assert_eq!(
+            detect(Vendor::Qualcomm, 0x43050A01),
+            Some(Architecture::Adreno7xx)
+        );
+        assert_eq!(
+            Architecture::detect(
+                Vendor::Qualcomm,
+                0,
+                "Turnip Adreno (TM) 750",
+                &empty_characteristics()
+            ),
+            Some(Architecture::Adreno7xx)
+        );
+    }
+
+    #[test]
+    fn test_vendor_property_fallback() {
+        let mut characteristics = empty_characteristics();
+        characteristics.simd_per_compute_unit = Some(4);
+        assert_eq!(
+            Architecture::detect(Vendor::AMD, 0, "", &characteristics),
+            Some(Architecture::Gcn)
+        );
+
+        characteristics.warps_per_sm = Some(32);
+        assert_eq!(
+            Architecture::detect(Vendor::Nvidia, 0, "", &characteristics),
+            Some(Architecture::Turing)
+        );
+    }
+
+    #[test]
+    fn test_intel_execution_units() {
+        assert_eq!(intel_execution_units(0x56A0), Some((512, Some(32))));
+        assert_eq!(intel_execution_units(0x9A49), Some((96, None)));
+        assert_eq!(intel_execution_units(0x0001), None);
+    }
+
+    #[test]
+    fn test_unknown() {
+        assert_eq!(detect(Vendor::AMD, 0x0001), None);
+        assert_eq!(detect(Vendor::Unknown, 0x2204), None);
+        assert_eq!(Architecture::Rdna2.to_string(), "RDNA 2");
+    }
+}
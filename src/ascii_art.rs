@@ -37,6 +37,14 @@ pub(crate) const VULKAN: &[&str] = &[
 pub(crate) const VULKAN_STYLE: [&str; LUT_SIZE] = [RED, "", "", "", BLACK];
 pub(crate) const VULKAN_STYLE_ALT: [&str; LUT_SIZE] = [BRIGHT_RED, "", "", "", BLACK];
 
+// Vendors without their own art get the Vulkan logo tinted in their brand color.
+pub(crate) const VULKAN_BLUE_STYLE: [&str; LUT_SIZE] = [BLUE, "", "", "", BLACK];
+pub(crate) const VULKAN_BLUE_STYLE_ALT: [&str; LUT_SIZE] = [BRIGHT_BLUE, "", "", "", BLACK];
+pub(crate) const VULKAN_YELLOW_STYLE: [&str; LUT_SIZE] = [YELLOW, "", "", "", BLACK];
+pub(crate) const VULKAN_YELLOW_STYLE_ALT: [&str; LUT_SIZE] = [BRIGHT_YELLOW, "", "", "", BLACK];
+pub(crate) const VULKAN_WHITE_STYLE: [&str; LUT_SIZE] = [WHITE, "", "", "", BLACK];
+pub(crate) const VULKAN_WHITE_STYLE_ALT: [&str; LUT_SIZE] = [BRIGHT_WHITE, "", "", "", BLACK];
+
 pub(crate) const APPLE: &[&str] = &[
     r#"                          ###                  "#,
     r#"                        ####                   "#,
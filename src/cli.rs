@@ -0,0 +1,377 @@
+use std::{fmt, path::PathBuf, time::Duration};
+
+use crate::exporter::DEFAULT_ADDRESS;
+use crate::record::RecordFormat;
+
+/// Number of processes listed by `--processes` without an explicit count.
+pub const DEFAULT_PROCESS_COUNT: usize = 5;
+
+/// Refresh interval of `--watch` without an explicit interval.
+pub const DEFAULT_WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Sampling interval of `--record` without `--interval`.
+pub const DEFAULT_RECORD_INTERVAL: Duration = Duration::from_secs(1);
+
+pub const USAGE: &str = "\
+Usage: vkfetch-rs [OPTIONS]
+
+Options:
+  --processes [N]    List the top N processes using each GPU (default 5, Linux only)
+  --watch [SECONDS]  Redraw memory usage and sensors every SECONDS (default 1)
+  --serve [ADDRESS]  Serve OpenMetrics on http://ADDRESS/metrics (default 127.0.0.1:9835)
+  --tui              Browse extensions, features, limits, formats and queues interactively
+  --record FILE      Sample memory usage to FILE ('-' for stdout) until Ctrl-C
+  --format FORMAT    Record as 'csv' or 'ndjson' (default from the FILE extension)
+  --interval SECONDS Seconds between recorded samples (default 1)
+  --duration SECONDS Stop recording after SECONDS
+  --samples N        Stop recording after N samples
+  --device INDEX     Only use the device at INDEX; may be repeated
+  --diagnose         Show Vulkan environment variables, drivers and layers (for bug reports)
+  --validate         Check the driver stack and vkfetch-rs with the validation layer
+  --no-implicit-layers
+                     Disable implicit layers such as overlays before querying devices
+  --import FILE      Show the devices of a vulkaninfo --json or gpuinfo.org report
+                     instead of querying Vulkan
+  --export FILE      Write extensions, features, properties, formats and queue families
+                     as vulkaninfo --json does to FILE ('-' for stdout)
+  --diff SNAPSHOT    Compare devices, or the --import report, against a snapshot saved
+                     with --export and list what changed
+  --json             Print the --diff result as JSON
+  --profile FILE     Check devices against every Vulkan profile in a profiles JSON file,
+                     e.g. VP_KHR_roadmap.json from Vulkan-Profiles; may be repeated
+  -h, --help         Print this help
+  -V, --version      Print the version";
+
+/// Command line options.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Options {
+    /// Lists this many processes per device, ordered by VRAM usage.
+    pub processes: Option<usize>,
+    /// Keeps refreshing the output at this interval.
+    pub watch: Option<Duration>,
+    /// Opens the interactive browser instead of printing.
+    pub tui: bool,
+    /// Serves `/metrics` on this address instead of printing.
+    pub serve: Option<String>,
+    /// Records samples to this file instead of printing.
+    pub record: Option<PathBuf>,
+    /// Overrides the format guessed from the `record` path.
+    pub record_format: Option<RecordFormat>,
+    pub interval: Option<Duration>,
+    pub duration: Option<Duration>,
+    pub samples: Option<u64>,
+    /// Indices of the devices to use, in enumeration order. Empty selects all.
+    pub devices: Vec<usize>,
+    /// Reports the loader's driver search instead of printing devices.
+    pub diagnose: bool,
+    /// Enables the validation layer and reports its messages.
+    pub validate: bool,
+    /// Disables implicit layers so their effect on the output can be compared.
+    pub no_implicit_layers: bool,
+    /// Renders the devices of this report file instead of live devices.
+    pub import: Option<PathBuf>,
+    /// Writes a vulkaninfo-compatible report to this file instead of printing.
+    pub export: Option<PathBuf>,
+    /// Compares devices against this snapshot instead of printing.
+    pub diff: Option<PathBuf>,
+    /// Prints the `diff` result as JSON.
+    pub json: bool,
+    /// Profiles files to check devices against.
+    pub profiles: Vec<PathBuf>,
+    pub help: bool,
+    pub version: bool,
+}
+
+/// An argument that could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    UnknownArgument(String),
+    MissingValue(&'static str),
+    InvalidValue { option: &'static str, value: String },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnknownArgument(argument) => write!(f, "unknown argument '{argument}'"),
+            ParseError::MissingValue(option) => write!(f, "'{option}' requires a value"),
+            ParseError::InvalidValue { option, value } => {
+                write!(f, "invalid value '{value}' for '{option}'")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl Options {
+    /// Parses arguments, excluding the program name.
+    pub fn parse<I>(args: I) -> Result<Self, ParseError>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut options = Options::default();
+        let mut args = args.into_iter().peekable();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--processes" => {
+                    let count = args
+                        .next_if(|value| !value.starts_with('-'))
+                        .map(|value| parse_value("--processes", value))
+                        .transpose()?;
+                    options.processes = Some(count.unwrap_or(DEFAULT_PROCESS_COUNT));
+                }
+                "--watch" => {
+                    let interval = args
+                        .next_if(|value| !value.starts_with('-'))
+                        .map(|value| parse_interval("--watch", value))
+                        .transpose()?;
+                    options.watch = Some(interval.unwrap_or(DEFAULT_WATCH_INTERVAL));
+                }
+                "--tui" => options.tui = true,
+                "--serve" => {
+                    let address = args.next_if(|value| !value.starts_with('-'));
+                    options.serve = Some(address.unwrap_or_else(|| DEFAULT_ADDRESS.to_string()));
+                }
+                "--record" => {
+                    options.record = Some(required_value(&mut args, "--record")?.into());
+                }
+                "--format" => {
+                    let format = required_value(&mut args, "--format")?;
+                    options.record_format = Some(match format.as_str() {
+                        "csv" => RecordFormat::Csv,
+                        "ndjson" | "jsonl" => RecordFormat::Ndjson,
+                        _ => {
+                            return Err(ParseError::InvalidValue {
+                                option: "--format",
+                                value: format,
+                            });
+                        }
+                    });
+                }
+                "--interval" => {
+                    let value = required_value(&mut args, "--interval")?;
+                    options.interval = Some(parse_interval("--interval", value)?);
+                }
+                "--duration" => {
+                    let value = required_value(&mut args, "--duration")?;
+                    options.duration = Some(parse_interval("--duration", value)?);
+                }
+                "--samples" => {
+                    let value = required_value(&mut args, "--samples")?;
+                    options.samples = Some(parse_value("--samples", value)?);
+                }
+                "--device" => {
+                    let value = required_value(&mut args, "--device")?;
+                    options.devices.push(parse_value("--device", value)?);
+                }
+                "--diagnose" => options.diagnose = true,
+                "--validate" => options.validate = true,
+                "--no-implicit-layers" => options.no_implicit_layers = true,
+                "--import" => {
+                    options.import = Some(required_value(&mut args, "--import")?.into());
+                }
+                "--export" => {
+                    options.export = Some(required_value(&mut args, "--export")?.into());
+                }
+                "--diff" => {
+                    options.diff = Some(required_value(&mut args, "--diff")?.into());
+                }
+                "--json" => options.json = true,
+                "--profile" => {
+                    options
+                        .profiles
+                        .push(required_value(&mut args, "--profile")?.into());
+                }
+                "-h" | "--help" => options.help = true,
+                "-V" | "--version" => options.version = true,
+                _ => return Err(ParseError::UnknownArgument(arg)),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+/// Takes the value of an option that cannot be used without one.
+fn required_value(
+    args: &mut impl Iterator<Item = String>,
+    option: &'static str,
+) -> Result<String, ParseError> {
+    args.next().ok_or(ParseError::MissingValue(option))
+}
+
+fn parse_value<T: std::str::FromStr>(option: &'static str, value: String) -> Result<T, ParseError> {
+    value
+        .parse()
+        .map_err(|_| ParseError::InvalidValue { option, value })
+}
+
+/// Parses a positive number of seconds, e.g. `2` or `0.5`.
+fn parse_interval(option: &'static str, value: String) -> Result<Duration, ParseError> {
+    let seconds: f64 = parse_value(option, value.clone())?;
+    Duration::try_from_secs_f64(seconds)
+        .ok()
+        .filter(|interval| !interval.is_zero())
+        .ok_or(ParseError::InvalidValue { option, value })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Options, ParseError> {
+        Options::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_no_arguments() {
+        assert_eq!(parse(&[]), Ok(Options::default()));
+    }
+
+    #[test]
+    fn test_processes() {
+        assert_eq!(parse(&["--processes"]).unwrap().processes, Some(5));
+        assert_eq!(parse(&["--processes", "3"]).unwrap().processes, Some(3));
+        assert_eq!(
+            parse(&["--processes", "--help"]).unwrap(),
+            Options {
+                processes: Some(5),
+                help: true,
+                ..Default::default()
+            }
+        );
+        assert!(matches!(
+            parse(&["--processes", "many"]),
+            Err(ParseError::InvalidValue { .. })
+        ));
+    }
+
+    #[test]
+    fn test_watch() {
+        assert_eq!(
+            parse(&["--watch"]).unwrap().watch,
+            Some(DEFAULT_WATCH_INTERVAL)
+        );
+        assert_eq!(
+            parse(&["--watch", "0.5"]).unwrap().watch,
+            Some(Duration::from_millis(500))
+        );
+        assert!(parse(&["--watch", "0"]).is_err());
+        assert!(parse(&["--watch", "NaN"]).is_err());
+    }
+
+    #[test]
+    fn test_tui() {
+        assert!(parse(&["--tui"]).unwrap().tui);
+    }
+
+    #[test]
+    fn test_serve() {
+        assert_eq!(
+            parse(&["--serve"]).unwrap().serve.as_deref(),
+            Some(DEFAULT_ADDRESS)
+        );
+        assert_eq!(
+            parse(&["--serve", "0.0.0.0:9100"])
+                .unwrap()
+                .serve
+                .as_deref(),
+            Some("0.0.0.0:9100")
+        );
+    }
+
+    #[test]
+    fn test_record() {
+        let options = parse(&[
+            "--record",
+            "soak.log",
+            "--format",
+            "ndjson",
+            "--interval",
+            "2",
+            "--samples",
+            "10",
+            "--device",
+            "1",
+            "--device",
+            "0",
+        ])
+        .unwrap();
+        assert_eq!(options.record, Some(PathBuf::from("soak.log")));
+        assert_eq!(options.record_format, Some(RecordFormat::Ndjson));
+        assert_eq!(options.interval, Some(Duration::from_secs(2)));
+        assert_eq!(options.samples, Some(10));
+        assert_eq!(options.devices, vec![1, 0]);
+
+        assert_eq!(
+            parse(&["--record", "-", "--duration", "60"])
+                .unwrap()
+                .duration,
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            parse(&["--record"]),
+            Err(ParseError::MissingValue("--record"))
+        );
+        assert!(parse(&["--format", "xml"]).is_err());
+    }
+
+    #[test]
+    fn test_diagnose() {
+        assert!(parse(&["--diagnose"]).unwrap().diagnose);
+        assert!(parse(&["--validate"]).unwrap().validate);
+        assert!(parse(&["--no-implicit-layers"]).unwrap().no_implicit_layers);
+    }
+
+    #[test]
+    fn test_import() {
+        assert_eq!(
+            parse(&["--import", "vulkaninfo.json"]).unwrap().import,
+            Some(PathBuf::from("vulkaninfo.json"))
+        );
+        assert_eq!(
+            parse(&["--export", "-"]).unwrap().export,
+            Some(PathBuf::from("-"))
+        );
+    }
+
+    #[test]
+    fn test_diff() {
+        let options = parse(&["--diff", "before.json", "--json"]).unwrap();
+        assert_eq!(options.diff, Some(PathBuf::from("before.json")));
+        assert!(options.json);
+        assert_eq!(parse(&["--diff"]), Err(ParseError::MissingValue("--diff")));
+    }
+
+    #[test]
+    fn test_profile() {
+        assert_eq!(
+            parse(&[
+                "--profile",
+                "VP_KHR_roadmap.json",
+                "--profile",
+                "engine.json"
+            ])
+            .unwrap()
+            .profiles,
+            [
+                PathBuf::from("VP_KHR_roadmap.json"),
+                PathBuf::from("engine.json")
+            ]
+        );
+        assert_eq!(
+            parse(&["--profile"]),
+            Err(ParseError::MissingValue("--profile"))
+        );
+    }
+
+    #[test]
+    fn test_unknown_argument() {
+        assert_eq!(
+            parse(&["--frobnicate"]),
+            Err(ParseError::UnknownArgument("--frobnicate".to_string()))
+        );
+    }
+}
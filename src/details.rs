@@ -0,0 +1,1776 @@
+use ash::{Instance, vk};
+use std::{fmt, ops::RangeInclusive};
+
+use crate::device::{cstring_to_string, decode_version_number};
+use crate::vulkan14::{
+    API_VERSION_1_4, PhysicalDeviceVulkan14Features, PhysicalDeviceVulkan14Properties,
+};
+
+/// A named property value as reported by the implementation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropertyValue {
+    Bool(bool),
+    Unsigned(u64),
+    Signed(i64),
+    Float(f32),
+    UnsignedArray(Vec<u64>),
+    FloatArray(Vec<f32>),
+    SampleCounts(vk::SampleCountFlags),
+    /// A packed version such as `apiVersion`.
+    Version(u32),
+    /// Set bits by their Vulkan names, e.g. `VK_SHADER_STAGE_COMPUTE_BIT`.
+    Flags(Vec<String>),
+    /// Names, enumerations by their Vulkan names and UUIDs in hex.
+    Text(String),
+}
+
+impl fmt::Display for PropertyValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn list<T: fmt::Display>(values: &[T]) -> String {
+            values
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        }
+
+        match self {
+            PropertyValue::Bool(value) => write!(f, "{value}"),
+            PropertyValue::Unsigned(value) => write!(f, "{value}"),
+            PropertyValue::Signed(value) => write!(f, "{value}"),
+            PropertyValue::Float(value) => write!(f, "{value}"),
+            PropertyValue::UnsignedArray(values) => write!(f, "[{}]", list(values)),
+            PropertyValue::FloatArray(values) => write!(f, "[{}]", list(values)),
+            PropertyValue::SampleCounts(flags) => {
+                let counts = (0..7)
+                    .map(|bit| 1u32 << bit)
+                    .filter(|&count| flags.contains(vk::SampleCountFlags::from_raw(count)))
+                    .collect::<Vec<_>>();
+                write!(f, "{}", list(&counts))
+            }
+            PropertyValue::Version(version) => write!(f, "{}", decode_version_number(*version)),
+            PropertyValue::Flags(names) => write!(f, "{}", list(names)),
+            PropertyValue::Text(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+/// A device extension and its specification revision.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Extension {
+    pub name: String,
+    pub spec_version: u32,
+}
+
+/// The features of one Vulkan feature structure, e.g.
+/// `VkPhysicalDeviceVulkan12Features`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeatureGroup {
+    pub name: String,
+    pub features: Vec<(String, bool)>,
+}
+
+/// The members of one Vulkan property structure, e.g.
+/// `VkPhysicalDeviceLimits`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropertyGroup {
+    pub name: String,
+    pub properties: Vec<(String, PropertyValue)>,
+}
+
+/// The features a format supports with each tiling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FormatSupport {
+    pub format: vk::Format,
+    pub linear_tiling: vk::FormatFeatureFlags,
+    pub optimal_tiling: vk::FormatFeatureFlags,
+    pub buffer: vk::FormatFeatureFlags,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueueFamily {
+    pub flags: vk::QueueFlags,
+    pub count: u32,
+    pub timestamp_valid_bits: u32,
+    pub min_image_transfer_granularity: [u32; 3],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryHeap {
+    pub size: u64,
+    pub flags: vk::MemoryHeapFlags,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryType {
+    pub heap_index: u32,
+    pub flags: vk::MemoryPropertyFlags,
+}
+
+/// The full set of capabilities behind a [`Device`](crate::device::Device):
+/// everything that does not fit into the fetch-style summary.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DeviceDetails {
+    pub extensions: Vec<Extension>,
+    pub features: Vec<FeatureGroup>,
+    /// `VkPhysicalDeviceProperties`, split into its own members,
+    /// `VkPhysicalDeviceLimits` and `VkPhysicalDeviceSparseProperties`,
+    /// followed by the `VkPhysicalDeviceVulkan1xProperties` of the version
+    /// both the device and the instance support.
+    pub properties: Vec<PropertyGroup>,
+    pub formats: Vec<FormatSupport>,
+    pub queue_families: Vec<QueueFamily>,
+    pub memory_heaps: Vec<MemoryHeap>,
+    pub memory_types: Vec<MemoryType>,
+}
+
+/// Raw values of the formats [`DeviceDetails::query`] reads: the core
+/// formats of Vulkan 1.0, then the multi-planar YCbCr formats of 1.1.
+const CORE_FORMATS: RangeInclusive<i32> = 1..=184;
+const YCBCR_FORMATS: RangeInclusive<i32> = 1_000_156_000..=1_000_156_033;
+
+/// Returns whether [`DeviceDetails::query`] reads the format named like
+/// `VK_FORMAT_R8G8B8A8_UNORM`, so that its absence means no support.
+pub(crate) fn is_queried_format(name: &str) -> bool {
+    let name = name.strip_prefix("VK_FORMAT_").unwrap_or(name);
+    CORE_FORMATS
+        .chain(YCBCR_FORMATS)
+        .any(|raw| format!("{:?}", vk::Format::from_raw(raw)) == name)
+}
+
+/// Builds the `(name, supported)` list of a feature structure.
+macro_rules! feature_list {
+    ($features:expr, { $($field:ident => $name:literal,)* }) => {
+        vec![$(($name.to_string(), $features.$field == vk::TRUE),)*]
+    };
+}
+
+impl DeviceDetails {
+    /// Queries every capability of `physical_device`. Structures of a
+    /// Vulkan version are only requested if both the device and the
+    /// instance, created with `instance_version`, support it.
+    pub fn query(
+        instance: &Instance,
+        instance_version: u32,
+        physical_device: vk::PhysicalDevice,
+    ) -> Self {
+        let properties = unsafe { instance.get_physical_device_properties(physical_device) };
+        let api_version = properties.api_version.min(instance_version);
+
+        let extensions = unsafe {
+            instance
+                .enumerate_device_extension_properties(physical_device)
+                .unwrap_or_default()
+        }
+        .iter()
+        .map(|extension| Extension {
+            name: cstring_to_string(extension.extension_name_as_c_str().unwrap_or(c"Unknown")),
+            spec_version: extension.spec_version,
+        })
+        .collect();
+
+        let mut features = vec![FeatureGroup {
+            name: "VkPhysicalDeviceFeatures".to_string(),
+            features: features_1_0(&unsafe {
+                instance.get_physical_device_features(physical_device)
+            }),
+        }];
+        // The per-version feature structures were introduced with Vulkan 1.2.
+        if api_version >= vk::API_VERSION_1_2 {
+            let mut vulkan11 = vk::PhysicalDeviceVulkan11Features::default();
+            let mut vulkan12 = vk::PhysicalDeviceVulkan12Features::default();
+            let mut vulkan13 = vk::PhysicalDeviceVulkan13Features::default();
+            let mut vulkan14 = PhysicalDeviceVulkan14Features::default();
+            let mut features2 = vk::PhysicalDeviceFeatures2::default()
+                .push_next(&mut vulkan11)
+                .push_next(&mut vulkan12);
+            if api_version >= vk::API_VERSION_1_3 {
+                features2 = features2.push_next(&mut vulkan13);
+            }
+            if api_version >= API_VERSION_1_4 {
+                features2 = features2.push_next(&mut vulkan14);
+            }
+            unsafe {
+                instance.get_physical_device_features2(physical_device, &mut features2);
+            }
+            features.push(FeatureGroup {
+                name: "VkPhysicalDeviceVulkan11Features".to_string(),
+                features: features_1_1(&vulkan11),
+            });
+            features.push(FeatureGroup {
+                name: "VkPhysicalDeviceVulkan12Features".to_string(),
+                features: features_1_2(&vulkan12),
+            });
+            if api_version >= vk::API_VERSION_1_3 {
+                features.push(FeatureGroup {
+                    name: "VkPhysicalDeviceVulkan13Features".to_string(),
+                    features: features_1_3(&vulkan13),
+                });
+            }
+            if api_version >= API_VERSION_1_4 {
+                features.push(FeatureGroup {
+                    name: "VkPhysicalDeviceVulkan14Features".to_string(),
+                    features: features_1_4(&vulkan14),
+                });
+            }
+        }
+
+        let group = |name: &str, properties| PropertyGroup {
+            name: name.to_string(),
+            properties,
+        };
+        let mut property_groups = vec![
+            group("VkPhysicalDeviceProperties", properties_1_0(&properties)),
+            group("VkPhysicalDeviceLimits", limits(&properties.limits)),
+            group(
+                "VkPhysicalDeviceSparseProperties",
+                sparse_properties(&properties.sparse_properties),
+            ),
+        ];
+        // As with the features, the per-version structures start with 1.2.
+        if api_version >= vk::API_VERSION_1_2 {
+            let mut vulkan11 = vk::PhysicalDeviceVulkan11Properties::default();
+            let mut vulkan12 = vk::PhysicalDeviceVulkan12Properties::default();
+            let mut vulkan13 = vk::PhysicalDeviceVulkan13Properties::default();
+            let mut vulkan14 = PhysicalDeviceVulkan14Properties::default();
+            let mut properties2 = vk::PhysicalDeviceProperties2::default()
+                .push_next(&mut vulkan11)
+                .push_next(&mut vulkan12);
+            if api_version >= vk::API_VERSION_1_3 {
+                properties2 = properties2.push_next(&mut vulkan13);
+            }
+            if api_version >= API_VERSION_1_4 {
+                properties2 = properties2.push_next(&mut vulkan14);
+            }
+            unsafe {
+                instance.get_physical_device_properties2(physical_device, &mut properties2);
+            }
+            property_groups.push(group(
+                "VkPhysicalDeviceVulkan11Properties",
+                properties_1_1(&vulkan11),
+            ));
+            property_groups.push(group(
+                "VkPhysicalDeviceVulkan12Properties",
+                properties_1_2(&vulkan12),
+            ));
+            if api_version >= vk::API_VERSION_1_3 {
+                property_groups.push(group(
+                    "VkPhysicalDeviceVulkan13Properties",
+                    properties_1_3(&vulkan13),
+                ));
+            }
+            if api_version >= API_VERSION_1_4 {
+                property_groups.push(group(
+                    "VkPhysicalDeviceVulkan14Properties",
+                    properties_1_4(&vulkan14),
+                ));
+            }
+        }
+
+        let mut format_range: Vec<i32> = CORE_FORMATS.collect();
+        if api_version >= vk::API_VERSION_1_1 {
+            format_range.extend(YCBCR_FORMATS);
+        }
+        let formats = format_range
+            .into_iter()
+            .map(vk::Format::from_raw)
+            .filter_map(|format| {
+                let properties = unsafe {
+                    instance.get_physical_device_format_properties(physical_device, format)
+                };
+                let support = FormatSupport {
+                    format,
+                    linear_tiling: properties.linear_tiling_features,
+                    optimal_tiling: properties.optimal_tiling_features,
+                    buffer: properties.buffer_features,
+                };
+                (!support.linear_tiling.is_empty()
+                    || !support.optimal_tiling.is_empty()
+                    || !support.buffer.is_empty())
+                .then_some(support)
+            })
+            .collect();
+
+        let queue_families =
+            unsafe { instance.get_physical_device_queue_family_properties(physical_device) }
+                .iter()
+                .map(|family| QueueFamily {
+                    flags: family.queue_flags,
+                    count: family.queue_count,
+                    timestamp_valid_bits: family.timestamp_valid_bits,
+                    min_image_transfer_granularity: [
+                        family.min_image_transfer_granularity.width,
+                        family.min_image_transfer_granularity.height,
+                        family.min_image_transfer_granularity.depth,
+                    ],
+                })
+                .collect();
+
+        let memory_properties =
+            unsafe { instance.get_physical_device_memory_properties(physical_device) };
+        let memory_heaps = memory_properties
+            .memory_heaps_as_slice()
+            .iter()
+            .map(|heap| MemoryHeap {
+                size: heap.size,
+                flags: heap.flags,
+            })
+            .collect();
+        let memory_types = memory_properties
+            .memory_types_as_slice()
+            .iter()
+            .map(|memory_type| MemoryType {
+                heap_index: memory_type.heap_index,
+                flags: memory_type.property_flags,
+            })
+            .collect();
+
+        DeviceDetails {
+            extensions,
+            features,
+            properties: property_groups,
+            formats,
+            queue_families,
+            memory_heaps,
+            memory_types,
+        }
+    }
+
+    /// Looks up a limit or other property by its Vulkan name.
+    pub fn limit(&self, name: &str) -> Option<&PropertyValue> {
+        self.properties
+            .iter()
+            .flat_map(|group| &group.properties)
+            .find(|(property, _)| property == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Looks up a feature by its Vulkan name in any feature structure.
+    pub fn feature(&self, name: &str) -> Option<bool> {
+        self.features
+            .iter()
+            .flat_map(|group| &group.features)
+            .find(|(feature, _)| feature == name)
+            .map(|(_, supported)| *supported)
+    }
+
+    /// Looks up a property of the structure `structure`, where
+    /// `VkPhysicalDeviceProperties` includes its `limits` and
+    /// `sparseProperties` as in profiles and vulkaninfo reports.
+    pub fn property_in(&self, structure: &str, name: &str) -> Option<&PropertyValue> {
+        let groups: &[&str] = match structure {
+            "VkPhysicalDeviceProperties" => &[
+                "VkPhysicalDeviceProperties",
+                "VkPhysicalDeviceLimits",
+                "VkPhysicalDeviceSparseProperties",
+            ],
+            _ => &[structure],
+        };
+        self.properties
+            .iter()
+            .filter(|group| groups.contains(&group.name.as_str()))
+            .flat_map(|group| &group.properties)
+            .find(|(property, _)| property == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Looks up a feature of the structure `structure`.
+    pub fn feature_in(&self, structure: &str, name: &str) -> Option<bool> {
+        self.features
+            .iter()
+            .filter(|group| group.name == structure)
+            .flat_map(|group| &group.features)
+            .find(|(feature, _)| feature == name)
+            .map(|(_, supported)| *supported)
+    }
+
+    pub fn has_extension(&self, name: &str) -> bool {
+        self.extensions
+            .iter()
+            .any(|extension| extension.name == name)
+    }
+}
+
+fn features_1_0(features: &vk::PhysicalDeviceFeatures) -> Vec<(String, bool)> {
+    feature_list!(features, {
+            robust_buffer_access => "robustBufferAccess",
+            full_draw_index_uint32 => "fullDrawIndexUint32",
+            image_cube_array => "imageCubeArray",
+            independent_blend => "independentBlend",
+            geometry_shader => "geometryShader",
+            tessellation_shader => "tessellationShader",
+            sample_rate_shading => "sampleRateShading",
+            dual_src_blend => "dualSrcBlend",
+            logic_op => "logicOp",
+            multi_draw_indirect => "multiDrawIndirect",
+            draw_indirect_first_instance => "drawIndirectFirstInstance",
+            depth_clamp => "depthClamp",
+            depth_bias_clamp => "depthBiasClamp",
+            fill_mode_non_solid => "fillModeNonSolid",
+            depth_bounds => "depthBounds",
+            wide_lines => "wideLines",
+            large_points => "largePoints",
+            alpha_to_one => "alphaToOne",
+            multi_viewport => "multiViewport",
+            sampler_anisotropy => "samplerAnisotropy",
+            texture_compression_etc2 => "textureCompressionETC2",
+            texture_compression_astc_ldr => "textureCompressionASTC_LDR",
+            texture_compression_bc => "textureCompressionBC",
+            occlusion_query_precise => "occlusionQueryPrecise",
+            pipeline_statistics_query => "pipelineStatisticsQuery",
+            vertex_pipeline_stores_and_atomics => "vertexPipelineStoresAndAtomics",
+            fragment_stores_and_atomics => "fragmentStoresAndAtomics",
+            shader_tessellation_and_geometry_point_size => "shaderTessellationAndGeometryPointSize",
+            shader_image_gather_extended => "shaderImageGatherExtended",
+            shader_storage_image_extended_formats => "shaderStorageImageExtendedFormats",
+            shader_storage_image_multisample => "shaderStorageImageMultisample",
+            shader_storage_image_read_without_format => "shaderStorageImageReadWithoutFormat",
+            shader_storage_image_write_without_format => "shaderStorageImageWriteWithoutFormat",
+            shader_uniform_buffer_array_dynamic_indexing => "shaderUniformBufferArrayDynamicIndexing",
+            shader_sampled_image_array_dynamic_indexing => "shaderSampledImageArrayDynamicIndexing",
+            shader_storage_buffer_array_dynamic_indexing => "shaderStorageBufferArrayDynamicIndexing",
+            shader_storage_image_array_dynamic_indexing => "shaderStorageImageArrayDynamicIndexing",
+            shader_clip_distance => "shaderClipDistance",
+            shader_cull_distance => "shaderCullDistance",
+            shader_float64 => "shaderFloat64",
+            shader_int64 => "shaderInt64",
+            shader_int16 => "shaderInt16",
+            shader_resource_residency => "shaderResourceResidency",
+            shader_resource_min_lod => "shaderResourceMinLod",
+            sparse_binding => "sparseBinding",
+            sparse_residency_buffer => "sparseResidencyBuffer",
+            sparse_residency_image2_d => "sparseResidencyImage2D",
+            sparse_residency_image3_d => "sparseResidencyImage3D",
+            sparse_residency2_samples => "sparseResidency2Samples",
+            sparse_residency4_samples => "sparseResidency4Samples",
+            sparse_residency8_samples => "sparseResidency8Samples",
+            sparse_residency16_samples => "sparseResidency16Samples",
+            sparse_residency_aliased => "sparseResidencyAliased",
+            variable_multisample_rate => "variableMultisampleRate",
+            inherited_queries => "inheritedQueries",
+    })
+}
+
+fn features_1_1(features: &vk::PhysicalDeviceVulkan11Features) -> Vec<(String, bool)> {
+    feature_list!(features, {
+            storage_buffer16_bit_access => "storageBuffer16BitAccess",
+            uniform_and_storage_buffer16_bit_access => "uniformAndStorageBuffer16BitAccess",
+            storage_push_constant16 => "storagePushConstant16",
+            storage_input_output16 => "storageInputOutput16",
+            multiview => "multiview",
+            multiview_geometry_shader => "multiviewGeometryShader",
+            multiview_tessellation_shader => "multiviewTessellationShader",
+            variable_pointers_storage_buffer => "variablePointersStorageBuffer",
+            variable_pointers => "variablePointers",
+            protected_memory => "protectedMemory",
+            sampler_ycbcr_conversion => "samplerYcbcrConversion",
+            shader_draw_parameters => "shaderDrawParameters",
+    })
+}
+
+fn features_1_2(features: &vk::PhysicalDeviceVulkan12Features) -> Vec<(String, bool)> {
+    feature_list!(features, {
+            sampler_mirror_clamp_to_edge => "samplerMirrorClampToEdge",
+            draw_indirect_count => "drawIndirectCount",
+            storage_buffer8_bit_access => "storageBuffer8BitAccess",
+            uniform_and_storage_buffer8_bit_access => "uniformAndStorageBuffer8BitAccess",
+            storage_push_constant8 => "storagePushConstant8",
+            shader_buffer_int64_atomics => "shaderBufferInt64Atomics",
+            shader_shared_int64_atomics => "shaderSharedInt64Atomics",
+            shader_float16 => "shaderFloat16",
+            shader_int8 => "shaderInt8",
+            descriptor_indexing => "descriptorIndexing",
+            shader_input_attachment_array_dynamic_indexing => "shaderInputAttachmentArrayDynamicIndexing",
+            shader_uniform_texel_buffer_array_dynamic_indexing => "shaderUniformTexelBufferArrayDynamicIndexing",
+            shader_storage_texel_buffer_array_dynamic_indexing => "shaderStorageTexelBufferArrayDynamicIndexing",
+            shader_uniform_buffer_array_non_uniform_indexing => "shaderUniformBufferArrayNonUniformIndexing",
+            shader_sampled_image_array_non_uniform_indexing => "shaderSampledImageArrayNonUniformIndexing",
+            shader_storage_buffer_array_non_uniform_indexing => "shaderStorageBufferArrayNonUniformIndexing",
+            shader_storage_image_array_non_uniform_indexing => "shaderStorageImageArrayNonUniformIndexing",
+            shader_input_attachment_array_non_uniform_indexing => "shaderInputAttachmentArrayNonUniformIndexing",
+            shader_uniform_texel_buffer_array_non_uniform_indexing => "shaderUniformTexelBufferArrayNonUniformIndexing",
+            shader_storage_texel_buffer_array_non_uniform_indexing => "shaderStorageTexelBufferArrayNonUniformIndexing",
+            descriptor_binding_uniform_buffer_update_after_bind => "descriptorBindingUniformBufferUpdateAfterBind",
+            descriptor_binding_sampled_image_update_after_bind => "descriptorBindingSampledImageUpdateAfterBind",
+            descriptor_binding_storage_image_update_after_bind => "descriptorBindingStorageImageUpdateAfterBind",
+            descriptor_binding_storage_buffer_update_after_bind => "descriptorBindingStorageBufferUpdateAfterBind",
+            descriptor_binding_uniform_texel_buffer_update_after_bind => "descriptorBindingUniformTexelBufferUpdateAfterBind",
+            descriptor_binding_storage_texel_buffer_update_after_bind => "descriptorBindingStorageTexelBufferUpdateAfterBind",
+            descriptor_binding_update_unused_while_pending => "descriptorBindingUpdateUnusedWhilePending",
+            descriptor_binding_partially_bound => "descriptorBindingPartiallyBound",
+            descriptor_binding_variable_descriptor_count => "descriptorBindingVariableDescriptorCount",
+            runtime_descriptor_array => "runtimeDescriptorArray",
+            sampler_filter_minmax => "samplerFilterMinmax",
+            scalar_block_layout => "scalarBlockLayout",
+            imageless_framebuffer => "imagelessFramebuffer",
+            uniform_buffer_standard_layout => "uniformBufferStandardLayout",
+            shader_subgroup_extended_types => "shaderSubgroupExtendedTypes",
+            separate_depth_stencil_layouts => "separateDepthStencilLayouts",
+            host_query_reset => "hostQueryReset",
+            timeline_semaphore => "timelineSemaphore",
+            buffer_device_address => "bufferDeviceAddress",
+            buffer_device_address_capture_replay => "bufferDeviceAddressCaptureReplay",
+            buffer_device_address_multi_device => "bufferDeviceAddressMultiDevice",
+            vulkan_memory_model => "vulkanMemoryModel",
+            vulkan_memory_model_device_scope => "vulkanMemoryModelDeviceScope",
+            vulkan_memory_model_availability_visibility_chains => "vulkanMemoryModelAvailabilityVisibilityChains",
+            shader_output_viewport_index => "shaderOutputViewportIndex",
+            shader_output_layer => "shaderOutputLayer",
+            subgroup_broadcast_dynamic_id => "subgroupBroadcastDynamicId",
+    })
+}
+
+fn features_1_3(features: &vk::PhysicalDeviceVulkan13Features) -> Vec<(String, bool)> {
+    feature_list!(features, {
+            robust_image_access => "robustImageAccess",
+            inline_uniform_block => "inlineUniformBlock",
+            descriptor_binding_inline_uniform_block_update_after_bind => "descriptorBindingInlineUniformBlockUpdateAfterBind",
+            pipeline_creation_cache_control => "pipelineCreationCacheControl",
+            private_data => "privateData",
+            shader_demote_to_helper_invocation => "shaderDemoteToHelperInvocation",
+            shader_terminate_invocation => "shaderTerminateInvocation",
+            subgroup_size_control => "subgroupSizeControl",
+            compute_full_subgroups => "computeFullSubgroups",
+            synchronization2 => "synchronization2",
+            texture_compression_astc_hdr => "textureCompressionASTC_HDR",
+            shader_zero_initialize_workgroup_memory => "shaderZeroInitializeWorkgroupMemory",
+            dynamic_rendering => "dynamicRendering",
+            shader_integer_dot_product => "shaderIntegerDotProduct",
+            maintenance4 => "maintenance4",
+    })
+}
+
+fn features_1_4(features: &PhysicalDeviceVulkan14Features) -> Vec<(String, bool)> {
+    feature_list!(features, {
+            global_priority_query => "globalPriorityQuery",
+            shader_subgroup_rotate => "shaderSubgroupRotate",
+            shader_subgroup_rotate_clustered => "shaderSubgroupRotateClustered",
+            shader_float_controls2 => "shaderFloatControls2",
+            shader_expect_assume => "shaderExpectAssume",
+            rectangular_lines => "rectangularLines",
+            bresenham_lines => "bresenhamLines",
+            smooth_lines => "smoothLines",
+            stippled_rectangular_lines => "stippledRectangularLines",
+            stippled_bresenham_lines => "stippledBresenhamLines",
+            stippled_smooth_lines => "stippledSmoothLines",
+            vertex_attribute_instance_rate_divisor => "vertexAttributeInstanceRateDivisor",
+            vertex_attribute_instance_rate_zero_divisor => "vertexAttributeInstanceRateZeroDivisor",
+            index_type_uint8 => "indexTypeUint8",
+            dynamic_rendering_local_read => "dynamicRenderingLocalRead",
+            maintenance5 => "maintenance5",
+            maintenance6 => "maintenance6",
+            pipeline_protected_access => "pipelineProtectedAccess",
+            pipeline_robustness => "pipelineRobustness",
+            host_image_copy => "hostImageCopy",
+            push_descriptor => "pushDescriptor",
+    })
+}
+
+fn limits(limits: &vk::PhysicalDeviceLimits) -> Vec<(String, PropertyValue)> {
+    use PropertyValue::*;
+    [
+        (
+            "maxImageDimension1D",
+            Unsigned(limits.max_image_dimension1_d.into()),
+        ),
+        (
+            "maxImageDimension2D",
+            Unsigned(limits.max_image_dimension2_d.into()),
+        ),
+        (
+            "maxImageDimension3D",
+            Unsigned(limits.max_image_dimension3_d.into()),
+        ),
+        (
+            "maxImageDimensionCube",
+            Unsigned(limits.max_image_dimension_cube.into()),
+        ),
+        (
+            "maxImageArrayLayers",
+            Unsigned(limits.max_image_array_layers.into()),
+        ),
+        (
+            "maxTexelBufferElements",
+            Unsigned(limits.max_texel_buffer_elements.into()),
+        ),
+        (
+            "maxUniformBufferRange",
+            Unsigned(limits.max_uniform_buffer_range.into()),
+        ),
+        (
+            "maxStorageBufferRange",
+            Unsigned(limits.max_storage_buffer_range.into()),
+        ),
+        (
+            "maxPushConstantsSize",
+            Unsigned(limits.max_push_constants_size.into()),
+        ),
+        (
+            "maxMemoryAllocationCount",
+            Unsigned(limits.max_memory_allocation_count.into()),
+        ),
+        (
+            "maxSamplerAllocationCount",
+            Unsigned(limits.max_sampler_allocation_count.into()),
+        ),
+        (
+            "bufferImageGranularity",
+            Unsigned(limits.buffer_image_granularity),
+        ),
+        (
+            "sparseAddressSpaceSize",
+            Unsigned(limits.sparse_address_space_size),
+        ),
+        (
+            "maxBoundDescriptorSets",
+            Unsigned(limits.max_bound_descriptor_sets.into()),
+        ),
+        (
+            "maxPerStageDescriptorSamplers",
+            Unsigned(limits.max_per_stage_descriptor_samplers.into()),
+        ),
+        (
+            "maxPerStageDescriptorUniformBuffers",
+            Unsigned(limits.max_per_stage_descriptor_uniform_buffers.into()),
+        ),
+        (
+            "maxPerStageDescriptorStorageBuffers",
+            Unsigned(limits.max_per_stage_descriptor_storage_buffers.into()),
+        ),
+        (
+            "maxPerStageDescriptorSampledImages",
+            Unsigned(limits.max_per_stage_descriptor_sampled_images.into()),
+        ),
+        (
+            "maxPerStageDescriptorStorageImages",
+            Unsigned(limits.max_per_stage_descriptor_storage_images.into()),
+        ),
+        (
+            "maxPerStageDescriptorInputAttachments",
+            Unsigned(limits.max_per_stage_descriptor_input_attachments.into()),
+        ),
+        (
+            "maxPerStageResources",
+            Unsigned(limits.max_per_stage_resources.into()),
+        ),
+        (
+            "maxDescriptorSetSamplers",
+            Unsigned(limits.max_descriptor_set_samplers.into()),
+        ),
+        (
+            "maxDescriptorSetUniformBuffers",
+            Unsigned(limits.max_descriptor_set_uniform_buffers.into()),
+        ),
+        (
+            "maxDescriptorSetUniformBuffersDynamic",
+            Unsigned(limits.max_descriptor_set_uniform_buffers_dynamic.into()),
+        ),
+        (
+            "maxDescriptorSetStorageBuffers",
+            Unsigned(limits.max_descriptor_set_storage_buffers.into()),
+        ),
+        (
+            "maxDescriptorSetStorageBuffersDynamic",
+            Unsigned(limits.max_descriptor_set_storage_buffers_dynamic.into()),
+        ),
+        (
+            "maxDescriptorSetSampledImages",
+            Unsigned(limits.max_descriptor_set_sampled_images.into()),
+        ),
+        (
+            "maxDescriptorSetStorageImages",
+            Unsigned(limits.max_descriptor_set_storage_images.into()),
+        ),
+        (
+            "maxDescriptorSetInputAttachments",
+            Unsigned(limits.max_descriptor_set_input_attachments.into()),
+        ),
+        (
+            "maxVertexInputAttributes",
+            Unsigned(limits.max_vertex_input_attributes.into()),
+        ),
+        (
+            "maxVertexInputBindings",
+            Unsigned(limits.max_vertex_input_bindings.into()),
+        ),
+        (
+            "maxVertexInputAttributeOffset",
+            Unsigned(limits.max_vertex_input_attribute_offset.into()),
+        ),
+        (
+            "maxVertexInputBindingStride",
+            Unsigned(limits.max_vertex_input_binding_stride.into()),
+        ),
+        (
+            "maxVertexOutputComponents",
+            Unsigned(limits.max_vertex_output_components.into()),
+        ),
+        (
+            "maxTessellationGenerationLevel",
+            Unsigned(limits.max_tessellation_generation_level.into()),
+        ),
+        (
+            "maxTessellationPatchSize",
+            Unsigned(limits.max_tessellation_patch_size.into()),
+        ),
+        (
+            "maxTessellationControlPerVertexInputComponents",
+            Unsigned(
+                limits
+                    .max_tessellation_control_per_vertex_input_components
+                    .into(),
+            ),
+        ),
+        (
+            "maxTessellationControlPerVertexOutputComponents",
+            Unsigned(
+                limits
+                    .max_tessellation_control_per_vertex_output_components
+                    .into(),
+            ),
+        ),
+        (
+            "maxTessellationControlPerPatchOutputComponents",
+            Unsigned(
+                limits
+                    .max_tessellation_control_per_patch_output_components
+                    .into(),
+            ),
+        ),
+        (
+            "maxTessellationControlTotalOutputComponents",
+            Unsigned(
+                limits
+                    .max_tessellation_control_total_output_components
+                    .into(),
+            ),
+        ),
+        (
+            "maxTessellationEvaluationInputComponents",
+            Unsigned(limits.max_tessellation_evaluation_input_components.into()),
+        ),
+        (
+            "maxTessellationEvaluationOutputComponents",
+            Unsigned(limits.max_tessellation_evaluation_output_components.into()),
+        ),
+        (
+            "maxGeometryShaderInvocations",
+            Unsigned(limits.max_geometry_shader_invocations.into()),
+        ),
+        (
+            "maxGeometryInputComponents",
+            Unsigned(limits.max_geometry_input_components.into()),
+        ),
+        (
+            "maxGeometryOutputComponents",
+            Unsigned(limits.max_geometry_output_components.into()),
+        ),
+        (
+            "maxGeometryOutputVertices",
+            Unsigned(limits.max_geometry_output_vertices.into()),
+        ),
+        (
+            "maxGeometryTotalOutputComponents",
+            Unsigned(limits.max_geometry_total_output_components.into()),
+        ),
+        (
+            "maxFragmentInputComponents",
+            Unsigned(limits.max_fragment_input_components.into()),
+        ),
+        (
+            "maxFragmentOutputAttachments",
+            Unsigned(limits.max_fragment_output_attachments.into()),
+        ),
+        (
+            "maxFragmentDualSrcAttachments",
+            Unsigned(limits.max_fragment_dual_src_attachments.into()),
+        ),
+        (
+            "maxFragmentCombinedOutputResources",
+            Unsigned(limits.max_fragment_combined_output_resources.into()),
+        ),
+        (
+            "maxComputeSharedMemorySize",
+            Unsigned(limits.max_compute_shared_memory_size.into()),
+        ),
+        (
+            "maxComputeWorkGroupCount",
+            UnsignedArray(limits.max_compute_work_group_count.map(u64::from).to_vec()),
+        ),
+        (
+            "maxComputeWorkGroupInvocations",
+            Unsigned(limits.max_compute_work_group_invocations.into()),
+        ),
+        (
+            "maxComputeWorkGroupSize",
+            UnsignedArray(limits.max_compute_work_group_size.map(u64::from).to_vec()),
+        ),
+        (
+            "subPixelPrecisionBits",
+            Unsigned(limits.sub_pixel_precision_bits.into()),
+        ),
+        (
+            "subTexelPrecisionBits",
+            Unsigned(limits.sub_texel_precision_bits.into()),
+        ),
+        (
+            "mipmapPrecisionBits",
+            Unsigned(limits.mipmap_precision_bits.into()),
+        ),
+        (
+            "maxDrawIndexedIndexValue",
+            Unsigned(limits.max_draw_indexed_index_value.into()),
+        ),
+        (
+            "maxDrawIndirectCount",
+            Unsigned(limits.max_draw_indirect_count.into()),
+        ),
+        ("maxSamplerLodBias", Float(limits.max_sampler_lod_bias)),
+        ("maxSamplerAnisotropy", Float(limits.max_sampler_anisotropy)),
+        ("maxViewports", Unsigned(limits.max_viewports.into())),
+        (
+            "maxViewportDimensions",
+            UnsignedArray(limits.max_viewport_dimensions.map(u64::from).to_vec()),
+        ),
+        (
+            "viewportBoundsRange",
+            FloatArray(limits.viewport_bounds_range.to_vec()),
+        ),
+        (
+            "viewportSubPixelBits",
+            Unsigned(limits.viewport_sub_pixel_bits.into()),
+        ),
+        (
+            "minMemoryMapAlignment",
+            Unsigned(limits.min_memory_map_alignment as u64),
+        ),
+        (
+            "minTexelBufferOffsetAlignment",
+            Unsigned(limits.min_texel_buffer_offset_alignment),
+        ),
+        (
+            "minUniformBufferOffsetAlignment",
+            Unsigned(limits.min_uniform_buffer_offset_alignment),
+        ),
+        (
+            "minStorageBufferOffsetAlignment",
+            Unsigned(limits.min_storage_buffer_offset_alignment),
+        ),
+        ("minTexelOffset", Signed(limits.min_texel_offset.into())),
+        ("maxTexelOffset", Unsigned(limits.max_texel_offset.into())),
+        (
+            "minTexelGatherOffset",
+            Signed(limits.min_texel_gather_offset.into()),
+        ),
+        (
+            "maxTexelGatherOffset",
+            Unsigned(limits.max_texel_gather_offset.into()),
+        ),
+        (
+            "minInterpolationOffset",
+            Float(limits.min_interpolation_offset),
+        ),
+        (
+            "maxInterpolationOffset",
+            Float(limits.max_interpolation_offset),
+        ),
+        (
+            "subPixelInterpolationOffsetBits",
+            Unsigned(limits.sub_pixel_interpolation_offset_bits.into()),
+        ),
+        (
+            "maxFramebufferWidth",
+            Unsigned(limits.max_framebuffer_width.into()),
+        ),
+        (
+            "maxFramebufferHeight",
+            Unsigned(limits.max_framebuffer_height.into()),
+        ),
+        (
+            "maxFramebufferLayers",
+            Unsigned(limits.max_framebuffer_layers.into()),
+        ),
+        (
+            "framebufferColorSampleCounts",
+            SampleCounts(limits.framebuffer_color_sample_counts),
+        ),
+        (
+            "framebufferDepthSampleCounts",
+            SampleCounts(limits.framebuffer_depth_sample_counts),
+        ),
+        (
+            "framebufferStencilSampleCounts",
+            SampleCounts(limits.framebuffer_stencil_sample_counts),
+        ),
+        (
+            "framebufferNoAttachmentsSampleCounts",
+            SampleCounts(limits.framebuffer_no_attachments_sample_counts),
+        ),
+        (
+            "maxColorAttachments",
+            Unsigned(limits.max_color_attachments.into()),
+        ),
+        (
+            "sampledImageColorSampleCounts",
+            SampleCounts(limits.sampled_image_color_sample_counts),
+        ),
+        (
+            "sampledImageIntegerSampleCounts",
+            SampleCounts(limits.sampled_image_integer_sample_counts),
+        ),
+        (
+            "sampledImageDepthSampleCounts",
+            SampleCounts(limits.sampled_image_depth_sample_counts),
+        ),
+        (
+            "sampledImageStencilSampleCounts",
+            SampleCounts(limits.sampled_image_stencil_sample_counts),
+        ),
+        (
+            "storageImageSampleCounts",
+            SampleCounts(limits.storage_image_sample_counts),
+        ),
+        (
+            "maxSampleMaskWords",
+            Unsigned(limits.max_sample_mask_words.into()),
+        ),
+        (
+            "timestampComputeAndGraphics",
+            Bool(limits.timestamp_compute_and_graphics == vk::TRUE),
+        ),
+        ("timestampPeriod", Float(limits.timestamp_period)),
+        (
+            "maxClipDistances",
+            Unsigned(limits.max_clip_distances.into()),
+        ),
+        (
+            "maxCullDistances",
+            Unsigned(limits.max_cull_distances.into()),
+        ),
+        (
+            "maxCombinedClipAndCullDistances",
+            Unsigned(limits.max_combined_clip_and_cull_distances.into()),
+        ),
+        (
+            "discreteQueuePriorities",
+            Unsigned(limits.discrete_queue_priorities.into()),
+        ),
+        (
+            "pointSizeRange",
+            FloatArray(limits.point_size_range.to_vec()),
+        ),
+        (
+            "lineWidthRange",
+            FloatArray(limits.line_width_range.to_vec()),
+        ),
+        ("pointSizeGranularity", Float(limits.point_size_granularity)),
+        ("lineWidthGranularity", Float(limits.line_width_granularity)),
+        ("strictLines", Bool(limits.strict_lines == vk::TRUE)),
+        (
+            "standardSampleLocations",
+            Bool(limits.standard_sample_locations == vk::TRUE),
+        ),
+        (
+            "optimalBufferCopyOffsetAlignment",
+            Unsigned(limits.optimal_buffer_copy_offset_alignment),
+        ),
+        (
+            "optimalBufferCopyRowPitchAlignment",
+            Unsigned(limits.optimal_buffer_copy_row_pitch_alignment),
+        ),
+        (
+            "nonCoherentAtomSize",
+            Unsigned(limits.non_coherent_atom_size),
+        ),
+    ]
+    .into_iter()
+    .map(|(name, value)| (name.to_string(), value))
+    .collect()
+}
+
+fn sparse_properties(sparse: &vk::PhysicalDeviceSparseProperties) -> Vec<(String, PropertyValue)> {
+    use PropertyValue::*;
+    [
+        (
+            "residencyStandard2DBlockShape",
+            Bool(sparse.residency_standard2_d_block_shape == vk::TRUE),
+        ),
+        (
+            "residencyStandard2DMultisampleBlockShape",
+            Bool(sparse.residency_standard2_d_multisample_block_shape == vk::TRUE),
+        ),
+        (
+            "residencyStandard3DBlockShape",
+            Bool(sparse.residency_standard3_d_block_shape == vk::TRUE),
+        ),
+        (
+            "residencyAlignedMipSize",
+            Bool(sparse.residency_aligned_mip_size == vk::TRUE),
+        ),
+        (
+            "residencyNonResidentStrict",
+            Bool(sparse.residency_non_resident_strict == vk::TRUE),
+        ),
+    ]
+    .into_iter()
+    .map(|(name, value)| (name.to_string(), value))
+    .collect()
+}
+
+fn properties_1_0(properties: &vk::PhysicalDeviceProperties) -> Vec<(String, PropertyValue)> {
+    use PropertyValue::*;
+    [
+        ("apiVersion", Version(properties.api_version)),
+        ("driverVersion", Unsigned(properties.driver_version.into())),
+        ("vendorID", Unsigned(properties.vendor_id.into())),
+        ("deviceID", Unsigned(properties.device_id.into())),
+        (
+            "deviceType",
+            Text(format!(
+                "VK_PHYSICAL_DEVICE_TYPE_{:?}",
+                properties.device_type
+            )),
+        ),
+        (
+            "deviceName",
+            Text(cstring_to_string(
+                properties.device_name_as_c_str().unwrap_or(c"Unknown"),
+            )),
+        ),
+        ("pipelineCacheUUID", hex(&properties.pipeline_cache_uuid)),
+    ]
+    .into_iter()
+    .map(|(name, value)| (name.to_string(), value))
+    .collect()
+}
+
+const FLOAT_CONTROLS_INDEPENDENCE: [&str; 3] = ["32_BIT_ONLY", "ALL", "NONE"];
+const BUFFER_ROBUSTNESS: [&str; 4] = [
+    "DEVICE_DEFAULT",
+    "DISABLED",
+    "ROBUST_BUFFER_ACCESS",
+    "ROBUST_BUFFER_ACCESS_2",
+];
+
+/// Names an enumeration value from the names of its values in order. ash
+/// spells some differently, e.g. `TYPE_32_ONLY` for `32_BIT_ONLY`.
+fn enum_name(prefix: &str, raw: i32, names: &[&str]) -> PropertyValue {
+    let name = usize::try_from(raw)
+        .ok()
+        .and_then(|index| names.get(index))
+        .map(|name| format!("{prefix}{name}"))
+        .unwrap_or_else(|| raw.to_string());
+    PropertyValue::Text(name)
+}
+
+fn hex(bytes: &[u8]) -> PropertyValue {
+    PropertyValue::Text(bytes.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+/// Names the set bits of a flags value: `prefix` plus ash's name for the
+/// bit, with `_BIT` before any vendor suffix as in the Vulkan headers.
+pub(crate) fn flag_names<F: fmt::Debug>(
+    prefix: &str,
+    bits: u32,
+    from_raw: fn(u32) -> F,
+) -> Vec<String> {
+    const VENDORS: [&str; 8] = ["KHR", "EXT", "NV", "AMD", "ARM", "QCOM", "HUAWEI", "INTEL"];
+    (0..u32::BITS)
+        .map(|bit| 1 << bit)
+        .filter(|&bit| bits & bit != 0)
+        .map(|bit| {
+            let name = format!("{:?}", from_raw(bit));
+            match name.rsplit_once('_') {
+                Some((base, vendor)) if VENDORS.contains(&vendor) => {
+                    format!("{prefix}{base}_BIT_{vendor}")
+                }
+                _ => format!("{prefix}{name}_BIT"),
+            }
+        })
+        .collect()
+}
+
+fn properties_1_1(
+    properties: &vk::PhysicalDeviceVulkan11Properties,
+) -> Vec<(String, PropertyValue)> {
+    use PropertyValue::*;
+    [
+        ("deviceUUID", hex(&properties.device_uuid)),
+        ("driverUUID", hex(&properties.driver_uuid)),
+        ("deviceLUID", hex(&properties.device_luid)),
+        (
+            "deviceNodeMask",
+            Unsigned(properties.device_node_mask.into()),
+        ),
+        (
+            "deviceLUIDValid",
+            Bool(properties.device_luid_valid == vk::TRUE),
+        ),
+        ("subgroupSize", Unsigned(properties.subgroup_size.into())),
+        (
+            "subgroupSupportedStages",
+            Flags(flag_names(
+                "VK_SHADER_STAGE_",
+                properties.subgroup_supported_stages.as_raw(),
+                vk::ShaderStageFlags::from_raw,
+            )),
+        ),
+        (
+            "subgroupSupportedOperations",
+            Flags(flag_names(
+                "VK_SUBGROUP_FEATURE_",
+                properties.subgroup_supported_operations.as_raw(),
+                vk::SubgroupFeatureFlags::from_raw,
+            )),
+        ),
+        (
+            "subgroupQuadOperationsInAllStages",
+            Bool(properties.subgroup_quad_operations_in_all_stages == vk::TRUE),
+        ),
+        (
+            "pointClippingBehavior",
+            enum_name(
+                "VK_POINT_CLIPPING_BEHAVIOR_",
+                properties.point_clipping_behavior.as_raw(),
+                &["ALL_CLIP_PLANES", "USER_CLIP_PLANES_ONLY"],
+            ),
+        ),
+        (
+            "maxMultiviewViewCount",
+            Unsigned(properties.max_multiview_view_count.into()),
+        ),
+        (
+            "maxMultiviewInstanceIndex",
+            Unsigned(properties.max_multiview_instance_index.into()),
+        ),
+        (
+            "protectedNoFault",
+            Bool(properties.protected_no_fault == vk::TRUE),
+        ),
+        (
+            "maxPerSetDescriptors",
+            Unsigned(properties.max_per_set_descriptors.into()),
+        ),
+        (
+            "maxMemoryAllocationSize",
+            Unsigned(properties.max_memory_allocation_size),
+        ),
+    ]
+    .into_iter()
+    .map(|(name, value)| (name.to_string(), value))
+    .collect()
+}
+
+fn properties_1_2(
+    properties: &vk::PhysicalDeviceVulkan12Properties,
+) -> Vec<(String, PropertyValue)> {
+    use PropertyValue::*;
+    let version = properties.conformance_version;
+    [
+        (
+            "driverID",
+            Text(format!("VK_DRIVER_ID_{:?}", properties.driver_id)),
+        ),
+        (
+            "driverName",
+            Text(cstring_to_string(
+                properties.driver_name_as_c_str().unwrap_or(c"Unknown"),
+            )),
+        ),
+        (
+            "driverInfo",
+            Text(cstring_to_string(
+                properties.driver_info_as_c_str().unwrap_or(c"Unknown"),
+            )),
+        ),
+        (
+            "conformanceVersion",
+            Text(format!(
+                "{}.{}.{}.{}",
+                version.major, version.minor, version.subminor, version.patch
+            )),
+        ),
+        (
+            "denormBehaviorIndependence",
+            enum_name(
+                "VK_SHADER_FLOAT_CONTROLS_INDEPENDENCE_",
+                properties.denorm_behavior_independence.as_raw(),
+                &FLOAT_CONTROLS_INDEPENDENCE,
+            ),
+        ),
+        (
+            "roundingModeIndependence",
+            enum_name(
+                "VK_SHADER_FLOAT_CONTROLS_INDEPENDENCE_",
+                properties.rounding_mode_independence.as_raw(),
+                &FLOAT_CONTROLS_INDEPENDENCE,
+            ),
+        ),
+        (
+            "shaderSignedZeroInfNanPreserveFloat16",
+            Bool(properties.shader_signed_zero_inf_nan_preserve_float16 == vk::TRUE),
+        ),
+        (
+            "shaderSignedZeroInfNanPreserveFloat32",
+            Bool(properties.shader_signed_zero_inf_nan_preserve_float32 == vk::TRUE),
+        ),
+        (
+            "shaderSignedZeroInfNanPreserveFloat64",
+            Bool(properties.shader_signed_zero_inf_nan_preserve_float64 == vk::TRUE),
+        ),
+        (
+            "shaderDenormPreserveFloat16",
+            Bool(properties.shader_denorm_preserve_float16 == vk::TRUE),
+        ),
+        (
+            "shaderDenormPreserveFloat32",
+            Bool(properties.shader_denorm_preserve_float32 == vk::TRUE),
+        ),
+        (
+            "shaderDenormPreserveFloat64",
+            Bool(properties.shader_denorm_preserve_float64 == vk::TRUE),
+        ),
+        (
+            "shaderDenormFlushToZeroFloat16",
+            Bool(properties.shader_denorm_flush_to_zero_float16 == vk::TRUE),
+        ),
+        (
+            "shaderDenormFlushToZeroFloat32",
+            Bool(properties.shader_denorm_flush_to_zero_float32 == vk::TRUE),
+        ),
+        (
+            "shaderDenormFlushToZeroFloat64",
+            Bool(properties.shader_denorm_flush_to_zero_float64 == vk::TRUE),
+        ),
+        (
+            "shaderRoundingModeRTEFloat16",
+            Bool(properties.shader_rounding_mode_rte_float16 == vk::TRUE),
+        ),
+        (
+            "shaderRoundingModeRTEFloat32",
+            Bool(properties.shader_rounding_mode_rte_float32 == vk::TRUE),
+        ),
+        (
+            "shaderRoundingModeRTEFloat64",
+            Bool(properties.shader_rounding_mode_rte_float64 == vk::TRUE),
+        ),
+        (
+            "shaderRoundingModeRTZFloat16",
+            Bool(properties.shader_rounding_mode_rtz_float16 == vk::TRUE),
+        ),
+        (
+            "shaderRoundingModeRTZFloat32",
+            Bool(properties.shader_rounding_mode_rtz_float32 == vk::TRUE),
+        ),
+        (
+            "shaderRoundingModeRTZFloat64",
+            Bool(properties.shader_rounding_mode_rtz_float64 == vk::TRUE),
+        ),
+        (
+            "maxUpdateAfterBindDescriptorsInAllPools",
+            Unsigned(
+                properties
+                    .max_update_after_bind_descriptors_in_all_pools
+                    .into(),
+            ),
+        ),
+        (
+            "shaderUniformBufferArrayNonUniformIndexingNative",
+            Bool(properties.shader_uniform_buffer_array_non_uniform_indexing_native == vk::TRUE),
+        ),
+        (
+            "shaderSampledImageArrayNonUniformIndexingNative",
+            Bool(properties.shader_sampled_image_array_non_uniform_indexing_native == vk::TRUE),
+        ),
+        (
+            "shaderStorageBufferArrayNonUniformIndexingNative",
+            Bool(properties.shader_storage_buffer_array_non_uniform_indexing_native == vk::TRUE),
+        ),
+        (
+            "shaderStorageImageArrayNonUniformIndexingNative",
+            Bool(properties.shader_storage_image_array_non_uniform_indexing_native == vk::TRUE),
+        ),
+        (
+            "shaderInputAttachmentArrayNonUniformIndexingNative",
+            Bool(properties.shader_input_attachment_array_non_uniform_indexing_native == vk::TRUE),
+        ),
+        (
+            "robustBufferAccessUpdateAfterBind",
+            Bool(properties.robust_buffer_access_update_after_bind == vk::TRUE),
+        ),
+        (
+            "quadDivergentImplicitLod",
+            Bool(properties.quad_divergent_implicit_lod == vk::TRUE),
+        ),
+        (
+            "maxPerStageDescriptorUpdateAfterBindSamplers",
+            Unsigned(
+                properties
+                    .max_per_stage_descriptor_update_after_bind_samplers
+                    .into(),
+            ),
+        ),
+        (
+            "maxPerStageDescriptorUpdateAfterBindUniformBuffers",
+            Unsigned(
+                properties
+                    .max_per_stage_descriptor_update_after_bind_uniform_buffers
+                    .into(),
+            ),
+        ),
+        (
+            "maxPerStageDescriptorUpdateAfterBindStorageBuffers",
+            Unsigned(
+                properties
+                    .max_per_stage_descriptor_update_after_bind_storage_buffers
+                    .into(),
+            ),
+        ),
+        (
+            "maxPerStageDescriptorUpdateAfterBindSampledImages",
+            Unsigned(
+                properties
+                    .max_per_stage_descriptor_update_after_bind_sampled_images
+                    .into(),
+            ),
+        ),
+        (
+            "maxPerStageDescriptorUpdateAfterBindStorageImages",
+            Unsigned(
+                properties
+                    .max_per_stage_descriptor_update_after_bind_storage_images
+                    .into(),
+            ),
+        ),
+        (
+            "maxPerStageDescriptorUpdateAfterBindInputAttachments",
+            Unsigned(
+                properties
+                    .max_per_stage_descriptor_update_after_bind_input_attachments
+                    .into(),
+            ),
+        ),
+        (
+            "maxPerStageUpdateAfterBindResources",
+            Unsigned(properties.max_per_stage_update_after_bind_resources.into()),
+        ),
+        (
+            "maxDescriptorSetUpdateAfterBindSamplers",
+            Unsigned(
+                properties
+                    .max_descriptor_set_update_after_bind_samplers
+                    .into(),
+            ),
+        ),
+        (
+            "maxDescriptorSetUpdateAfterBindUniformBuffers",
+            Unsigned(
+                properties
+                    .max_descriptor_set_update_after_bind_uniform_buffers
+                    .into(),
+            ),
+        ),
+        (
+            "maxDescriptorSetUpdateAfterBindUniformBuffersDynamic",
+            Unsigned(
+                properties
+                    .max_descriptor_set_update_after_bind_uniform_buffers_dynamic
+                    .into(),
+            ),
+        ),
+        (
+            "maxDescriptorSetUpdateAfterBindStorageBuffers",
+            Unsigned(
+                properties
+                    .max_descriptor_set_update_after_bind_storage_buffers
+                    .into(),
+            ),
+        ),
+        (
+            "maxDescriptorSetUpdateAfterBindStorageBuffersDynamic",
+            Unsigned(
+                properties
+                    .max_descriptor_set_update_after_bind_storage_buffers_dynamic
+                    .into(),
+            ),
+        ),
+        (
+            "maxDescriptorSetUpdateAfterBindSampledImages",
+            Unsigned(
+                properties
+                    .max_descriptor_set_update_after_bind_sampled_images
+                    .into(),
+            ),
+        ),
+        (
+            "maxDescriptorSetUpdateAfterBindStorageImages",
+            Unsigned(
+                properties
+                    .max_descriptor_set_update_after_bind_storage_images
+                    .into(),
+            ),
+        ),
+        (
+            "maxDescriptorSetUpdateAfterBindInputAttachments",
+            Unsigned(
+                properties
+                    .max_descriptor_set_update_after_bind_input_attachments
+                    .into(),
+            ),
+        ),
+        (
+            "supportedDepthResolveModes",
+            Flags(flag_names(
+                "VK_RESOLVE_MODE_",
+                properties.supported_depth_resolve_modes.as_raw(),
+                vk::ResolveModeFlags::from_raw,
+            )),
+        ),
+        (
+            "supportedStencilResolveModes",
+            Flags(flag_names(
+                "VK_RESOLVE_MODE_",
+                properties.supported_stencil_resolve_modes.as_raw(),
+                vk::ResolveModeFlags::from_raw,
+            )),
+        ),
+        (
+            "independentResolveNone",
+            Bool(properties.independent_resolve_none == vk::TRUE),
+        ),
+        (
+            "independentResolve",
+            Bool(properties.independent_resolve == vk::TRUE),
+        ),
+        (
+            "filterMinmaxSingleComponentFormats",
+            Bool(properties.filter_minmax_single_component_formats == vk::TRUE),
+        ),
+        (
+            "filterMinmaxImageComponentMapping",
+            Bool(properties.filter_minmax_image_component_mapping == vk::TRUE),
+        ),
+        (
+            "maxTimelineSemaphoreValueDifference",
+            Unsigned(properties.max_timeline_semaphore_value_difference),
+        ),
+        (
+            "framebufferIntegerColorSampleCounts",
+            SampleCounts(properties.framebuffer_integer_color_sample_counts),
+        ),
+    ]
+    .into_iter()
+    .map(|(name, value)| (name.to_string(), value))
+    .collect()
+}
+
+fn properties_1_3(
+    properties: &vk::PhysicalDeviceVulkan13Properties,
+) -> Vec<(String, PropertyValue)> {
+    use PropertyValue::*;
+    [
+        ("minSubgroupSize", Unsigned(properties.min_subgroup_size.into())),
+        ("maxSubgroupSize", Unsigned(properties.max_subgroup_size.into())),
+        ("maxComputeWorkgroupSubgroups", Unsigned(properties.max_compute_workgroup_subgroups.into())),
+        ("requiredSubgroupSizeStages", Flags(flag_names("VK_SHADER_STAGE_", properties.required_subgroup_size_stages.as_raw(), vk::ShaderStageFlags::from_raw))),
+        ("maxInlineUniformBlockSize", Unsigned(properties.max_inline_uniform_block_size.into())),
+        ("maxPerStageDescriptorInlineUniformBlocks", Unsigned(properties.max_per_stage_descriptor_inline_uniform_blocks.into())),
+        ("maxPerStageDescriptorUpdateAfterBindInlineUniformBlocks", Unsigned(properties.max_per_stage_descriptor_update_after_bind_inline_uniform_blocks.into())),
+        ("maxDescriptorSetInlineUniformBlocks", Unsigned(properties.max_descriptor_set_inline_uniform_blocks.into())),
+        ("maxDescriptorSetUpdateAfterBindInlineUniformBlocks", Unsigned(properties.max_descriptor_set_update_after_bind_inline_uniform_blocks.into())),
+        ("maxInlineUniformTotalSize", Unsigned(properties.max_inline_uniform_total_size.into())),
+        ("integerDotProduct8BitUnsignedAccelerated", Bool(properties.integer_dot_product8_bit_unsigned_accelerated == vk::TRUE)),
+        ("integerDotProduct8BitSignedAccelerated", Bool(properties.integer_dot_product8_bit_signed_accelerated == vk::TRUE)),
+        ("integerDotProduct8BitMixedSignednessAccelerated", Bool(properties.integer_dot_product8_bit_mixed_signedness_accelerated == vk::TRUE)),
+        ("integerDotProduct4x8BitPackedUnsignedAccelerated", Bool(properties.integer_dot_product4x8_bit_packed_unsigned_accelerated == vk::TRUE)),
+        ("integerDotProduct4x8BitPackedSignedAccelerated", Bool(properties.integer_dot_product4x8_bit_packed_signed_accelerated == vk::TRUE)),
+        ("integerDotProduct4x8BitPackedMixedSignednessAccelerated", Bool(properties.integer_dot_product4x8_bit_packed_mixed_signedness_accelerated == vk::TRUE)),
+        ("integerDotProduct16BitUnsignedAccelerated", Bool(properties.integer_dot_product16_bit_unsigned_accelerated == vk::TRUE)),
+        ("integerDotProduct16BitSignedAccelerated", Bool(properties.integer_dot_product16_bit_signed_accelerated == vk::TRUE)),
+        ("integerDotProduct16BitMixedSignednessAccelerated", Bool(properties.integer_dot_product16_bit_mixed_signedness_accelerated == vk::TRUE)),
+        ("integerDotProduct32BitUnsignedAccelerated", Bool(properties.integer_dot_product32_bit_unsigned_accelerated == vk::TRUE)),
+        ("integerDotProduct32BitSignedAccelerated", Bool(properties.integer_dot_product32_bit_signed_accelerated == vk::TRUE)),
+        ("integerDotProduct32BitMixedSignednessAccelerated", Bool(properties.integer_dot_product32_bit_mixed_signedness_accelerated == vk::TRUE)),
+        ("integerDotProduct64BitUnsignedAccelerated", Bool(properties.integer_dot_product64_bit_unsigned_accelerated == vk::TRUE)),
+        ("integerDotProduct64BitSignedAccelerated", Bool(properties.integer_dot_product64_bit_signed_accelerated == vk::TRUE)),
+        ("integerDotProduct64BitMixedSignednessAccelerated", Bool(properties.integer_dot_product64_bit_mixed_signedness_accelerated == vk::TRUE)),
+        ("integerDotProductAccumulatingSaturating8BitUnsignedAccelerated", Bool(properties.integer_dot_product_accumulating_saturating8_bit_unsigned_accelerated == vk::TRUE)),
+        ("integerDotProductAccumulatingSaturating8BitSignedAccelerated", Bool(properties.integer_dot_product_accumulating_saturating8_bit_signed_accelerated == vk::TRUE)),
+        ("integerDotProductAccumulatingSaturating8BitMixedSignednessAccelerated", Bool(properties.integer_dot_product_accumulating_saturating8_bit_mixed_signedness_accelerated == vk::TRUE)),
+        ("integerDotProductAccumulatingSaturating4x8BitPackedUnsignedAccelerated", Bool(properties.integer_dot_product_accumulating_saturating4x8_bit_packed_unsigned_accelerated == vk::TRUE)),
+        ("integerDotProductAccumulatingSaturating4x8BitPackedSignedAccelerated", Bool(properties.integer_dot_product_accumulating_saturating4x8_bit_packed_signed_accelerated == vk::TRUE)),
+        ("integerDotProductAccumulatingSaturating4x8BitPackedMixedSignednessAccelerated", Bool(properties.integer_dot_product_accumulating_saturating4x8_bit_packed_mixed_signedness_accelerated == vk::TRUE)),
+        ("integerDotProductAccumulatingSaturating16BitUnsignedAccelerated", Bool(properties.integer_dot_product_accumulating_saturating16_bit_unsigned_accelerated == vk::TRUE)),
+        ("integerDotProductAccumulatingSaturating16BitSignedAccelerated", Bool(properties.integer_dot_product_accumulating_saturating16_bit_signed_accelerated == vk::TRUE)),
+        ("integerDotProductAccumulatingSaturating16BitMixedSignednessAccelerated", Bool(properties.integer_dot_product_accumulating_saturating16_bit_mixed_signedness_accelerated == vk::TRUE)),
+        ("integerDotProductAccumulatingSaturating32BitUnsignedAccelerated", Bool(properties.integer_dot_product_accumulating_saturating32_bit_unsigned_accelerated == vk::TRUE)),
+        ("integerDotProductAccumulatingSaturating32BitSignedAccelerated", Bool(properties.integer_dot_product_accumulating_saturating32_bit_signed_accelerated == vk::TRUE)),
+        ("integerDotProductAccumulatingSaturating32BitMixedSignednessAccelerated", Bool(properties.integer_dot_product_accumulating_saturating32_bit_mixed_signedness_accelerated == vk::TRUE)),
+        ("integerDotProductAccumulatingSaturating64BitUnsignedAccelerated", Bool(properties.integer_dot_product_accumulating_saturating64_bit_unsigned_accelerated == vk::TRUE)),
+        ("integerDotProductAccumulatingSaturating64BitSignedAccelerated", Bool(properties.integer_dot_product_accumulating_saturating64_bit_signed_accelerated == vk::TRUE)),
+        ("integerDotProductAccumulatingSaturating64BitMixedSignednessAccelerated", Bool(properties.integer_dot_product_accumulating_saturating64_bit_mixed_signedness_accelerated == vk::TRUE)),
+        ("storageTexelBufferOffsetAlignmentBytes", Unsigned(properties.storage_texel_buffer_offset_alignment_bytes)),
+        ("storageTexelBufferOffsetSingleTexelAlignment", Bool(properties.storage_texel_buffer_offset_single_texel_alignment == vk::TRUE)),
+        ("uniformTexelBufferOffsetAlignmentBytes", Unsigned(properties.uniform_texel_buffer_offset_alignment_bytes)),
+        ("uniformTexelBufferOffsetSingleTexelAlignment", Bool(properties.uniform_texel_buffer_offset_single_texel_alignment == vk::TRUE)),
+        ("maxBufferSize", Unsigned(properties.max_buffer_size)),
+    ]
+    .into_iter()
+    .map(|(name, value)| (name.to_string(), value))
+    .collect()
+}
+
+fn properties_1_4(properties: &PhysicalDeviceVulkan14Properties) -> Vec<(String, PropertyValue)> {
+    use PropertyValue::*;
+    let bool = |value: vk::Bool32| Bool(value == vk::TRUE);
+    [
+        (
+            "lineSubPixelPrecisionBits",
+            Unsigned(properties.line_sub_pixel_precision_bits.into()),
+        ),
+        (
+            "maxVertexAttribDivisor",
+            Unsigned(properties.max_vertex_attrib_divisor.into()),
+        ),
+        (
+            "supportsNonZeroFirstInstance",
+            bool(properties.supports_non_zero_first_instance),
+        ),
+        (
+            "maxPushDescriptors",
+            Unsigned(properties.max_push_descriptors.into()),
+        ),
+        (
+            "dynamicRenderingLocalReadDepthStencilAttachments",
+            bool(properties.dynamic_rendering_local_read_depth_stencil_attachments),
+        ),
+        (
+            "dynamicRenderingLocalReadMultisampledAttachments",
+            bool(properties.dynamic_rendering_local_read_multisampled_attachments),
+        ),
+        (
+            "earlyFragmentMultisampleCoverageAfterSampleCounting",
+            bool(properties.early_fragment_multisample_coverage_after_sample_counting),
+        ),
+        (
+            "earlyFragmentSampleMaskTestBeforeSampleCounting",
+            bool(properties.early_fragment_sample_mask_test_before_sample_counting),
+        ),
+        (
+            "depthStencilSwizzleOneSupport",
+            bool(properties.depth_stencil_swizzle_one_support),
+        ),
+        (
+            "polygonModePointSize",
+            bool(properties.polygon_mode_point_size),
+        ),
+        (
+            "nonStrictSinglePixelWideLinesUseParallelogram",
+            bool(properties.non_strict_single_pixel_wide_lines_use_parallelogram),
+        ),
+        (
+            "nonStrictWideLinesUseParallelogram",
+            bool(properties.non_strict_wide_lines_use_parallelogram),
+        ),
+        (
+            "blockTexelViewCompatibleMultipleLayers",
+            bool(properties.block_texel_view_compatible_multiple_layers),
+        ),
+        (
+            "maxCombinedImageSamplerDescriptorCount",
+            Unsigned(
+                properties
+                    .max_combined_image_sampler_descriptor_count
+                    .into(),
+            ),
+        ),
+        (
+            "fragmentShadingRateClampCombinerInputs",
+            bool(properties.fragment_shading_rate_clamp_combiner_inputs),
+        ),
+        (
+            "defaultRobustnessStorageBuffers",
+            enum_name(
+                "VK_PIPELINE_ROBUSTNESS_BUFFER_BEHAVIOR_",
+                properties.default_robustness_storage_buffers.as_raw(),
+                &BUFFER_ROBUSTNESS,
+            ),
+        ),
+        (
+            "defaultRobustnessUniformBuffers",
+            enum_name(
+                "VK_PIPELINE_ROBUSTNESS_BUFFER_BEHAVIOR_",
+                properties.default_robustness_uniform_buffers.as_raw(),
+                &BUFFER_ROBUSTNESS,
+            ),
+        ),
+        (
+            "defaultRobustnessVertexInputs",
+            enum_name(
+                "VK_PIPELINE_ROBUSTNESS_BUFFER_BEHAVIOR_",
+                properties.default_robustness_vertex_inputs.as_raw(),
+                &BUFFER_ROBUSTNESS,
+            ),
+        ),
+        (
+            "defaultRobustnessImages",
+            enum_name(
+                "VK_PIPELINE_ROBUSTNESS_IMAGE_BEHAVIOR_",
+                properties.default_robustness_images.as_raw(),
+                &[
+                    "DEVICE_DEFAULT",
+                    "DISABLED",
+                    "ROBUST_IMAGE_ACCESS",
+                    "ROBUST_IMAGE_ACCESS_2",
+                ],
+            ),
+        ),
+        (
+            "copySrcLayoutCount",
+            Unsigned(properties.copy_src_layout_count.into()),
+        ),
+        (
+            "copyDstLayoutCount",
+            Unsigned(properties.copy_dst_layout_count.into()),
+        ),
+        (
+            "optimalTilingLayoutUUID",
+            hex(&properties.optimal_tiling_layout_uuid),
+        ),
+        (
+            "identicalMemoryTypeRequirements",
+            bool(properties.identical_memory_type_requirements),
+        ),
+    ]
+    .into_iter()
+    .map(|(name, value)| (name.to_string(), value))
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limit_names() {
+        let limits = limits(&vk::PhysicalDeviceLimits {
+            max_image_dimension2_d: 16384,
+            max_compute_work_group_size: [1024, 1024, 64],
+            framebuffer_color_sample_counts: vk::SampleCountFlags::TYPE_1
+                | vk::SampleCountFlags::TYPE_4,
+            ..Default::default()
+        });
+        assert_eq!(limits.len(), 106);
+        let details = DeviceDetails {
+            properties: vec![PropertyGroup {
+                name: "VkPhysicalDeviceLimits".to_string(),
+                properties: limits,
+            }],
+            ..Default::default()
+        };
+        assert_eq!(
+            details.limit("maxImageDimension2D"),
+            Some(&PropertyValue::Unsigned(16384))
+        );
+        assert_eq!(
+            details
+                .limit("maxComputeWorkGroupSize")
+                .map(ToString::to_string)
+                .as_deref(),
+            Some("[1024, 1024, 64]")
+        );
+        assert_eq!(
+            details
+                .limit("framebufferColorSampleCounts")
+                .map(ToString::to_string)
+                .as_deref(),
+            Some("1, 4")
+        );
+    }
+
+    #[test]
+    fn test_vulkan14_properties() {
+        let properties = properties_1_4(&PhysicalDeviceVulkan14Properties {
+            max_push_descriptors: 32,
+            ..Default::default()
+        });
+        let details = DeviceDetails {
+            properties: vec![PropertyGroup {
+                name: "VkPhysicalDeviceVulkan1xProperties".to_string(),
+                properties,
+            }],
+            ..Default::default()
+        };
+        assert_eq!(
+            details.limit("maxPushDescriptors"),
+            Some(&PropertyValue::Unsigned(32))
+        );
+        assert_eq!(
+            details
+                .limit("defaultRobustnessImages")
+                .map(ToString::to_string)
+                .as_deref(),
+            Some("VK_PIPELINE_ROBUSTNESS_IMAGE_BEHAVIOR_DEVICE_DEFAULT")
+        );
+        assert_eq!(
+            details
+                .limit("optimalTilingLayoutUUID")
+                .map(ToString::to_string),
+            Some("0".repeat(32))
+        );
+    }
+
+    #[test]
+    fn test_flag_names() {
+        let stages = vk::ShaderStageFlags::COMPUTE | vk::ShaderStageFlags::TASK_EXT;
+        assert_eq!(
+            flag_names(
+                "VK_SHADER_STAGE_",
+                stages.as_raw(),
+                vk::ShaderStageFlags::from_raw
+            ),
+            [
+                "VK_SHADER_STAGE_COMPUTE_BIT",
+                "VK_SHADER_STAGE_TASK_BIT_EXT"
+            ]
+        );
+        let properties = properties_1_1(&vk::PhysicalDeviceVulkan11Properties {
+            subgroup_supported_operations: vk::SubgroupFeatureFlags::BASIC
+                | vk::SubgroupFeatureFlags::SHUFFLE_RELATIVE,
+            ..Default::default()
+        });
+        let details = DeviceDetails {
+            properties: vec![PropertyGroup {
+                name: "VkPhysicalDeviceVulkan1xProperties".to_string(),
+                properties,
+            }],
+            ..Default::default()
+        };
+        assert_eq!(
+            details.limit("subgroupSupportedOperations"),
+            Some(&PropertyValue::Flags(vec![
+                "VK_SUBGROUP_FEATURE_BASIC_BIT".to_string(),
+                "VK_SUBGROUP_FEATURE_SHUFFLE_RELATIVE_BIT".to_string(),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_feature_names() {
+        let features = vk::PhysicalDeviceFeatures {
+            texture_compression_bc: vk::TRUE,
+            ..Default::default()
+        };
+        let details = DeviceDetails {
+            features: vec![FeatureGroup {
+                name: "VkPhysicalDeviceFeatures".to_string(),
+                features: features_1_0(&features),
+            }],
+            ..Default::default()
+        };
+        assert_eq!(details.feature("textureCompressionBC"), Some(true));
+        assert_eq!(details.feature("textureCompressionASTC_LDR"), Some(false));
+        assert_eq!(details.feature("sparseResidencyImage2D"), Some(false));
+        assert_eq!(details.feature("noSuchFeature"), None);
+    }
+}
@@ -1,12 +1,26 @@
 use ash::Instance;
 use ash::vk;
+use ash::vk::PhysicalDevicePortabilitySubsetFeaturesKHR;
+use ash::vk::PhysicalDevicePortabilitySubsetPropertiesKHR;
 use ash::vk::PhysicalDeviceProperties2;
+use ash::vk::PhysicalDeviceShaderCoreBuiltinsPropertiesARM;
 use ash::vk::PhysicalDeviceShaderCoreProperties2AMD;
 use ash::vk::PhysicalDeviceShaderCorePropertiesAMD;
+use ash::vk::PhysicalDeviceShaderCorePropertiesARM;
 use ash::vk::PhysicalDeviceShaderSMBuiltinsPropertiesNV;
+use ash::vk::PhysicalDeviceSubgroupSizeControlProperties;
+use ash::vk::PhysicalDeviceTilePropertiesFeaturesQCOM;
 use std::ffi::CStr;
-
+use std::path::PathBuf;
+
+use crate::architecture::{Architecture, intel_execution_units};
+use crate::drm::DrmInfo;
+use crate::kernel_stats::KernelStats;
+use crate::pcie::PcieLink;
+use crate::sensors::Sensors;
+use crate::sysfs::{PciAddress, SysRoot};
 use crate::vendor::Vendor;
+use crate::virtualization::LayeredDriver;
 
 /// Represents a physical GPU device.
 #[derive(Debug)]
@@ -17,8 +31,18 @@ pub struct Device {
     pub device_id: u32,
     pub vendor_id: u32,
     pub driver_name: String,
+    pub driver_id: vk::DriverId,
     pub driver_info: String,
     pub api_version: String,
+    pub architecture: Option<Architecture>,
+    pub layered_driver: Option<LayeredDriver>,
+    pub drm: Option<DrmInfo>,
+    pub pci_address: Option<PciAddress>,
+    /// The device's sysfs directory, e.g. `/sys/bus/pci/devices/0000:01:00.0`.
+    pub sysfs_dir: Option<PathBuf>,
+    pub sensors: Option<Sensors>,
+    pub kernel_stats: Option<KernelStats>,
+    pub pcie_link: Option<PcieLink>,
     // VRAM:
     pub heapbudget: Option<u64>,
     pub heapsize: u64,
@@ -28,7 +52,7 @@ pub struct Device {
 /// Contains various characteristics of a GPU.
 /// Vendor-specific properties are stored as Options.
 /// Also includes some general device limits.
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct GPUCharacteristics {
     /// Memory pressure as computed from VRAM usage (0.0 to 1.0)
     pub memory_pressure: Option<f32>,
@@ -44,6 +68,23 @@ pub struct GPUCharacteristics {
     // NVIDIA-specific properties.
     pub streaming_multiprocessors: Option<u32>,
     pub warps_per_sm: Option<u32>,
+    // ARM-specific properties.
+    pub shader_cores: Option<u32>,
+    pub warps_per_core: Option<u32>,
+    pub pixel_rate: Option<u32>,
+    pub texel_rate: Option<u32>,
+    pub fma_rate: Option<u32>,
+    // Intel-specific properties.
+    pub execution_units: Option<u32>,
+    pub xe_cores: Option<u32>,
+    pub min_subgroup_size: Option<u32>,
+    pub max_subgroup_size: Option<u32>,
+    // Qualcomm-specific properties.
+    pub tile_memory_size: Option<u64>,
+    pub supports_tile_properties: Option<bool>,
+    // Portability subset properties (MoltenVK on any vendor).
+    pub unsupported_portability_features: Option<Vec<&'static str>>,
+    pub min_vertex_input_binding_stride_alignment: Option<u32>,
     // General device limits.
     pub max_image_dimension_2d: u32,
     pub max_compute_shared_memory_size: u32,
@@ -56,19 +97,36 @@ pub struct GPUCharacteristics {
 
 impl Device {
     /// Constructs a new `PhysicalDevice` by querying Vulkan properties.
-    pub fn new(instance: &Instance, physical_device: vk::PhysicalDevice) -> Self {
+    /// `instance_version` is the API version the instance was created with;
+    /// core structures newer than it are not queried.
+    pub fn new(
+        instance: &Instance,
+        instance_version: u32,
+        physical_device: vk::PhysicalDevice,
+    ) -> Self {
         // Get the core properties and limits.
         let physical_device_properties: vk::PhysicalDeviceProperties =
             unsafe { instance.get_physical_device_properties(physical_device) };
         let limits = physical_device_properties.limits;
+        let query_version = physical_device_properties.api_version.min(instance_version);
+
+        let extensions = unsafe {
+            instance
+                .enumerate_device_extension_properties(physical_device)
+                .unwrap_or_default()
+        };
 
-        // Query additional driver properties.
+        // Query additional driver properties, core since Vulkan 1.2.
         let mut driver_properties: vk::PhysicalDeviceDriverProperties =
             vk::PhysicalDeviceDriverProperties::default();
-        let mut properties2: PhysicalDeviceProperties2 =
-            PhysicalDeviceProperties2::default().push_next(&mut driver_properties);
-        unsafe {
-            instance.get_physical_device_properties2(physical_device, &mut properties2);
+        if query_version >= vk::API_VERSION_1_2
+            || has_extension(&extensions, vk::KHR_DRIVER_PROPERTIES_NAME)
+        {
+            let mut properties2: PhysicalDeviceProperties2 =
+                PhysicalDeviceProperties2::default().push_next(&mut driver_properties);
+            unsafe {
+                instance.get_physical_device_properties2(physical_device, &mut properties2);
+            }
         }
 
         let vendor_id = physical_device_properties.vendor_id;
@@ -93,69 +151,80 @@ impl Device {
                 .unwrap_or(c"Unknown"),
         );
 
-        let extensions = unsafe {
-            instance
-                .enumerate_device_extension_properties(physical_device)
-                .unwrap_or_default()
-        };
+        let layered_driver =
+            LayeredDriver::detect(driver_properties.driver_id, &driver_name, &device_name);
 
-        // Query VRAM details.
-        let mut memory_properties2 = vk::PhysicalDeviceMemoryProperties2::default();
-        unsafe {
-            instance
-                .get_physical_device_memory_properties2(physical_device, &mut memory_properties2);
-        }
-        let memory_properties = memory_properties2.memory_properties;
-        let vram_heap_index = (0..memory_properties.memory_heap_count)
-            .find(|&i| {
-                memory_properties.memory_heaps[i as usize]
-                    .flags
-                    .contains(vk::MemoryHeapFlags::DEVICE_LOCAL)
+        // Query the PCI location and the Linux DRM nodes backing the device.
+        let sysroot = SysRoot::from_env();
+        let pci_address = if has_extension(&extensions, vk::EXT_PCI_BUS_INFO_NAME) {
+            let mut pci_bus_info = vk::PhysicalDevicePCIBusInfoPropertiesEXT::default();
+            let mut pci_properties2 =
+                PhysicalDeviceProperties2::default().push_next(&mut pci_bus_info);
+            unsafe {
+                instance.get_physical_device_properties2(physical_device, &mut pci_properties2);
+            }
+            Some(PciAddress {
+                domain: pci_bus_info.pci_domain,
+                bus: pci_bus_info.pci_bus,
+                device: pci_bus_info.pci_device,
+                function: pci_bus_info.pci_function,
             })
-            .unwrap_or(0);
-        let heapsize = memory_properties.memory_heaps[vram_heap_index as usize].size;
+        } else {
+            None
+        };
 
-        let heapbudget = if has_extension(&extensions, vk::EXT_MEMORY_BUDGET_NAME) {
-            let mut memory_budget = vk::PhysicalDeviceMemoryBudgetPropertiesEXT::default();
-            let mut memory_properties2 =
-                vk::PhysicalDeviceMemoryProperties2::default().push_next(&mut memory_budget);
+        let drm = if has_extension(&extensions, vk::EXT_PHYSICAL_DEVICE_DRM_NAME) {
+            let mut drm_properties = vk::PhysicalDeviceDrmPropertiesEXT::default();
+            let mut drm_properties2 =
+                PhysicalDeviceProperties2::default().push_next(&mut drm_properties);
             unsafe {
-                instance.get_physical_device_memory_properties2(
-                    physical_device,
-                    &mut memory_properties2,
-                );
+                instance.get_physical_device_properties2(physical_device, &mut drm_properties2);
             }
-            Some(memory_budget.heap_budget[vram_heap_index as usize])
+            let primary = (drm_properties.has_primary == vk::TRUE)
+                .then_some((drm_properties.primary_major, drm_properties.primary_minor));
+            let render = (drm_properties.has_render == vk::TRUE)
+                .then_some((drm_properties.render_major, drm_properties.render_minor));
+            Some(DrmInfo::resolve(&sysroot, primary, render))
         } else {
             None
         };
 
-        let memory_pressure = heapbudget.and_then(|budget| {
-            if heapsize > 0 && budget <= heapsize {
-                Some((heapsize - budget) as f32 / heapsize as f32)
-            } else {
-                None
-            }
+        let sysfs_dir = pci_address
+            .map(|address| address.sysfs_dir(&sysroot))
+            .filter(|dir| dir.is_dir())
+            .or_else(|| drm.as_ref().and_then(|drm| drm.device_dir(&sysroot)));
+        let pci_address = pci_address.or_else(|| {
+            sysfs_dir
+                .as_ref()
+                .and_then(|dir| dir.canonicalize().ok())
+                .and_then(|dir| dir.file_name()?.to_str()?.parse().ok())
         });
+        let sensors = sysfs_dir.as_deref().and_then(Sensors::read);
+        let kernel_stats = sysfs_dir
+            .as_deref()
+            .and_then(|dir| KernelStats::read(&sysroot, dir, pci_address));
+        let pcie_link = sysfs_dir.as_deref().and_then(PcieLink::read);
+
+        // Query VRAM details.
+        let mut memory_properties2 = vk::PhysicalDeviceMemoryProperties2::default();
+        unsafe {
+            instance
+                .get_physical_device_memory_properties2(physical_device, &mut memory_properties2);
+        }
+        let memory_properties = memory_properties2.memory_properties;
+        let (heapsize, heapbudget) = query_vram_heap(
+            instance,
+            physical_device,
+            &memory_properties,
+            has_extension(&extensions, vk::EXT_MEMORY_BUDGET_NAME),
+        );
+        let memory_pressure = memory_pressure(kernel_stats, heapsize, heapbudget);
 
         // Query queue family properties.
         let queue_families =
             unsafe { instance.get_physical_device_queue_family_properties(physical_device) };
-        let mut dedicated_transfer_queue = false;
-        let mut dedicated_async_compute_queue = false;
-        for qf in queue_families.iter() {
-            let flags = qf.queue_flags;
-            if flags.contains(vk::QueueFlags::TRANSFER)
-                && !(flags.contains(vk::QueueFlags::GRAPHICS)
-                    || flags.contains(vk::QueueFlags::COMPUTE))
-            {
-                dedicated_transfer_queue = true;
-            }
-            if flags.contains(vk::QueueFlags::COMPUTE) && !flags.contains(vk::QueueFlags::GRAPHICS)
-            {
-                dedicated_async_compute_queue = true;
-            }
-        }
+        let (dedicated_transfer_queue, dedicated_async_compute_queue) =
+            dedicated_queues(queue_families.iter().map(|family| family.queue_flags));
 
         // Check for ray tracing support via device extensions.
         let supports_ray_tracing = has_extension(&extensions, vk::KHR_RAY_TRACING_PIPELINE_NAME)
@@ -174,6 +243,19 @@ impl Device {
             wavefront_size: None,
             streaming_multiprocessors: None,
             warps_per_sm: None,
+            shader_cores: None,
+            warps_per_core: None,
+            pixel_rate: None,
+            texel_rate: None,
+            fma_rate: None,
+            execution_units: None,
+            xe_cores: None,
+            min_subgroup_size: None,
+            max_subgroup_size: None,
+            tile_memory_size: None,
+            supports_tile_properties: None,
+            unsupported_portability_features: None,
+            min_vertex_input_binding_stride_alignment: None,
             // General limits:
             max_image_dimension_2d: limits.max_image_dimension2_d,
             max_compute_shared_memory_size: limits.max_compute_shared_memory_size,
@@ -232,11 +314,108 @@ impl Device {
                 characteristics.streaming_multiprocessors = Some(sm_builtins.shader_sm_count);
                 characteristics.warps_per_sm = Some(sm_builtins.shader_warps_per_sm);
             }
+            Vendor::ARM => {
+                if has_extension(&extensions, vk::ARM_SHADER_CORE_BUILTINS_NAME) {
+                    let mut core_builtins =
+                        PhysicalDeviceShaderCoreBuiltinsPropertiesARM::default();
+                    let mut arm_properties2 =
+                        PhysicalDeviceProperties2::default().push_next(&mut core_builtins);
+                    unsafe {
+                        instance
+                            .get_physical_device_properties2(physical_device, &mut arm_properties2);
+                    }
+                    characteristics.shader_cores = Some(core_builtins.shader_core_count);
+                    characteristics.warps_per_core = Some(core_builtins.shader_warps_per_core);
+                }
+
+                if has_extension(&extensions, vk::ARM_SHADER_CORE_PROPERTIES_NAME) {
+                    let mut core_properties = PhysicalDeviceShaderCorePropertiesARM::default();
+                    let mut arm_properties2 =
+                        PhysicalDeviceProperties2::default().push_next(&mut core_properties);
+                    unsafe {
+                        instance
+                            .get_physical_device_properties2(physical_device, &mut arm_properties2);
+                    }
+                    characteristics.pixel_rate = Some(core_properties.pixel_rate);
+                    characteristics.texel_rate = Some(core_properties.texel_rate);
+                    characteristics.fma_rate = Some(core_properties.fma_rate);
+                }
+            }
+            Vendor::Intel => {
+                if let Some((execution_units, xe_cores)) = intel_execution_units(device_id) {
+                    characteristics.execution_units = Some(execution_units);
+                    characteristics.xe_cores = xe_cores;
+                }
+
+                if query_version >= vk::API_VERSION_1_3
+                    || has_extension(&extensions, vk::EXT_SUBGROUP_SIZE_CONTROL_NAME)
+                {
+                    let mut subgroup_size_control =
+                        PhysicalDeviceSubgroupSizeControlProperties::default();
+                    let mut intel_properties2 =
+                        PhysicalDeviceProperties2::default().push_next(&mut subgroup_size_control);
+                    unsafe {
+                        instance.get_physical_device_properties2(
+                            physical_device,
+                            &mut intel_properties2,
+                        );
+                    }
+                    characteristics.min_subgroup_size =
+                        Some(subgroup_size_control.min_subgroup_size);
+                    characteristics.max_subgroup_size =
+                        Some(subgroup_size_control.max_subgroup_size);
+                }
+            }
+            Vendor::Qualcomm | Vendor::QualcommPartner => {
+                if has_extension(&extensions, QCOM_TILE_MEMORY_HEAP_NAME) {
+                    characteristics.tile_memory_size = (0..memory_properties.memory_heap_count)
+                        .map(|i| memory_properties.memory_heaps[i as usize])
+                        .find(|heap| heap.flags.contains(MEMORY_HEAP_TILE_MEMORY_QCOM))
+                        .map(|heap| heap.size);
+                }
+
+                if has_extension(&extensions, vk::QCOM_TILE_PROPERTIES_NAME) {
+                    let mut tile_features = PhysicalDeviceTilePropertiesFeaturesQCOM::default();
+                    let mut features2 =
+                        vk::PhysicalDeviceFeatures2::default().push_next(&mut tile_features);
+                    unsafe {
+                        instance.get_physical_device_features2(physical_device, &mut features2);
+                    }
+                    characteristics.supports_tile_properties =
+                        Some(tile_features.tile_properties == vk::TRUE);
+                }
+            }
             _ => {
                 // For other vendors, vendor-specific fields remain None.
             }
         };
 
+        // MoltenVK reports the vendor of the GPU underneath, Intel and AMD
+        // included, so the portability subset is keyed on the extension.
+        if has_extension(&extensions, vk::KHR_PORTABILITY_SUBSET_NAME) {
+            let mut portability_features = PhysicalDevicePortabilitySubsetFeaturesKHR::default();
+            let mut features2 =
+                vk::PhysicalDeviceFeatures2::default().push_next(&mut portability_features);
+            unsafe {
+                instance.get_physical_device_features2(physical_device, &mut features2);
+            }
+            characteristics.unsupported_portability_features =
+                Some(unsupported_portability_features(&portability_features));
+
+            let mut portability_properties =
+                PhysicalDevicePortabilitySubsetPropertiesKHR::default();
+            let mut portability_properties2 =
+                PhysicalDeviceProperties2::default().push_next(&mut portability_properties);
+            unsafe {
+                instance
+                    .get_physical_device_properties2(physical_device, &mut portability_properties2);
+            }
+            characteristics.min_vertex_input_binding_stride_alignment =
+                Some(portability_properties.min_vertex_input_binding_stride_alignment);
+        }
+
+        let architecture = Architecture::detect(vendor, device_id, &device_name, &characteristics);
+
         Device {
             vendor,
             device_name,
@@ -244,13 +423,118 @@ impl Device {
             device_id,
             vendor_id,
             driver_name,
+            driver_id: driver_properties.driver_id,
             driver_info,
             api_version,
+            architecture,
+            layered_driver,
+            drm,
+            pci_address,
+            sysfs_dir,
+            sensors,
+            kernel_stats,
+            pcie_link,
             heapbudget,
             heapsize,
             characteristics,
         }
     }
+
+    /// Re-queries the values that change while the device is in use:
+    /// the memory budget, kernel statistics and sensors.
+    pub fn refresh(&mut self, instance: &Instance, physical_device: vk::PhysicalDevice) {
+        let memory_properties =
+            unsafe { instance.get_physical_device_memory_properties(physical_device) };
+        // A budget is only ever reported when `VK_EXT_memory_budget` is present.
+        let (heapsize, heapbudget) = query_vram_heap(
+            instance,
+            physical_device,
+            &memory_properties,
+            self.heapbudget.is_some(),
+        );
+
+        if let Some(sysfs_dir) = &self.sysfs_dir {
+            self.sensors = Sensors::read(sysfs_dir);
+            self.kernel_stats =
+                KernelStats::read(&SysRoot::from_env(), sysfs_dir, self.pci_address);
+            self.pcie_link = PcieLink::read(sysfs_dir);
+        }
+        self.heapsize = heapsize;
+        self.heapbudget = heapbudget;
+        self.characteristics.memory_pressure =
+            memory_pressure(self.kernel_stats, heapsize, heapbudget);
+    }
+}
+
+/// Returns the size of the first device-local heap and, if `has_memory_budget`,
+/// its budget from `VK_EXT_memory_budget`.
+fn query_vram_heap(
+    instance: &Instance,
+    physical_device: vk::PhysicalDevice,
+    memory_properties: &vk::PhysicalDeviceMemoryProperties,
+    has_memory_budget: bool,
+) -> (u64, Option<u64>) {
+    let vram_heap_index = (0..memory_properties.memory_heap_count)
+        .find(|&i| {
+            memory_properties.memory_heaps[i as usize]
+                .flags
+                .contains(vk::MemoryHeapFlags::DEVICE_LOCAL)
+        })
+        .unwrap_or(0);
+    let heapsize = memory_properties.memory_heaps[vram_heap_index as usize].size;
+
+    let heapbudget = if has_memory_budget {
+        let mut memory_budget = vk::PhysicalDeviceMemoryBudgetPropertiesEXT::default();
+        let mut memory_properties2 =
+            vk::PhysicalDeviceMemoryProperties2::default().push_next(&mut memory_budget);
+        unsafe {
+            instance
+                .get_physical_device_memory_properties2(physical_device, &mut memory_properties2);
+        }
+        Some(memory_budget.heap_budget[vram_heap_index as usize])
+    } else {
+        None
+    };
+
+    (heapsize, heapbudget)
+}
+
+/// Prefers the kernel's system-wide VRAM usage over this process' budget.
+fn memory_pressure(
+    kernel_stats: Option<KernelStats>,
+    heapsize: u64,
+    heapbudget: Option<u64>,
+) -> Option<f32> {
+    kernel_stats
+        .and_then(|stats| stats.vram_usage(heapsize))
+        .or_else(|| {
+            heapbudget.and_then(|budget| {
+                if heapsize > 0 && budget <= heapsize {
+                    Some((heapsize - budget) as f32 / heapsize as f32)
+                } else {
+                    None
+                }
+            })
+        })
+}
+
+/// Whether any queue family is transfer-only and whether any is compute
+/// without graphics.
+pub(crate) fn dedicated_queues(families: impl IntoIterator<Item = vk::QueueFlags>) -> (bool, bool) {
+    let mut dedicated_transfer_queue = false;
+    let mut dedicated_async_compute_queue = false;
+    for flags in families {
+        if flags.contains(vk::QueueFlags::TRANSFER)
+            && !(flags.contains(vk::QueueFlags::GRAPHICS)
+                || flags.contains(vk::QueueFlags::COMPUTE))
+        {
+            dedicated_transfer_queue = true;
+        }
+        if flags.contains(vk::QueueFlags::COMPUTE) && !flags.contains(vk::QueueFlags::GRAPHICS) {
+            dedicated_async_compute_queue = true;
+        }
+    }
+    (dedicated_transfer_queue, dedicated_async_compute_queue)
 }
 
 fn has_extension(extensions: &[vk::ExtensionProperties], extension_name: &CStr) -> bool {
@@ -261,6 +545,52 @@ fn has_extension(extensions: &[vk::ExtensionProperties], extension_name: &CStr)
     })
 }
 
+/// `VK_QCOM_tile_memory_heap` is newer than the bundled Vulkan headers.
+const QCOM_TILE_MEMORY_HEAP_NAME: &CStr = c"VK_QCOM_tile_memory_heap";
+const MEMORY_HEAP_TILE_MEMORY_QCOM: vk::MemoryHeapFlags = vk::MemoryHeapFlags::from_raw(0x8);
+
+/// Lists the portability subset features that the implementation does not support.
+fn unsupported_portability_features(
+    features: &PhysicalDevicePortabilitySubsetFeaturesKHR,
+) -> Vec<&'static str> {
+    [
+        (
+            features.constant_alpha_color_blend_factors,
+            "constantAlphaColorBlendFactors",
+        ),
+        (features.events, "events"),
+        (
+            features.image_view_format_reinterpretation,
+            "imageViewFormatReinterpretation",
+        ),
+        (features.image_view_format_swizzle, "imageViewFormatSwizzle"),
+        (features.image_view2_d_on3_d_image, "imageView2DOn3DImage"),
+        (features.multisample_array_image, "multisampleArrayImage"),
+        (
+            features.mutable_comparison_samplers,
+            "mutableComparisonSamplers",
+        ),
+        (features.point_polygons, "pointPolygons"),
+        (features.sampler_mip_lod_bias, "samplerMipLodBias"),
+        (features.separate_stencil_mask_ref, "separateStencilMaskRef"),
+        (
+            features.shader_sample_rate_interpolation_functions,
+            "shaderSampleRateInterpolationFunctions",
+        ),
+        (features.tessellation_isolines, "tessellationIsolines"),
+        (features.tessellation_point_mode, "tessellationPointMode"),
+        (features.triangle_fans, "triangleFans"),
+        (
+            features.vertex_attribute_access_beyond_stride,
+            "vertexAttributeAccessBeyondStride",
+        ),
+    ]
+    .into_iter()
+    .filter(|(supported, _)| *supported != vk::TRUE)
+    .map(|(_, name)| name)
+    .collect()
+}
+
 fn total_amd_compute_units(
     shader_engine_count: u32,
     shader_arrays_per_engine_count: u32,
@@ -361,11 +691,39 @@ mod tests {
         assert_eq!(DeviceType::from(99).name(), "Unknown");
     }
 
+    #[test]
+    fn test_memory_pressure() {
+        let gib = 1024 * 1024 * 1024;
+        assert_eq!(memory_pressure(None, 4 * gib, Some(3 * gib)), Some(0.25));
+        assert_eq!(memory_pressure(None, 4 * gib, None), None);
+        let kernel_stats = KernelStats {
+            vram_used: Some(2 * gib),
+            ..Default::default()
+        };
+        assert_eq!(
+            memory_pressure(Some(kernel_stats), 4 * gib, Some(3 * gib)),
+            Some(0.5)
+        );
+    }
+
     #[test]
     fn test_total_amd_compute_units() {
         assert_eq!(total_amd_compute_units(2, 2, 10), 40);
     }
 
+    #[test]
+    fn test_unsupported_portability_features() {
+        let features = PhysicalDevicePortabilitySubsetFeaturesKHR {
+            events: vk::TRUE,
+            triangle_fans: vk::FALSE,
+            ..Default::default()
+        };
+        let unsupported = unsupported_portability_features(&features);
+        assert!(unsupported.contains(&"triangleFans"));
+        assert!(!unsupported.contains(&"events"));
+        assert_eq!(unsupported.len(), 14);
+    }
+
     #[test]
     fn test_gpu_characteristics_defaults() {
         // Create dummy limits.
@@ -389,6 +747,19 @@ mod tests {
             wavefront_size: None,
             streaming_multiprocessors: None,
             warps_per_sm: None,
+            shader_cores: None,
+            warps_per_core: None,
+            pixel_rate: None,
+            texel_rate: None,
+            fma_rate: None,
+            execution_units: None,
+            xe_cores: None,
+            min_subgroup_size: None,
+            max_subgroup_size: None,
+            tile_memory_size: None,
+            supports_tile_properties: None,
+            unsupported_portability_features: None,
+            min_vertex_input_binding_stride_alignment: None,
             max_image_dimension_2d: limits.max_image_dimension2_d,
             max_compute_shared_memory_size: limits.max_compute_shared_memory_size,
             max_compute_work_group_invocations: limits.max_compute_work_group_invocations,
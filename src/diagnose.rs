@@ -0,0 +1,390 @@
+use ash::{Instance, vk};
+
+use crate::ascii_art::BRIGHT_RED;
+use crate::device::Device;
+use crate::environment;
+use crate::loader::{
+    self, IcdManifest, LayerActivation, LayerKind, LayerManifest, LoaderEnv, ManifestError,
+    SearchPath,
+};
+use crate::{ALIGNMENT, BOLD, DIM, EMPTY, RESET, is_ansi_supported};
+
+/// Prints the environment variables that affect Vulkan, where the loader
+/// looks for drivers and layers and what it finds there. This needs no
+/// instance, so it also works when instance creation fails.
+pub(crate) fn print_loader_report(env: &LoaderEnv) {
+    let use_ansi = is_ansi_supported();
+    let mut lines = environment_report(env, use_ansi);
+    lines.push(String::new());
+    lines.extend(driver_report(
+        &env.driver_search_paths(),
+        &env.driver_manifests(),
+        use_ansi,
+    ));
+    for kind in [LayerKind::Implicit, LayerKind::Explicit] {
+        lines.push(String::new());
+        lines.extend(layer_report(
+            env,
+            kind,
+            &env.layer_manifests(kind),
+            use_ansi,
+        ));
+    }
+    for line in lines {
+        println!("{}", line);
+    }
+}
+
+/// Prints which driver manifest each physical device most likely came from.
+pub(crate) fn print_device_drivers(
+    instance: &Instance,
+    instance_version: u32,
+    device_handles: &[vk::PhysicalDevice],
+    env: &LoaderEnv,
+) {
+    let devices = device_handles
+        .iter()
+        .map(|&handle| Device::new(instance, instance_version, handle))
+        .collect::<Vec<_>>();
+    let manifests = env
+        .driver_manifests()
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    for line in device_driver_report(&devices, &manifests, is_ansi_supported()) {
+        println!("{}", line);
+    }
+}
+
+fn heading(title: &str, use_ansi: bool) -> String {
+    if use_ansi {
+        format!("{}{}{}", BOLD, title, RESET)
+    } else {
+        title.to_string()
+    }
+}
+
+fn environment_report(env: &LoaderEnv, use_ansi: bool) -> Vec<String> {
+    let (dim, reset) = if use_ansi {
+        (DIM, RESET)
+    } else {
+        (EMPTY, EMPTY)
+    };
+    let mut lines = vec![heading("Environment", use_ansi)];
+    let variables = environment::set_variables(env);
+    if variables.is_empty() {
+        lines.push(format!(
+            "{}none of the well-known Vulkan variables are set",
+            ALIGNMENT
+        ));
+    }
+    for variable in variables {
+        lines.push(format!("{}{}={}", ALIGNMENT, variable.name, variable.value));
+        lines.push(format!(
+            "{0}{0}{1}{2}{3}",
+            ALIGNMENT, dim, variable.effect, reset
+        ));
+    }
+    lines
+}
+
+fn driver_report(
+    search: &[SearchPath],
+    manifests: &[Result<IcdManifest, ManifestError>],
+    use_ansi: bool,
+) -> Vec<String> {
+    let (red, reset) = if use_ansi {
+        (BRIGHT_RED, RESET)
+    } else {
+        (EMPTY, EMPTY)
+    };
+
+    let mut lines = vec![heading("Driver search paths", use_ansi)];
+    for entry in search {
+        let mut line = format!("{}{}", ALIGNMENT, entry.path.display());
+        if let Some(origin) = entry.origin {
+            line.push_str(&format!(" (from {})", origin));
+        }
+        if !entry.path.exists() {
+            line.push_str(" (missing)");
+        }
+        lines.push(line);
+    }
+
+    lines.push(String::new());
+    lines.push(heading("Driver manifests", use_ansi));
+    if manifests.is_empty() {
+        lines.push(format!("{}{}none found{}", ALIGNMENT, red, reset));
+    }
+    for manifest in manifests {
+        match manifest {
+            Ok(manifest) => {
+                lines.push(format!("{}{}", ALIGNMENT, manifest.path.display()));
+                let library = match &manifest.library {
+                    Some(path) if path.to_str() == Some(manifest.library_path.as_str()) => {
+                        manifest.library_path.clone()
+                    }
+                    Some(path) => format!("{} -> {}", manifest.library_path, path.display()),
+                    None => format!("{}{} (not found){}", red, manifest.library_path, reset),
+                };
+                lines.push(format!("{0}{0}library: {1}", ALIGNMENT, library));
+                lines.push(format!(
+                    "{0}{0}api version: {1}",
+                    ALIGNMENT,
+                    manifest.api_version.as_deref().unwrap_or("not declared")
+                ));
+                if let Some(arch) = &manifest.library_arch {
+                    lines.push(format!("{0}{0}arch: {1}-bit", ALIGNMENT, arch));
+                }
+                if manifest.is_portability_driver {
+                    lines.push(format!("{0}{0}portability driver", ALIGNMENT));
+                }
+            }
+            Err(error) => {
+                lines.push(format!("{}{}", ALIGNMENT, error.path.display()));
+                lines.push(format!(
+                    "{0}{0}{1}error: {2}{3}",
+                    ALIGNMENT, red, error.message, reset
+                ));
+            }
+        }
+    }
+    lines
+}
+
+fn layer_report(
+    env: &LoaderEnv,
+    kind: LayerKind,
+    layers: &[Result<LayerManifest, ManifestError>],
+    use_ansi: bool,
+) -> Vec<String> {
+    let (red, reset) = if use_ansi {
+        (BRIGHT_RED, RESET)
+    } else {
+        (EMPTY, EMPTY)
+    };
+    let title = match kind {
+        LayerKind::Implicit => "Implicit layers",
+        LayerKind::Explicit => "Explicit layers",
+    };
+
+    let mut lines = vec![heading(title, use_ansi)];
+    let searched = env
+        .layer_search_paths(kind)
+        .iter()
+        .filter(|search| search.path.exists())
+        .map(|search| search.path.display().to_string())
+        .collect::<Vec<_>>();
+    lines.push(format!(
+        "{}searched: {}",
+        ALIGNMENT,
+        if searched.is_empty() {
+            "no existing directories".to_string()
+        } else {
+            searched.join(", ")
+        }
+    ));
+    if layers.is_empty() {
+        lines.push(format!("{}none found", ALIGNMENT));
+    }
+
+    for layer in layers {
+        let layer = match layer {
+            Ok(layer) => layer,
+            Err(error) => {
+                lines.push(format!("{}{}", ALIGNMENT, error.path.display()));
+                lines.push(format!(
+                    "{0}{0}{1}error: {2}{3}",
+                    ALIGNMENT, red, error.message, reset
+                ));
+                continue;
+            }
+        };
+
+        let activation = env.layer_activation(layer);
+        let (state_color, state_reset) = match activation {
+            LayerActivation::Active | LayerActivation::EnabledBy(_) if use_ansi => (BOLD, RESET),
+            _ => (EMPTY, EMPTY),
+        };
+        lines.push(format!(
+            "{}{} ({}{}{})",
+            ALIGNMENT, layer.name, state_color, activation, state_reset
+        ));
+        lines.push(format!(
+            "{0}{0}manifest: {1}",
+            ALIGNMENT,
+            layer.path.display()
+        ));
+        match (&layer.library_path, &layer.library) {
+            (Some(library_path), Some(library)) => lines.push(format!(
+                "{0}{0}library: {1} -> {2}",
+                ALIGNMENT,
+                library_path,
+                library.display()
+            )),
+            (Some(library_path), None) => lines.push(format!(
+                "{0}{0}library: {1}{2} (not found){3}",
+                ALIGNMENT, red, library_path, reset
+            )),
+            (None, _) => lines.push(format!(
+                "{0}{0}meta layer: {1}",
+                ALIGNMENT,
+                layer.component_layers.join(", ")
+            )),
+        }
+        lines.push(format!(
+            "{0}{0}api version: {1}",
+            ALIGNMENT,
+            layer.api_version.as_deref().unwrap_or("not declared")
+        ));
+        for (label, variables) in [
+            ("enable", &layer.enable_environment),
+            ("disable", &layer.disable_environment),
+        ] {
+            for (name, value) in variables {
+                let current = match env.var(name) {
+                    Some(current) => format!("set to {}", current.to_string_lossy()),
+                    None => "not set".to_string(),
+                };
+                lines.push(format!(
+                    "{0}{0}{1}: {2}={3} ({4})",
+                    ALIGNMENT, label, name, value, current
+                ));
+            }
+        }
+    }
+    lines
+}
+
+fn device_driver_report(
+    devices: &[Device],
+    manifests: &[IcdManifest],
+    use_ansi: bool,
+) -> Vec<String> {
+    let mut lines = vec![String::new(), heading("Devices", use_ansi)];
+    for device in devices {
+        let source = match loader::match_driver(manifests, device.driver_id) {
+            Some(manifest) => manifest.path.display().to_string(),
+            None => "no matching manifest".to_string(),
+        };
+        lines.push(format!(
+            "{}{} ({}, Vulkan {}): {}",
+            ALIGNMENT, device.device_name, device.driver_name, device.api_version, source
+        ));
+    }
+    lines
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::loader::tests::loader_env;
+    use crate::sysfs::fixture::Fixture;
+
+    #[test]
+    fn test_driver_report() {
+        let fixture = Fixture::new("diagnose");
+        fixture.write(
+            "usr/share/vulkan/icd.d/radeon_icd.json",
+            r#"{"ICD": {"library_path": "libvulkan_radeon.so", "api_version": "1.3.278"}}"#,
+        );
+        fixture.write("usr/share/vulkan/icd.d/broken.json", "{");
+        let env = loader_env(&fixture, &[("VK_ADD_DRIVER_FILES", "/nonexistent.json")]);
+        let manifests = env.driver_manifests();
+
+        let lines = driver_report(&env.driver_search_paths(), &manifests, false);
+        assert_eq!(
+            lines[1],
+            "    /nonexistent.json (from VK_ADD_DRIVER_FILES) (missing)"
+        );
+        let radeon = lines
+            .iter()
+            .position(|line| line.ends_with("radeon_icd.json"))
+            .unwrap();
+        assert_eq!(
+            lines[radeon + 1],
+            "        library: libvulkan_radeon.so (not found)"
+        );
+        assert_eq!(lines[radeon + 2], "        api version: 1.3.278");
+        assert!(
+            lines
+                .iter()
+                .any(|line| line.starts_with("        error: EOF"))
+        );
+
+        let mut device = crate::tests::dummy_physical_device();
+        device.driver_name = "radv".to_string();
+        let manifests = manifests.into_iter().flatten().collect::<Vec<_>>();
+        let lines = device_driver_report(&[device], &manifests, false);
+        assert_eq!(
+            lines[2],
+            format!(
+                "    TestDevice (radv, Vulkan 1.2.3.4): {}",
+                fixture
+                    .path("usr/share/vulkan/icd.d/radeon_icd.json")
+                    .display()
+            )
+        );
+    }
+
+    #[test]
+    fn test_environment_report() {
+        let fixture = Fixture::new("diagnose-environment");
+        let lines = environment_report(&loader_env(&fixture, &[("DRI_PRIME", "1")]), false);
+        assert_eq!(
+            lines,
+            [
+                "Environment",
+                "    DRI_PRIME=1",
+                "        Makes Mesa report the selected GPU first (PRIME render offload)",
+            ]
+        );
+
+        let lines = environment_report(&loader_env(&fixture, &[]), false);
+        assert_eq!(
+            lines[1],
+            "    none of the well-known Vulkan variables are set"
+        );
+    }
+
+    #[test]
+    fn test_layer_report() {
+        let fixture = Fixture::new("diagnose-layers");
+        fixture.write(
+            "etc/vulkan/implicit_layer.d/obs.json",
+            r#"{"layer": {
+                "name": "VK_LAYER_OBS_HOOK",
+                "library_path": "../lib/libobs_vulkan.so",
+                "api_version": "1.3.0",
+                "disable_environment": {"DISABLE_VULKAN_OBS_CAPTURE": "1"}
+            }}"#,
+        );
+        fixture.write("etc/vulkan/lib/libobs_vulkan.so", "");
+        let env = loader_env(&fixture, &[("DISABLE_VULKAN_OBS_CAPTURE", "1")]);
+        let layers = env.layer_manifests(LayerKind::Implicit);
+
+        let lines = layer_report(&env, LayerKind::Implicit, &layers, false);
+        assert_eq!(lines[0], "Implicit layers");
+        assert_eq!(
+            lines[1],
+            format!(
+                "    searched: {}",
+                fixture.path("etc/vulkan/implicit_layer.d").display()
+            )
+        );
+        assert_eq!(
+            lines[2],
+            "    VK_LAYER_OBS_HOOK (disabled by DISABLE_VULKAN_OBS_CAPTURE)"
+        );
+        assert!(lines[4].starts_with("        library: ../lib/libobs_vulkan.so -> "));
+        assert_eq!(lines[5], "        api version: 1.3.0");
+        assert_eq!(
+            lines[6],
+            "        disable: DISABLE_VULKAN_OBS_CAPTURE=1 (set to 1)"
+        );
+
+        let lines = layer_report(&env, LayerKind::Explicit, &[], false);
+        assert_eq!(lines[1], "    searched: no existing directories");
+        assert_eq!(lines[2], "    none found");
+    }
+}
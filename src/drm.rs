@@ -0,0 +1,139 @@
+use std::path::PathBuf;
+
+use crate::sysfs::{SysRoot, link_name};
+
+/// A DRM character device identified by its major:minor numbers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DrmNode {
+    pub major: i64,
+    pub minor: i64,
+    /// Device node path, e.g. `/dev/dri/renderD128`, if it could be resolved.
+    pub path: Option<PathBuf>,
+}
+
+/// The DRM nodes behind a Vulkan device, as reported by
+/// `VK_EXT_physical_device_drm` and resolved through `/sys/dev/char`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DrmInfo {
+    pub primary: Option<DrmNode>,
+    pub render: Option<DrmNode>,
+    /// Kernel DRM driver bound to the device, e.g. `amdgpu` or `i915`.
+    pub kernel_driver: Option<String>,
+}
+
+impl DrmInfo {
+    /// Resolves the primary and render node numbers against `root`.
+    pub fn resolve(
+        root: &SysRoot,
+        primary: Option<(i64, i64)>,
+        render: Option<(i64, i64)>,
+    ) -> Self {
+        let primary = primary.map(|(major, minor)| resolve_node(root, major, minor));
+        let render = render.map(|(major, minor)| resolve_node(root, major, minor));
+        let kernel_driver = [&primary, &render].into_iter().flatten().find_map(|node| {
+            link_name(char_device_dir(root, node.major, node.minor).join("device/driver"))
+        });
+
+        Self {
+            primary,
+            render,
+            kernel_driver,
+        }
+    }
+
+    /// Returns the sysfs directory of the underlying (usually PCI) device.
+    pub fn device_dir(&self, root: &SysRoot) -> Option<PathBuf> {
+        [&self.render, &self.primary]
+            .into_iter()
+            .flatten()
+            .map(|node| char_device_dir(root, node.major, node.minor).join("device"))
+            .find(|dir| dir.is_dir())
+    }
+
+    /// Returns the resolved node paths, primary first.
+    pub fn node_paths(&self) -> impl Iterator<Item = String> + '_ {
+        [&self.primary, &self.render]
+            .into_iter()
+            .flatten()
+            .map(|node| match &node.path {
+                Some(path) => path.display().to_string(),
+                None => format!("{}:{}", node.major, node.minor),
+            })
+    }
+}
+
+fn char_device_dir(root: &SysRoot, major: i64, minor: i64) -> PathBuf {
+    root.sys().join("dev/char").join(format!("{major}:{minor}"))
+}
+
+fn resolve_node(root: &SysRoot, major: i64, minor: i64) -> DrmNode {
+    // `/sys/dev/char/226:128` links to `.../drm/renderD128`.
+    let path = link_name(char_device_dir(root, major, minor))
+        .map(|name| root.dev().join("dri").join(name));
+    DrmNode { major, minor, path }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::sysfs::fixture::Fixture;
+
+    const PCI_DEVICE: &str = "sys/devices/pci0000:00/0000:00:01.0/0000:01:00.0";
+
+    fn amdgpu_fixture() -> Fixture {
+        let fixture = Fixture::new("drm");
+        fixture.mkdir(&format!("{PCI_DEVICE}/drm/card1"));
+        fixture.mkdir(&format!("{PCI_DEVICE}/drm/renderD128"));
+        fixture.mkdir("sys/bus/pci/drivers/amdgpu");
+        fixture.symlink(
+            &format!("{PCI_DEVICE}/driver"),
+            "../../../../bus/pci/drivers/amdgpu",
+        );
+        for node in ["card1", "renderD128"] {
+            fixture.symlink(
+                &format!("{PCI_DEVICE}/drm/{node}/device"),
+                "../../../0000:01:00.0",
+            );
+        }
+        fixture.symlink(
+            "sys/dev/char/226:1",
+            "../../devices/pci0000:00/0000:00:01.0/0000:01:00.0/drm/card1",
+        );
+        fixture.symlink(
+            "sys/dev/char/226:128",
+            "../../devices/pci0000:00/0000:00:01.0/0000:01:00.0/drm/renderD128",
+        );
+        fixture
+    }
+
+    #[test]
+    fn test_resolve_nodes_and_driver() {
+        let fixture = amdgpu_fixture();
+        let root = fixture.root();
+        let drm = DrmInfo::resolve(&root, Some((226, 1)), Some((226, 128)));
+
+        assert_eq!(
+            drm.primary.as_ref().and_then(|node| node.path.clone()),
+            Some(root.dev().join("dri/card1"))
+        );
+        assert_eq!(
+            drm.render.as_ref().and_then(|node| node.path.clone()),
+            Some(root.dev().join("dri/renderD128"))
+        );
+        assert_eq!(drm.kernel_driver.as_deref(), Some("amdgpu"));
+        assert!(
+            drm.device_dir(&root)
+                .is_some_and(|dir| dir.join("driver").exists())
+        );
+    }
+
+    #[test]
+    fn test_unresolved_nodes_fall_back_to_numbers() {
+        let fixture = Fixture::new("drm-empty");
+        let drm = DrmInfo::resolve(&fixture.root(), None, Some((226, 129)));
+
+        assert!(drm.primary.is_none());
+        assert_eq!(drm.kernel_driver, None);
+        assert_eq!(drm.node_paths().collect::<Vec<_>>(), ["226:129"]);
+    }
+}
@@ -0,0 +1,204 @@
+use crate::loader::LoaderEnv;
+
+/// A well-known variable that changes which devices are reported or how.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KnownVariable {
+    pub name: &'static str,
+    pub effect: &'static str,
+}
+
+const fn known(name: &'static str, effect: &'static str) -> KnownVariable {
+    KnownVariable { name, effect }
+}
+
+pub const KNOWN_VARIABLES: &[KnownVariable] = &[
+    known(
+        "VK_DRIVER_FILES",
+        "Replaces the driver search with these manifests",
+    ),
+    known(
+        "VK_ICD_FILENAMES",
+        "Replaces the driver search with these manifests (older name of VK_DRIVER_FILES)",
+    ),
+    known(
+        "VK_ADD_DRIVER_FILES",
+        "Loads these driver manifests before the standard ones",
+    ),
+    known(
+        "VK_LOADER_DRIVERS_SELECT",
+        "Only loads drivers whose manifest file names match",
+    ),
+    known(
+        "VK_LOADER_DRIVERS_DISABLE",
+        "Skips drivers whose manifest file names match",
+    ),
+    known(
+        "VK_LAYER_PATH",
+        "Replaces the explicit layer search with these paths",
+    ),
+    known(
+        "VK_ADD_LAYER_PATH",
+        "Searches these paths for explicit layers first",
+    ),
+    known(
+        "VK_IMPLICIT_LAYER_PATH",
+        "Replaces the implicit layer search with these paths",
+    ),
+    known(
+        "VK_ADD_IMPLICIT_LAYER_PATH",
+        "Searches these paths for implicit layers first",
+    ),
+    known(
+        "VK_INSTANCE_LAYERS",
+        "Enables these layers in every instance",
+    ),
+    known(
+        "VK_LOADER_LAYERS_ENABLE",
+        "Forces on the layers matching these filters",
+    ),
+    known(
+        "VK_LOADER_LAYERS_DISABLE",
+        "Turns off the layers matching these filters",
+    ),
+    known("VK_LOADER_DEBUG", "Makes the loader log to stderr"),
+    known(
+        "VK_LOADER_DISABLE_INST_EXT_FILTER",
+        "Exposes instance extensions the loader does not know about",
+    ),
+    known(
+        "DRI_PRIME",
+        "Makes Mesa report the selected GPU first (PRIME render offload)",
+    ),
+    known(
+        "MESA_VK_DEVICE_SELECT",
+        "Makes Mesa's device-select layer report the selected GPU first, or lists GPUs",
+    ),
+    known(
+        "MESA_VK_DEVICE_SELECT_FORCE_DEFAULT_DEVICE",
+        "Hides every GPU but the selected one",
+    ),
+    known("NODEVICE_SELECT", "Disables Mesa's device-select layer"),
+    known(
+        "MESA_VK_VERSION_OVERRIDE",
+        "Changes the Vulkan version Mesa drivers report",
+    ),
+    known(
+        "AMD_VULKAN_ICD",
+        "Picks RADV or AMDVLK when both are installed (on distributions that support it)",
+    ),
+    known(
+        "RADV_PERFTEST",
+        "Enables experimental RADV features, which can change reported extensions",
+    ),
+    known("RADV_DEBUG", "Changes RADV behaviour for debugging"),
+    known(
+        "INTEL_DEBUG",
+        "Changes Intel driver behaviour for debugging",
+    ),
+    known(
+        "__NV_PRIME_RENDER_OFFLOAD",
+        "Makes the NVIDIA driver report its GPU first (PRIME render offload)",
+    ),
+    known(
+        "__NV_PRIME_RENDER_OFFLOAD_PROVIDER",
+        "Picks the GPU used for NVIDIA PRIME render offload",
+    ),
+    known(
+        "__VK_LAYER_NV_optimus",
+        "Hides NVIDIA (non_NVIDIA_only) or other (NVIDIA_only) GPUs",
+    ),
+    known(
+        "DXVK_FILTER_DEVICE_NAME",
+        "Restricts DXVK to GPUs whose name contains this text",
+    ),
+    known(
+        "LD_LIBRARY_PATH",
+        "Changes where the loader, drivers and layers are loaded from",
+    ),
+    known(
+        crate::sysfs::SYSROOT_ENV,
+        "Reads /sys, /dev and /proc from a captured copy",
+    ),
+];
+
+/// Prefixes of driver and loader options not listed individually.
+const KNOWN_PREFIXES: &[(&str, &str)] = &[
+    ("VK_LOADER_", "Vulkan loader option"),
+    ("MESA_VK_", "Mesa Vulkan option"),
+    ("RADV_", "RADV option"),
+    ("ANV_", "ANV (Intel) option"),
+    ("NVK_", "NVK option"),
+    ("DXVK_", "DXVK option"),
+    ("VKD3D_", "vkd3d-proton option"),
+];
+
+/// A set variable that is known to affect Vulkan.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetVariable {
+    pub name: String,
+    pub value: String,
+    pub effect: &'static str,
+}
+
+/// Returns the known variables that are set, in [`KNOWN_VARIABLES`] order,
+/// followed by other set variables with a known prefix.
+pub fn set_variables(env: &LoaderEnv) -> Vec<SetVariable> {
+    let mut set = KNOWN_VARIABLES
+        .iter()
+        .filter_map(|variable| {
+            Some(SetVariable {
+                name: variable.name.to_string(),
+                value: env.var(variable.name)?.to_string_lossy().into_owned(),
+                effect: variable.effect,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for (name, value) in env.vars() {
+        if KNOWN_VARIABLES.iter().any(|variable| variable.name == name) {
+            continue;
+        }
+        if let Some((_, effect)) = KNOWN_PREFIXES
+            .iter()
+            .find(|(prefix, _)| name.starts_with(prefix))
+        {
+            set.push(SetVariable {
+                name: name.to_string(),
+                value: value.to_string_lossy().into_owned(),
+                effect,
+            });
+        }
+    }
+    set
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::loader::tests::loader_env;
+    use crate::sysfs::fixture::Fixture;
+
+    #[test]
+    fn test_set_variables() {
+        let fixture = Fixture::new("environment");
+        let env = loader_env(
+            &fixture,
+            &[
+                ("RADV_PERFTEST", "gpl"),
+                ("DRI_PRIME", "1"),
+                ("RADV_FORCE_FAMILY", "navi21"),
+                ("VK_LOADER_DEBUG", ""),
+                ("HOME", "/home/user"),
+            ],
+        );
+
+        let set = set_variables(&env);
+        let names = set
+            .iter()
+            .map(|variable| variable.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["DRI_PRIME", "RADV_PERFTEST", "RADV_FORCE_FAMILY"]);
+        assert_eq!(set[1].value, "gpl");
+        assert_eq!(set[2].effect, "RADV option");
+    }
+}
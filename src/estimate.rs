@@ -0,0 +1,261 @@
+use crate::architecture::Architecture;
+use crate::device::Device;
+use crate::vendor::Vendor;
+
+/// Peak throughput derived from unit counts, per-unit ALU widths, and clocks
+/// (from sysfs when available, a table of known SKUs otherwise).
+/// These are theoretical figures, not measurements.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThroughputEstimate {
+    pub fp32_tflops: f64,
+    /// `None` where the FP16 rate differs within the architecture.
+    pub fp16_tflops: Option<f64>,
+    pub bandwidth_gb_per_s: Option<f64>,
+}
+
+/// Clock and memory configuration of a known retail SKU.
+/// SKUs sharing a device ID are told apart by their unit count.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Sku {
+    device_id: u32,
+    units: u32,
+    boost_clock_mhz: u32,
+    bus_width_bits: u32,
+    memory_gbps: f64,
+}
+
+const fn sku(
+    device_id: u32,
+    units: u32,
+    boost_clock_mhz: u32,
+    bus_width_bits: u32,
+    memory_gbps: f64,
+) -> Sku {
+    Sku {
+        device_id,
+        units,
+        boost_clock_mhz,
+        bus_width_bits,
+        memory_gbps,
+    }
+}
+
+const KNOWN_SKUS: &[Sku] = &[
+    // AMD, keyed by compute units.
+    sku(0x163F, 8, 1600, 128, 5.5),
+    sku(0x67DF, 36, 1340, 256, 8.0),
+    sku(0x73BF, 72, 2250, 256, 16.0),
+    sku(0x73BF, 80, 2250, 256, 16.0),
+    sku(0x73DF, 40, 2581, 192, 16.0),
+    sku(0x73FF, 32, 2589, 128, 16.0),
+    sku(0x744C, 84, 2400, 320, 20.0),
+    sku(0x744C, 96, 2500, 384, 20.0),
+    sku(0x7480, 32, 2655, 128, 18.0),
+    sku(0x7550, 56, 2520, 256, 20.0),
+    sku(0x7550, 64, 2970, 256, 20.0),
+    // NVIDIA, keyed by streaming multiprocessors.
+    sku(0x1E84, 40, 1770, 256, 14.0),
+    sku(0x2204, 82, 1695, 384, 19.5),
+    sku(0x2206, 68, 1710, 320, 19.0),
+    sku(0x2484, 46, 1725, 256, 14.0),
+    sku(0x2503, 28, 1777, 192, 15.0),
+    sku(0x2684, 128, 2520, 384, 21.0),
+    sku(0x2704, 76, 2505, 256, 22.4),
+    sku(0x2782, 60, 2610, 192, 21.0),
+    sku(0x2786, 46, 2475, 192, 21.0),
+    sku(0x2B85, 170, 2407, 512, 28.0),
+    sku(0x2C02, 84, 2617, 256, 30.0),
+    // Intel, keyed by execution units.
+    sku(0x56A0, 512, 2100, 256, 17.5),
+    sku(0x56A1, 448, 2050, 256, 16.0),
+    sku(0xE20B, 160, 2670, 192, 19.0),
+];
+
+/// Returns FP32 lanes per unit (compute unit, SM, EU) and the FP16:FP32 rate.
+/// RDNA 3 and later count dual-issue, which is how AMD quotes peak figures.
+const fn lanes_per_unit(architecture: Architecture) -> Option<(u32, Option<f64>)> {
+    match architecture {
+        // Vega packs two FP16 operations per lane, earlier GCN does not.
+        Architecture::Gcn => Some((64, None)),
+        Architecture::Cdna | Architecture::Rdna1 | Architecture::Rdna2 => Some((64, Some(2.0))),
+        Architecture::Rdna3 | Architecture::Rdna4 => Some((128, Some(2.0))),
+        Architecture::Kepler => Some((192, Some(1.0))),
+        Architecture::Maxwell => Some((128, Some(1.0))),
+        // Consumer Pascal (GP102 to GP108) runs FP16 at 1/64 rate.
+        Architecture::Pascal => Some((128, Some(1.0 / 64.0))),
+        Architecture::Volta | Architecture::Turing => Some((64, Some(2.0))),
+        Architecture::Hopper => Some((128, Some(2.0))),
+        Architecture::Ampere | Architecture::AdaLovelace | Architecture::Blackwell => {
+            Some((128, Some(1.0)))
+        }
+        Architecture::Gen9
+        | Architecture::Gen11
+        | Architecture::XeLp
+        | Architecture::XeLpg
+        | Architecture::XeHpg => Some((8, Some(2.0))),
+        Architecture::Xe2 => Some((16, Some(2.0))),
+        Architecture::Midgard
+        | Architecture::Bifrost
+        | Architecture::Valhall
+        | Architecture::Mali5thGen
+        | Architecture::Adreno5xx
+        | Architecture::Adreno6xx
+        | Architecture::Adreno7xx
+        | Architecture::Adreno8xx => None,
+    }
+}
+
+impl ThroughputEstimate {
+    /// Estimates peak throughput for a device, or `None` if the unit count,
+    /// ALU width or clock cannot be determined.
+    pub fn for_device(device: &Device) -> Option<Self> {
+        let characteristics = &device.characteristics;
+        let (units, lanes, fp16_ratio) = match device.vendor {
+            Vendor::ARM => {
+                // Mali reports its FMA rate directly.
+                let cores = characteristics.shader_cores?;
+                (cores, characteristics.fma_rate?, Some(2.0))
+            }
+            vendor => {
+                let units = match vendor {
+                    Vendor::AMD => characteristics
+                        .active_compute_units
+                        .filter(|&active| active > 0)
+                        .or(characteristics.compute_units)?,
+                    Vendor::Nvidia => characteristics.streaming_multiprocessors?,
+                    Vendor::Intel => characteristics.execution_units?,
+                    _ => return None,
+                };
+                let (lanes, fp16_ratio) = lanes_per_unit(device.architecture?)?;
+                (units, lanes, fp16_ratio)
+            }
+        };
+
+        let sku = find_sku(device.device_id, units);
+        Some(Self::from_parts(
+            units,
+            lanes,
+            fp16_ratio,
+            clock_mhz(device, sku)?,
+            sku.map(|sku| sku.bus_width_bits as f64 * sku.memory_gbps / 8.0),
+        ))
+    }
+
+    fn from_parts(
+        units: u32,
+        lanes: u32,
+        fp16_ratio: Option<f64>,
+        clock_mhz: u32,
+        bandwidth_gb_per_s: Option<f64>,
+    ) -> Self {
+        // One fused multiply-add counts as two floating point operations.
+        let fp32_tflops = units as f64 * lanes as f64 * 2.0 * clock_mhz as f64 / 1_000_000.0;
+        Self {
+            fp32_tflops,
+            fp16_tflops: fp16_ratio.map(|ratio| fp32_tflops * ratio),
+            bandwidth_gb_per_s,
+        }
+    }
+}
+
+/// Returns the peak core clock: the highest clock the kernel reports for
+/// this exact board (amdgpu DPM levels, i915/xe GT limits or devfreq), or
+/// the boost clock of the known SKU.
+fn clock_mhz(device: &Device, sku: Option<&Sku>) -> Option<u32> {
+    device
+        .sensors
+        .as_ref()
+        .and_then(|sensors| sensors.core_clock?.max_mhz)
+        .or(sku.map(|sku| sku.boost_clock_mhz))
+}
+
+fn find_sku(device_id: u32, units: u32) -> Option<&'static Sku> {
+    KNOWN_SKUS
+        .iter()
+        .find(|sku| sku.device_id == device_id && sku.units == units)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_parts() {
+        // Radeon RX 7900 XTX: 96 CUs at 2.5 GHz with dual-issue.
+        let estimate = ThroughputEstimate::from_parts(96, 128, Some(2.0), 2500, None);
+        assert!((estimate.fp32_tflops - 61.44).abs() < 0.01);
+        assert!((estimate.fp16_tflops.unwrap() - 122.88).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_sysfs_clock_over_sku() {
+        // Radeon RX 7900 XTX whose DPM levels top out above the SKU boost.
+        let mut device = crate::tests::dummy_physical_device();
+        device.vendor = Vendor::AMD;
+        device.device_id = 0x744C;
+        device.architecture = Some(Architecture::Rdna3);
+        device.characteristics.active_compute_units = Some(96);
+        let sku = find_sku(0x744C, 96);
+        device.sensors.as_mut().unwrap().core_clock = Some(crate::sensors::Clock {
+            current_mhz: Some(500),
+            max_mhz: Some(2900),
+        });
+        assert_eq!(clock_mhz(&device, sku), Some(2900));
+        device.sensors = None;
+        assert_eq!(clock_mhz(&device, sku), Some(2500));
+        assert_eq!(clock_mhz(&device, None), None);
+    }
+
+    #[test]
+    fn test_fp16_rates() {
+        let fp16 = |architecture| {
+            ThroughputEstimate::from_parts(28, 128, lanes_per_unit(architecture)?.1, 1000, None)
+                .fp16_tflops
+        };
+        // 28 Pascal SMs at 1 GHz: 7.168 TFLOPS FP32, 1/64 of it in FP16.
+        assert!((fp16(Architecture::Pascal).unwrap() - 0.112).abs() < 0.001);
+        assert_eq!(fp16(Architecture::Gcn), None);
+    }
+
+    #[test]
+    fn test_find_sku() {
+        let sku = find_sku(0x744C, 96).expect("7900 XTX should be known");
+        assert_eq!(sku.boost_clock_mhz, 2500);
+        assert_eq!(sku.bus_width_bits as f64 * sku.memory_gbps / 8.0, 960.0);
+        assert_eq!(
+            find_sku(0x744C, 84).map(|sku| sku.bus_width_bits),
+            Some(320)
+        );
+        assert_eq!(find_sku(0x744C, 1), None);
+    }
+
+    #[test]
+    fn test_mali_uses_sysfs_clock() {
+        // Mali-G610 MC4: 4 cores at 64 FMA per cycle, clocked by devfreq.
+        let mut device = crate::tests::dummy_physical_device();
+        device.vendor = Vendor::ARM;
+        device.characteristics.shader_cores = Some(4);
+        device.characteristics.fma_rate = Some(64);
+        let sensors = device.sensors.as_mut().unwrap();
+        sensors.core_clock = Some(crate::sensors::Clock {
+            current_mhz: Some(300),
+            max_mhz: Some(1000),
+        });
+        let estimate = ThroughputEstimate::for_device(&device).unwrap();
+        assert!((estimate.fp32_tflops - 0.512).abs() < 0.001);
+        assert_eq!(estimate.bandwidth_gb_per_s, None);
+
+        device.sensors = None;
+        assert_eq!(ThroughputEstimate::for_device(&device), None);
+    }
+
+    #[test]
+    fn test_lanes_per_unit() {
+        assert_eq!(
+            lanes_per_unit(Architecture::AdaLovelace),
+            Some((128, Some(1.0)))
+        );
+        assert_eq!(lanes_per_unit(Architecture::Xe2), Some((16, Some(2.0))));
+        assert_eq!(lanes_per_unit(Architecture::Valhall), None);
+    }
+}
@@ -0,0 +1,263 @@
+use ash::{Instance, vk};
+use std::{
+    error::Error,
+    fmt::Write as _,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    time::Duration,
+};
+
+use crate::device::Device;
+
+/// Address `--serve` listens on without an explicit address.
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:9835";
+
+const CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// How long a client may take to send its request or read the response.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Bytes read of the request line and headers; anything beyond is ignored.
+const MAX_REQUEST_BYTES: u64 = 8 * 1024;
+
+/// Serves `/metrics` until the process is stopped. Every scrape creates the
+/// devices anew, so the memory budget and sensors are always current.
+pub(crate) fn serve_metrics(
+    instance: &Instance,
+    instance_version: u32,
+    device_handles: &[vk::PhysicalDevice],
+    address: &str,
+) -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind(address)?;
+    eprintln!(
+        "Serving metrics on http://{}/metrics",
+        listener.local_addr()?
+    );
+
+    for stream in listener.incoming() {
+        let result = stream.and_then(|stream| {
+            handle_connection(stream, REQUEST_TIMEOUT, || {
+                let devices = device_handles
+                    .iter()
+                    .map(|&handle| Device::new(instance, instance_version, handle))
+                    .collect::<Vec<_>>();
+                render_metrics(&devices)
+            })
+        });
+        // A misbehaving client must not take the exporter down; the timeouts
+        // keep a silent one from blocking later scrapes.
+        if let Err(error) = result {
+            eprintln!("Failed to answer scrape: {}", error);
+        }
+    }
+    Ok(())
+}
+
+/// Answers a single HTTP request, rendering the metrics only for `/metrics`.
+fn handle_connection(
+    stream: TcpStream,
+    timeout: Duration,
+    metrics: impl FnOnce() -> String,
+) -> io::Result<()> {
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(timeout))?;
+    let mut reader = BufReader::new((&stream).take(MAX_REQUEST_BYTES));
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Drain the headers; requests to /metrics carry no body.
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        header.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (status, content_type, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some(path)) if path == "/metrics" || path.starts_with("/metrics?") => {
+            ("200 OK", CONTENT_TYPE, metrics())
+        }
+        (Some("GET"), Some(_)) => ("404 Not Found", "text/plain", "Not Found\n".to_string()),
+        _ => (
+            "405 Method Not Allowed",
+            "text/plain",
+            "Method Not Allowed\n".to_string(),
+        ),
+    };
+
+    let mut stream = &stream;
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+/// A gauge and how to read it from a device.
+struct Gauge {
+    name: &'static str,
+    unit: Option<&'static str>,
+    help: &'static str,
+    value: fn(&Device) -> Option<f64>,
+}
+
+const GAUGES: &[Gauge] = &[
+    Gauge {
+        name: "vkfetch_vram_heap_size_bytes",
+        unit: Some("bytes"),
+        help: "Size of the first device-local memory heap.",
+        value: |device| Some(device.heapsize as f64),
+    },
+    Gauge {
+        name: "vkfetch_vram_budget_bytes",
+        unit: Some("bytes"),
+        help: "Device-local heap budget reported by VK_EXT_memory_budget.",
+        value: |device| device.heapbudget.map(|budget| budget as f64),
+    },
+    Gauge {
+        name: "vkfetch_vram_used_bytes",
+        unit: Some("bytes"),
+        help: "System-wide VRAM usage reported by the kernel driver.",
+        value: |device| {
+            device
+                .kernel_stats
+                .and_then(|stats| stats.vram_used)
+                .map(|used| used as f64)
+        },
+    },
+    Gauge {
+        name: "vkfetch_memory_pressure_ratio",
+        unit: Some("ratio"),
+        help: "Fraction of device-local memory in use.",
+        value: |device| {
+            device
+                .characteristics
+                .memory_pressure
+                .map(|pressure| pressure as f64)
+        },
+    },
+    Gauge {
+        name: "vkfetch_gpu_busy_ratio",
+        unit: Some("ratio"),
+        help: "Fraction of time the GPU was busy, as reported by the kernel driver.",
+        value: |device| {
+            device
+                .kernel_stats
+                .and_then(|stats| stats.gpu_busy)
+                .map(|busy| busy as f64)
+        },
+    },
+];
+
+/// Renders every gauge of every device in the OpenMetrics text format.
+pub fn render_metrics(devices: &[Device]) -> String {
+    let mut output = String::new();
+    for gauge in GAUGES {
+        let samples = devices
+            .iter()
+            .filter_map(|device| Some((labels(device), (gauge.value)(device)?)))
+            .collect::<Vec<_>>();
+        if samples.is_empty() {
+            continue;
+        }
+
+        let _ = writeln!(output, "# TYPE {} gauge", gauge.name);
+        if let Some(unit) = gauge.unit {
+            let _ = writeln!(output, "# UNIT {} {}", gauge.name, unit);
+        }
+        let _ = writeln!(output, "# HELP {} {}", gauge.name, gauge.help);
+        for (labels, value) in samples {
+            let _ = writeln!(output, "{}{{{}}} {}", gauge.name, labels, value);
+        }
+    }
+    output.push_str("# EOF\n");
+    output
+}
+
+fn labels(device: &Device) -> String {
+    let pci_address = device
+        .pci_address
+        .map(|address| address.to_string())
+        .unwrap_or_default();
+    [
+        ("vendor", device.vendor.name().to_string()),
+        ("device", device.device_name.clone()),
+        ("device_id", format!("0x{:X}", device.device_id)),
+        ("driver", device.driver_name.clone()),
+        ("pci_address", pci_address),
+    ]
+    .iter()
+    .map(|(name, value)| format!("{}=\"{}\"", name, escape_label(value)))
+    .collect::<Vec<_>>()
+    .join(",")
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{io::Read, net::SocketAddr, thread};
+
+    fn request(address: SocketAddr, request: &'static str) -> thread::JoinHandle<String> {
+        thread::spawn(move || {
+            let mut stream = TcpStream::connect(address).unwrap();
+            stream.write_all(request.as_bytes()).unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        })
+    }
+
+    #[test]
+    fn test_render_metrics() {
+        let mut device = crate::tests::dummy_physical_device();
+        device.device_name = "Test \"GPU\"".to_string();
+        let metrics = render_metrics(&[device]);
+
+        assert!(metrics.contains("# TYPE vkfetch_vram_heap_size_bytes gauge\n"));
+        assert!(metrics.contains("# UNIT vkfetch_vram_heap_size_bytes bytes\n"));
+        assert!(metrics.contains(
+            "vkfetch_vram_budget_bytes{vendor=\"Unknown\",device=\"Test \\\"GPU\\\"\",\
+             device_id=\"0xDEADBEEF\",driver=\"TestDriver\",pci_address=\"0000:03:00.0\"} 8589934592\n"
+        ));
+        assert!(metrics.contains("vkfetch_gpu_busy_ratio{"));
+        assert!(metrics.ends_with("# EOF\n"));
+        assert_eq!(render_metrics(&[]), "# EOF\n");
+    }
+
+    #[test]
+    fn test_serves_metrics_over_http() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let client = request(address, "GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n");
+        let (stream, _) = listener.accept().unwrap();
+        handle_connection(stream, REQUEST_TIMEOUT, || "# EOF\n".to_string()).unwrap();
+        let response = client.join().unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains(CONTENT_TYPE));
+        assert!(response.ends_with("\r\n\r\n# EOF\n"));
+
+        let client = request(address, "GET / HTTP/1.1\r\n\r\n");
+        let (stream, _) = listener.accept().unwrap();
+        handle_connection(stream, REQUEST_TIMEOUT, || unreachable!()).unwrap();
+        assert!(client.join().unwrap().starts_with("HTTP/1.1 404 Not Found"));
+    }
+
+    #[test]
+    fn test_silent_client_times_out() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let _client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (stream, _) = listener.accept().unwrap();
+        let result = handle_connection(stream, Duration::from_millis(50), || unreachable!());
+        assert!(result.is_err());
+    }
+}
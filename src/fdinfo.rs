@@ -0,0 +1,276 @@
+use std::{collections::HashSet, fs, path::Path};
+
+use crate::sysfs::{PciAddress, read_trimmed};
+
+/// A DRM client as described by one `/proc/<pid>/fdinfo/<fd>` entry.
+/// See the kernel's `Documentation/gpu/drm-usage-stats.rst`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DrmClient {
+    pub pid: u32,
+    pub driver: String,
+    pub pdev: Option<PciAddress>,
+    pub client_id: Option<u64>,
+    /// Resident bytes per memory region, e.g. `vram`, `gtt` or `local0`.
+    pub memory: Vec<(String, u64)>,
+    /// Accumulated busy time per engine in nanoseconds, e.g. `gfx` or `render`.
+    pub engines: Vec<(String, u64)>,
+}
+
+/// The DRM clients of one process on one device, summed up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessUsage {
+    pub pid: u32,
+    pub command: String,
+    pub vram_bytes: u64,
+    pub engine_ns: u64,
+}
+
+impl DrmClient {
+    /// Parses an fdinfo file, returning `None` if it is not a DRM client.
+    pub fn parse(pid: u32, contents: &str) -> Option<Self> {
+        let mut client = DrmClient {
+            pid,
+            driver: String::new(),
+            pdev: None,
+            client_id: None,
+            memory: Vec::new(),
+            engines: Vec::new(),
+        };
+        // `drm-resident-*` supersedes the older `drm-memory-*` keys.
+        let mut legacy_memory = Vec::new();
+
+        for line in contents.lines() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+            match key.trim() {
+                "drm-driver" => client.driver = value.to_string(),
+                "drm-pdev" => client.pdev = value.parse().ok(),
+                "drm-client-id" => client.client_id = value.parse().ok(),
+                key => {
+                    if let Some(engine) = key.strip_prefix("drm-engine-")
+                        && !engine.starts_with("capacity-")
+                    {
+                        client
+                            .engines
+                            .extend(parse_nanoseconds(value).map(|ns| (engine.to_string(), ns)));
+                    } else if let Some(region) = key.strip_prefix("drm-resident-") {
+                        client
+                            .memory
+                            .extend(parse_bytes(value).map(|b| (region.to_string(), b)));
+                    } else if let Some(region) = key.strip_prefix("drm-memory-") {
+                        legacy_memory.extend(parse_bytes(value).map(|b| (region.to_string(), b)));
+                    }
+                }
+            }
+        }
+
+        if client.driver.is_empty() {
+            return None;
+        }
+        if client.memory.is_empty() {
+            client.memory = legacy_memory;
+        }
+        Some(client)
+    }
+
+    /// Returns whether the client reports a device-local region, which
+    /// integrated GPUs do not have.
+    pub fn has_vram(&self) -> bool {
+        self.memory.iter().any(|(region, _)| is_vram_region(region))
+    }
+
+    /// Returns the resident bytes in device-local regions.
+    pub fn vram_bytes(&self) -> u64 {
+        self.memory
+            .iter()
+            .filter(|(region, _)| is_vram_region(region))
+            .map(|(_, bytes)| bytes)
+            .sum()
+    }
+}
+
+/// amdgpu and xe call device memory `vram`/`vram0`, i915 calls it `local0`.
+fn is_vram_region(region: &str) -> bool {
+    region.starts_with("vram") || region.starts_with("local")
+}
+
+/// Parses `4096`, `12 KiB` or `3 MiB`.
+fn parse_bytes(value: &str) -> Option<u64> {
+    let mut parts = value.split_whitespace();
+    let amount = parts.next()?.parse::<u64>().ok()?;
+    let scale = match parts.next() {
+        None => 1,
+        Some("KiB") => 1024,
+        Some("MiB") => 1024 * 1024,
+        Some("GiB") => 1024 * 1024 * 1024,
+        Some(_) => return None,
+    };
+    Some(amount * scale)
+}
+
+/// Parses `123456 ns`.
+fn parse_nanoseconds(value: &str) -> Option<u64> {
+    value.strip_suffix("ns")?.trim().parse().ok()
+}
+
+/// Scans every readable `<proc>/<pid>/fdinfo/<fd>` for DRM clients.
+/// A client shared between several file descriptors or processes is
+/// reported once. Other users' processes are only visible with sufficient
+/// privileges.
+pub fn scan(proc_dir: &Path) -> Vec<DrmClient> {
+    let Ok(processes) = fs::read_dir(proc_dir) else {
+        return Vec::new();
+    };
+    let mut seen = HashSet::new();
+    let mut clients = Vec::new();
+
+    for process in processes.filter_map(Result::ok) {
+        let Some(pid) = process
+            .file_name()
+            .to_str()
+            .and_then(|name| name.parse::<u32>().ok())
+        else {
+            continue;
+        };
+        let Ok(fds) = fs::read_dir(process.path().join("fdinfo")) else {
+            continue;
+        };
+        for fd in fds.filter_map(Result::ok) {
+            let Some(client) = fs::read_to_string(fd.path())
+                .ok()
+                .and_then(|contents| DrmClient::parse(pid, &contents))
+            else {
+                continue;
+            };
+            if let Some(client_id) = client.client_id
+                && !seen.insert((client.driver.clone(), client.pdev, client_id))
+            {
+                continue;
+            }
+            clients.push(client);
+        }
+    }
+    clients.sort_by_key(|client| (client.pid, client.client_id));
+    clients
+}
+
+/// Returns up to `limit` processes using the device at `pci_address`,
+/// largest VRAM users first.
+pub fn top_processes(proc_dir: &Path, pci_address: PciAddress, limit: usize) -> Vec<ProcessUsage> {
+    let mut processes: Vec<ProcessUsage> = Vec::new();
+    for client in scan(proc_dir) {
+        if client.pdev != Some(pci_address) {
+            continue;
+        }
+        let usage = match processes.iter_mut().find(|usage| usage.pid == client.pid) {
+            Some(usage) => usage,
+            None => {
+                processes.push(ProcessUsage {
+                    pid: client.pid,
+                    command: read_trimmed(proc_dir.join(client.pid.to_string()).join("comm"))
+                        .unwrap_or_else(|| "???".to_string()),
+                    vram_bytes: 0,
+                    engine_ns: 0,
+                });
+                processes.last_mut().unwrap()
+            }
+        };
+        usage.vram_bytes += client.vram_bytes();
+        usage.engine_ns += client.engines.iter().map(|(_, ns)| ns).sum::<u64>();
+    }
+
+    processes.sort_by(|a, b| {
+        b.vram_bytes
+            .cmp(&a.vram_bytes)
+            .then(b.engine_ns.cmp(&a.engine_ns))
+            .then(a.pid.cmp(&b.pid))
+    });
+    processes.truncate(limit);
+    processes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AMDGPU_FDINFO: &str = "pos:\t0\nflags:\t02100002\nmnt_id:\t24\n\
+        drm-driver:\tamdgpu\ndrm-client-id:\t42\ndrm-pdev:\t0000:03:00.0\n\
+        drm-engine-gfx:\t1500000000 ns\ndrm-engine-compute:\t500000000 ns\n\
+        drm-engine-capacity-gfx:\t2\n\
+        drm-memory-vram:\t8192 KiB\ndrm-memory-gtt:\t2048 KiB\n";
+
+    #[test]
+    fn test_parse_client() {
+        let client = DrmClient::parse(1234, AMDGPU_FDINFO).unwrap();
+        assert_eq!(client.driver, "amdgpu");
+        assert_eq!(client.client_id, Some(42));
+        assert_eq!(client.pdev, "0000:03:00.0".parse().ok());
+        assert_eq!(client.vram_bytes(), 8 * 1024 * 1024);
+        assert_eq!(
+            client.engines,
+            [
+                ("gfx".to_string(), 1_500_000_000),
+                ("compute".to_string(), 500_000_000)
+            ]
+        );
+        assert_eq!(DrmClient::parse(1, "pos:\t0\nflags:\t0\n"), None);
+    }
+
+    #[test]
+    fn test_resident_supersedes_legacy_memory() {
+        let client = DrmClient::parse(
+            1,
+            "drm-driver:\ti915\ndrm-memory-local0:\t1 MiB\n\
+             drm-resident-local0:\t4 MiB\ndrm-resident-system0:\t16 MiB\n",
+        )
+        .unwrap();
+        assert_eq!(client.vram_bytes(), 4 * 1024 * 1024);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_scan_deduplicates_clients() {
+        use crate::sysfs::fixture::Fixture;
+
+        let fixture = Fixture::new("fdinfo");
+        fixture.write("proc/100/fdinfo/5", AMDGPU_FDINFO);
+        fixture.write("proc/100/fdinfo/6", AMDGPU_FDINFO);
+        fixture.write("proc/100/fdinfo/0", "pos:\t0\n");
+        fixture.write("proc/self/fdinfo/5", AMDGPU_FDINFO);
+
+        let clients = scan(&fixture.root().proc());
+        assert_eq!(clients.len(), 1);
+        assert_eq!(clients[0].pid, 100);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_top_processes() {
+        use crate::sysfs::fixture::Fixture;
+
+        let fixture = Fixture::new("fdinfo-top");
+        let client = |id: u32, pdev: &str, vram_mib: u32| {
+            format!(
+                "drm-driver:\tamdgpu\ndrm-client-id:\t{id}\ndrm-pdev:\t{pdev}\n\
+                 drm-engine-gfx:\t1000 ns\ndrm-memory-vram:\t{vram_mib} MiB\n"
+            )
+        };
+        fixture.write("proc/100/comm", "game\n");
+        fixture.write("proc/100/fdinfo/5", &client(1, "0000:03:00.0", 100));
+        fixture.write("proc/100/fdinfo/6", &client(2, "0000:03:00.0", 50));
+        fixture.write("proc/200/comm", "compositor\n");
+        fixture.write("proc/200/fdinfo/5", &client(3, "0000:03:00.0", 20));
+        fixture.write("proc/300/fdinfo/5", &client(4, "0000:04:00.0", 500));
+
+        let address = "0000:03:00.0".parse().unwrap();
+        let processes = top_processes(&fixture.root().proc(), address, 5);
+        assert_eq!(processes.len(), 2);
+        assert_eq!(processes[0].command, "game");
+        assert_eq!(processes[0].vram_bytes, 150 * 1024 * 1024);
+        assert_eq!(processes[0].engine_ns, 2000);
+        assert_eq!(processes[1].pid, 200);
+        assert_eq!(top_processes(&fixture.root().proc(), address, 1).len(), 1);
+    }
+}
@@ -0,0 +1,544 @@
+//! Builds [`Device`]s from saved reports, so the fetch view works on a
+//! machine without the GPU. Three layouts are understood:
+//!
+//! - `vulkaninfo --json`, which writes a Vulkan Profiles file with one
+//!   profile per device,
+//! - the older `vulkaninfo --json` (devsim) layout with top-level
+//!   `VkPhysicalDeviceProperties`,
+//! - gpuinfo.org reports from the Vulkan Hardware Capability Viewer.
+
+use ash::vk;
+use serde_json::{Map, Value};
+use std::{fs, path::Path};
+
+use crate::architecture::{Architecture, intel_execution_units};
+use crate::details::flag_names;
+use crate::device::{Device, DeviceType, GPUCharacteristics, decode_version_number};
+use crate::sysfs::PciAddress;
+use crate::vendor::Vendor;
+use crate::virtualization::LayeredDriver;
+
+/// The parts of a report the fetch view needs, whatever its layout.
+#[derive(Debug, Default)]
+struct Report<'a> {
+    /// Property structures, searched in order by member name.
+    properties: Vec<&'a Map<String, Value>>,
+    extensions: Vec<String>,
+    queue_flags: Vec<vk::QueueFlags>,
+    memory_heaps: Vec<(u64, vk::MemoryHeapFlags)>,
+}
+
+impl<'a> Report<'a> {
+    fn vulkaninfo(json: &'a Value) -> Result<Vec<Self>, String> {
+        let capabilities = json
+            .get("capabilities")
+            .and_then(Value::as_object)
+            .ok_or("missing \"capabilities\" object")?;
+        let profiles = json
+            .get("profiles")
+            .and_then(Value::as_object)
+            .ok_or("missing \"profiles\" object")?;
+
+        profiles
+            .iter()
+            .map(|(name, profile)| {
+                let mut report = Report::default();
+                let names = profile
+                    .get("capabilities")
+                    .and_then(Value::as_array)
+                    .ok_or_else(|| format!("profile {name} has no \"capabilities\""))?;
+                for capability in names {
+                    // Of a list of alternatives, vulkaninfo only writes one.
+                    let capability = capability
+                        .as_array()
+                        .and_then(|alternatives| alternatives.first())
+                        .unwrap_or(capability);
+                    let block = capability
+                        .as_str()
+                        .and_then(|name| capabilities.get(name))
+                        .ok_or_else(|| format!("profile {name} names an unknown capability"))?;
+                    report.add_capability(block);
+                }
+                Ok(report)
+            })
+            .collect()
+    }
+
+    fn add_capability(&mut self, block: &'a Value) {
+        if let Some(structures) = block.get("properties").and_then(Value::as_object) {
+            self.properties
+                .extend(structures.values().filter_map(Value::as_object));
+            if let Some(memory) = structures.get("VkPhysicalDeviceMemoryProperties") {
+                self.memory_heaps = memory_heaps(memory);
+            }
+        }
+        if let Some(extensions) = block.get("extensions").and_then(Value::as_object) {
+            self.extensions.extend(extensions.keys().cloned());
+        }
+        if let Some(families) = block
+            .get("queueFamiliesProperties")
+            .and_then(Value::as_array)
+        {
+            self.queue_flags = families
+                .iter()
+                .filter_map(|family| family.get("VkQueueFamilyProperties"))
+                .map(queue_flags)
+                .collect();
+        }
+    }
+
+    fn devsim(json: &'a Value) -> Self {
+        let object = json.as_object();
+        Report {
+            properties: object
+                .into_iter()
+                .flatten()
+                .filter(|(name, _)| name.starts_with("VkPhysicalDevice"))
+                .filter_map(|(_, value)| value.as_object())
+                .collect(),
+            extensions: extension_names(json.get("ArrayOfVkExtensionProperties")),
+            queue_flags: json
+                .get("ArrayOfVkQueueFamilyProperties")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .map(queue_flags)
+                .collect(),
+            memory_heaps: json
+                .get("VkPhysicalDeviceMemoryProperties")
+                .map(memory_heaps)
+                .unwrap_or_default(),
+        }
+    }
+
+    fn gpuinfo(json: &'a Value) -> Self {
+        let mut properties = json
+            .get("properties")
+            .and_then(Value::as_object)
+            .into_iter()
+            .collect::<Vec<_>>();
+        properties.extend(
+            ["core11", "core12", "core13", "core14"]
+                .iter()
+                .filter_map(|core| json.get(core)?.get("properties")?.as_object()),
+        );
+        Report {
+            properties,
+            extensions: extension_names(json.get("extensions")),
+            queue_flags: json
+                .get("queues")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .map(queue_flags)
+                .collect(),
+            memory_heaps: json.get("memory").map(memory_heaps).unwrap_or_default(),
+        }
+    }
+
+    /// Finds a property member by name, including nested members such as
+    /// `VkPhysicalDeviceProperties.limits`.
+    fn property(&self, name: &str) -> Option<&'a Value> {
+        fn find<'v>(object: &'v Map<String, Value>, name: &str) -> Option<&'v Value> {
+            object.get(name).or_else(|| {
+                object
+                    .values()
+                    .filter_map(Value::as_object)
+                    .find_map(|nested| find(nested, name))
+            })
+        }
+        self.properties
+            .iter()
+            .find_map(|properties| find(properties, name))
+    }
+
+    fn has_extension(&self, name: &str) -> bool {
+        self.extensions.iter().any(|extension| extension == name)
+    }
+}
+
+/// Reads every device of a report file.
+pub fn read_devices(path: &Path) -> Result<Vec<Device>, String> {
+    let contents =
+        fs::read_to_string(path).map_err(|error| format!("{}: {}", path.display(), error))?;
+    parse_devices(&contents).map_err(|error| format!("{}: {}", path.display(), error))
+}
+
+/// Parses a `vulkaninfo --json` or gpuinfo.org report.
+pub fn parse_devices(contents: &str) -> Result<Vec<Device>, String> {
+    let json: Value = serde_json::from_str(contents).map_err(|error| error.to_string())?;
+    let reports = if json.get("profiles").is_some() {
+        Report::vulkaninfo(&json)?
+    } else if json.get("VkPhysicalDeviceProperties").is_some() {
+        vec![Report::devsim(&json)]
+    } else if json.get("properties").is_some() {
+        vec![Report::gpuinfo(&json)]
+    } else {
+        return Err("not a vulkaninfo or gpuinfo.org report".to_string());
+    };
+    reports.iter().map(|report| device(report, &json)).collect()
+}
+
+fn device(report: &Report, json: &Value) -> Result<Device, String> {
+    let unsigned = |name: &str| report.property(name).and_then(as_u32);
+    let string = |name: &str| {
+        report
+            .property(name)
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    };
+    // Vendor extension properties, which gpuinfo.org keeps elsewhere.
+    let extended =
+        |name: &str| unsigned(name).or_else(|| gpuinfo_extended(json, name).and_then(as_u32));
+
+    let device_name = string("deviceName").ok_or("the report has no deviceName")?;
+    let vendor_id = unsigned("vendorID").ok_or("the report has no vendorID")?;
+    let device_id = unsigned("deviceID").unwrap_or(0);
+    let vendor = Vendor::from_vendor_id_or_unknown(vendor_id);
+    let device_type = report
+        .property("deviceType")
+        .map(|value| {
+            DeviceType::from(enum_value(value, "VK_PHYSICAL_DEVICE_TYPE_", 0..5, |raw| {
+                vk::PhysicalDeviceType::from_raw(raw)
+            }))
+        })
+        .unwrap_or(DeviceType::Unknown);
+    let api_version = report
+        .property("apiVersion")
+        .map(|value| match value.as_str() {
+            Some(version) => version.to_string(),
+            None => decode_version_number(as_u32(value).unwrap_or(0)),
+        })
+        .unwrap_or_else(|| "Unknown".to_string());
+    let driver_id = report
+        .property("driverID")
+        .map(|value| {
+            vk::DriverId::from_raw(enum_value(value, "VK_DRIVER_ID_", 1..64, |raw| {
+                vk::DriverId::from_raw(raw)
+            }))
+        })
+        .unwrap_or_default();
+    let driver_name = string("driverName").unwrap_or_else(|| "Unknown".to_string());
+    let driver_info = string("driverInfo").unwrap_or_else(|| "Unknown".to_string());
+    let layered_driver = LayeredDriver::detect(driver_id, &driver_name, &device_name);
+
+    let pci_address = pci_address(&extended);
+
+    let heapsize = report
+        .memory_heaps
+        .iter()
+        .find(|(_, flags)| flags.contains(vk::MemoryHeapFlags::DEVICE_LOCAL))
+        .or(report.memory_heaps.first())
+        .map(|(size, _)| *size)
+        .unwrap_or(0);
+
+    let (dedicated_transfer_queue, dedicated_async_compute_queue) =
+        crate::device::dedicated_queues(report.queue_flags.iter().copied());
+    let mut characteristics = GPUCharacteristics {
+        max_image_dimension_2d: unsigned("maxImageDimension2D").unwrap_or(0),
+        max_compute_shared_memory_size: unsigned("maxComputeSharedMemorySize").unwrap_or(0),
+        max_compute_work_group_invocations: unsigned("maxComputeWorkGroupInvocations").unwrap_or(0),
+        dedicated_transfer_queue,
+        dedicated_async_compute_queue,
+        supports_ray_tracing: report.has_extension("VK_KHR_ray_tracing_pipeline")
+            || report.has_extension("VK_NV_ray_tracing"),
+        ..Default::default()
+    };
+
+    match vendor {
+        Vendor::AMD => {
+            characteristics.shader_engines = extended("shaderEngineCount");
+            characteristics.shader_arrays_per_engine_count = extended("shaderArraysPerEngineCount");
+            characteristics.compute_units_per_shader_array = extended("computeUnitsPerShaderArray");
+            characteristics.compute_units = characteristics
+                .shader_engines
+                .zip(characteristics.shader_arrays_per_engine_count)
+                .zip(characteristics.compute_units_per_shader_array)
+                .map(|((engines, arrays), units)| engines * arrays * units);
+            characteristics.simd_per_compute_unit = extended("simdPerComputeUnit");
+            characteristics.wavefronts_per_simd = extended("wavefrontsPerSimd");
+            characteristics.wavefront_size = extended("wavefrontSize");
+            characteristics.active_compute_units = extended("activeComputeUnitCount");
+        }
+        Vendor::Nvidia => {
+            characteristics.streaming_multiprocessors = extended("shaderSMCount");
+            characteristics.warps_per_sm = extended("shaderWarpsPerSM");
+        }
+        Vendor::ARM => {
+            characteristics.shader_cores = extended("shaderCoreCount");
+            characteristics.warps_per_core = extended("shaderWarpsPerCore");
+            characteristics.pixel_rate = extended("pixelRate");
+            characteristics.texel_rate = extended("texelRate");
+            characteristics.fma_rate = extended("fmaRate");
+        }
+        Vendor::Intel => {
+            if let Some((execution_units, xe_cores)) = intel_execution_units(device_id) {
+                characteristics.execution_units = Some(execution_units);
+                characteristics.xe_cores = xe_cores;
+            }
+            characteristics.min_subgroup_size = extended("minSubgroupSize");
+            characteristics.max_subgroup_size = extended("maxSubgroupSize");
+        }
+        Vendor::Apple => {
+            characteristics.min_vertex_input_binding_stride_alignment =
+                extended("minVertexInputBindingStrideAlignment");
+        }
+        _ => {}
+    }
+
+    let architecture = Architecture::detect(vendor, device_id, &device_name, &characteristics);
+
+    Ok(Device {
+        vendor,
+        device_name,
+        device_type,
+        device_id,
+        vendor_id,
+        driver_name,
+        driver_id,
+        driver_info,
+        api_version,
+        architecture,
+        layered_driver,
+        drm: None,
+        pci_address,
+        sysfs_dir: None,
+        sensors: None,
+        kernel_stats: None,
+        pcie_link: None,
+        heapbudget: None,
+        heapsize,
+        characteristics,
+    })
+}
+
+/// gpuinfo.org lists extension properties separately, as name and value.
+fn gpuinfo_extended<'a>(json: &'a Value, name: &str) -> Option<&'a Value> {
+    json.get("extended")?
+        .get("deviceproperties2")?
+        .as_array()?
+        .iter()
+        .find(|entry| entry.get("name").and_then(Value::as_str) == Some(name))?
+        .get("value")
+}
+
+fn pci_address(extended: &impl Fn(&str) -> Option<u32>) -> Option<PciAddress> {
+    Some(PciAddress {
+        domain: extended("pciDomain")?,
+        bus: extended("pciBus")?,
+        device: extended("pciDevice")?,
+        function: extended("pciFunction")?,
+    })
+}
+
+fn as_u32(value: &Value) -> Option<u32> {
+    value.as_u64().and_then(|value| u32::try_from(value).ok())
+}
+
+/// Reads an enum written as its raw value or as its Vulkan name.
+fn enum_value<E: std::fmt::Debug>(
+    value: &Value,
+    prefix: &str,
+    range: std::ops::Range<i32>,
+    from_raw: fn(i32) -> E,
+) -> i32 {
+    match value.as_str() {
+        Some(name) => {
+            let name = name.strip_prefix(prefix).unwrap_or(name);
+            range
+                .into_iter()
+                .find(|&raw| format!("{:?}", from_raw(raw)) == name)
+                .unwrap_or(-1)
+        }
+        None => value
+            .as_i64()
+            .and_then(|raw| i32::try_from(raw).ok())
+            .unwrap_or(-1),
+    }
+}
+
+/// Reads queue flags written as a number or as a list of Vulkan bit names.
+fn queue_flags(family: &Value) -> vk::QueueFlags {
+    let bits = match family.get("queueFlags") {
+        Some(Value::Array(names)) => names
+            .iter()
+            .filter_map(Value::as_str)
+            .filter_map(|name| {
+                (0..u32::BITS).map(|bit| 1 << bit).find(|&bit| {
+                    flag_names("VK_QUEUE_", bit, vk::QueueFlags::from_raw)
+                        .iter()
+                        .any(|bit_name| bit_name == name)
+                })
+            })
+            .fold(0, |flags, bit| flags | bit),
+        Some(value) => as_u32(value).unwrap_or(0),
+        None => 0,
+    };
+    vk::QueueFlags::from_raw(bits)
+}
+
+fn memory_heaps(memory: &Value) -> Vec<(u64, vk::MemoryHeapFlags)> {
+    memory
+        .get("memoryHeaps")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .map(|heap| {
+            let size = heap.get("size").and_then(Value::as_u64).unwrap_or(0);
+            // Only DEVICE_LOCAL matters here, written as 1 or by name.
+            let device_local = match heap.get("flags") {
+                Some(Value::Array(names)) => names
+                    .iter()
+                    .any(|name| name.as_str() == Some("VK_MEMORY_HEAP_DEVICE_LOCAL_BIT")),
+                Some(value) => as_u32(value).is_some_and(|flags| flags & 1 != 0),
+                None => false,
+            };
+            let flags = if device_local {
+                vk::MemoryHeapFlags::DEVICE_LOCAL
+            } else {
+                vk::MemoryHeapFlags::empty()
+            };
+            (size, flags)
+        })
+        .collect()
+}
+
+fn extension_names(extensions: Option<&Value>) -> Vec<String> {
+    extensions
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|extension| extension.get("extensionName")?.as_str())
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vulkaninfo() {
+        let devices = parse_devices(
+            r#"{
+                "capabilities": {
+                    "device": {
+                        "extensions": { "VK_AMD_shader_core_properties": 2, "VK_KHR_ray_tracing_pipeline": 1 },
+                        "properties": {
+                            "VkPhysicalDeviceProperties": {
+                                "apiVersion": 4206847,
+                                "deviceID": 29772,
+                                "deviceName": "AMD Radeon RX 7900 XTX (RADV NAVI31)",
+                                "deviceType": "VK_PHYSICAL_DEVICE_TYPE_DISCRETE_GPU",
+                                "vendorID": 4098,
+                                "limits": { "maxImageDimension2D": 16384 }
+                            },
+                            "VkPhysicalDeviceDriverProperties": {
+                                "driverID": "VK_DRIVER_ID_MESA_RADV",
+                                "driverName": "radv",
+                                "driverInfo": "Mesa 24.1.0"
+                            },
+                            "VkPhysicalDeviceShaderCorePropertiesAMD": {
+                                "shaderEngineCount": 6,
+                                "shaderArraysPerEngineCount": 2,
+                                "computeUnitsPerShaderArray": 8
+                            },
+                            "VkPhysicalDevicePCIBusInfoPropertiesEXT": {
+                                "pciDomain": 0, "pciBus": 3, "pciDevice": 0, "pciFunction": 0
+                            }
+                        },
+                        "queueFamiliesProperties": [
+                            { "VkQueueFamilyProperties": { "queueFlags": ["VK_QUEUE_GRAPHICS_BIT", "VK_QUEUE_COMPUTE_BIT"] } },
+                            { "VkQueueFamilyProperties": { "queueFlags": ["VK_QUEUE_COMPUTE_BIT", "VK_QUEUE_TRANSFER_BIT"] } }
+                        ]
+                    }
+                },
+                "profiles": {
+                    "VP_VULKANINFO_AMD_Radeon_RX_7900_XTX_24_1_0": { "capabilities": ["device"] }
+                }
+            }"#,
+        )
+        .unwrap();
+        let device = &devices[0];
+        assert_eq!(device.vendor, Vendor::AMD);
+        assert_eq!(device.device_name, "AMD Radeon RX 7900 XTX (RADV NAVI31)");
+        assert_eq!(device.device_type.name(), "Discrete GPU");
+        assert_eq!(device.api_version, "1.3.255");
+        assert_eq!(device.driver_id, vk::DriverId::MESA_RADV);
+        assert_eq!(device.driver_info, "Mesa 24.1.0");
+        assert_eq!(device.pci_address.map(|address| address.bus), Some(3));
+        let characteristics = &device.characteristics;
+        assert_eq!(characteristics.compute_units, Some(96));
+        assert_eq!(characteristics.max_image_dimension_2d, 16384);
+        assert!(characteristics.dedicated_async_compute_queue);
+        assert!(!characteristics.dedicated_transfer_queue);
+        assert!(characteristics.supports_ray_tracing);
+    }
+
+    #[test]
+    fn test_devsim() {
+        let devices = parse_devices(
+            r#"{
+                "VkPhysicalDeviceProperties": {
+                    "apiVersion": 4206592,
+                    "deviceName": "NVIDIA GeForce RTX 3080",
+                    "deviceType": 2,
+                    "vendorID": 4318,
+                    "limits": { "maxComputeSharedMemorySize": 49152 }
+                },
+                "VkPhysicalDeviceMemoryProperties": {
+                    "memoryHeaps": [
+                        { "flags": 0, "size": 33554432000 },
+                        { "flags": 1, "size": 10737418240 }
+                    ]
+                },
+                "ArrayOfVkQueueFamilyProperties": [{ "queueFlags": 4 }],
+                "ArrayOfVkExtensionProperties": [{ "extensionName": "VK_NV_ray_tracing", "specVersion": 3 }]
+            }"#,
+        )
+        .unwrap();
+        let device = &devices[0];
+        assert_eq!(device.vendor, Vendor::Nvidia);
+        assert_eq!(device.api_version, "1.3.0");
+        assert_eq!(device.heapsize, 10737418240);
+        assert_eq!(device.characteristics.max_compute_shared_memory_size, 49152);
+        assert!(device.characteristics.dedicated_transfer_queue);
+        assert!(device.characteristics.supports_ray_tracing);
+        assert_eq!(device.driver_name, "Unknown");
+    }
+
+    #[test]
+    fn test_gpuinfo() {
+        let devices = parse_devices(
+            r#"{
+                "properties": {
+                    "apiVersion": 4206847,
+                    "deviceID": 9860,
+                    "deviceName": "NVIDIA GeForce RTX 4090",
+                    "deviceType": 2,
+                    "vendorID": 4318
+                },
+                "core12": { "properties": { "driverID": 4, "driverName": "NVIDIA" } },
+                "extended": {
+                    "deviceproperties2": [
+                        { "extension": "VK_NV_shader_sm_builtins", "name": "shaderSMCount", "value": 128 },
+                        { "extension": "VK_NV_shader_sm_builtins", "name": "shaderWarpsPerSM", "value": 48 }
+                    ]
+                },
+                "memory": { "memoryHeaps": [{ "flags": 1, "size": 25757220864 }] }
+            }"#,
+        )
+        .unwrap();
+        let device = &devices[0];
+        assert_eq!(device.driver_id, vk::DriverId::NVIDIA_PROPRIETARY);
+        assert_eq!(device.characteristics.streaming_multiprocessors, Some(128));
+        assert_eq!(device.characteristics.warps_per_sm, Some(48));
+        assert_eq!(device.heapsize, 25757220864);
+    }
+
+    #[test]
+    fn test_unrecognized() {
+        assert!(parse_devices("{}").is_err());
+        assert!(parse_devices(r#"{ "properties": {} }"#).is_err());
+    }
+}
@@ -0,0 +1,140 @@
+use std::path::Path;
+
+use crate::fdinfo::{self, DrmClient};
+use crate::sysfs::{PciAddress, SysRoot, link_name, read_u64};
+
+/// System-wide utilization and VRAM usage as seen by the kernel driver,
+/// as opposed to the per-process view of `VK_EXT_memory_budget`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct KernelStats {
+    /// Fraction of time the GPU was busy (0.0 to 1.0).
+    pub gpu_busy: Option<f32>,
+    /// Fraction of time the memory controller was busy (0.0 to 1.0).
+    pub memory_busy: Option<f32>,
+    pub vram_used: Option<u64>,
+    pub vram_total: Option<u64>,
+}
+
+impl KernelStats {
+    /// Reads the statistics of the device whose sysfs directory is `device_dir`.
+    /// Returns `None` if its kernel driver exposes none of them.
+    pub fn read(
+        root: &SysRoot,
+        device_dir: &Path,
+        pci_address: Option<PciAddress>,
+    ) -> Option<Self> {
+        let stats = match link_name(device_dir.join("driver")).as_deref() {
+            Some("amdgpu") => KernelStats {
+                gpu_busy: read_percent(&device_dir.join("gpu_busy_percent")),
+                memory_busy: read_percent(&device_dir.join("mem_busy_percent")),
+                vram_used: read_u64(device_dir.join("mem_info_vram_used")),
+                vram_total: read_u64(device_dir.join("mem_info_vram_total")),
+            },
+            // i915 and xe only account memory per client, so sum up the
+            // clients visible to this user. Integrated GPUs report no VRAM
+            // region; leave them to the Vulkan budget.
+            Some(driver @ ("i915" | "xe")) => {
+                let pci_address = pci_address?;
+                let clients = fdinfo::scan(&root.proc())
+                    .into_iter()
+                    .filter(|client| client.driver == driver && client.pdev == Some(pci_address))
+                    .collect::<Vec<_>>();
+                KernelStats {
+                    vram_used: clients
+                        .iter()
+                        .any(DrmClient::has_vram)
+                        .then(|| clients.iter().map(DrmClient::vram_bytes).sum()),
+                    ..Default::default()
+                }
+            }
+            _ => return None,
+        };
+        (stats != KernelStats::default()).then_some(stats)
+    }
+
+    /// Returns the used fraction of VRAM, using `heap_size` if the kernel
+    /// does not report the total.
+    pub fn vram_usage(&self, heap_size: u64) -> Option<f32> {
+        let used = self.vram_used?;
+        let total = self.vram_total.unwrap_or(heap_size);
+        (total > 0 && used <= total).then(|| used as f32 / total as f32)
+    }
+}
+
+fn read_percent(path: &Path) -> Option<f32> {
+    read_u64(path).map(|percent| percent.min(100) as f32 / 100.0)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::sysfs::fixture::Fixture;
+
+    const PCI_DEVICE: &str = "sys/bus/pci/devices/0000:03:00.0";
+
+    #[test]
+    fn test_amdgpu_stats() {
+        let fixture = Fixture::new("kernel-stats-amdgpu");
+        fixture.mkdir("sys/bus/pci/drivers/amdgpu");
+        fixture.symlink(&format!("{PCI_DEVICE}/driver"), "../../drivers/amdgpu");
+        fixture.write(&format!("{PCI_DEVICE}/gpu_busy_percent"), "37\n");
+        fixture.write(&format!("{PCI_DEVICE}/mem_busy_percent"), "5\n");
+        fixture.write(&format!("{PCI_DEVICE}/mem_info_vram_used"), "1073741824\n");
+        fixture.write(&format!("{PCI_DEVICE}/mem_info_vram_total"), "4294967296\n");
+
+        let stats = KernelStats::read(&fixture.root(), &fixture.path(PCI_DEVICE), None).unwrap();
+        assert_eq!(stats.gpu_busy, Some(0.37));
+        assert_eq!(stats.memory_busy, Some(0.05));
+        assert_eq!(stats.vram_usage(0), Some(0.25));
+    }
+
+    #[test]
+    fn test_i915_stats_from_fdinfo() {
+        let fixture = Fixture::new("kernel-stats-i915");
+        fixture.mkdir("sys/bus/pci/drivers/i915");
+        fixture.symlink(&format!("{PCI_DEVICE}/driver"), "../../drivers/i915");
+        for (pid, client_id, pdev) in [(10, 1, "0000:03:00.0"), (11, 2, "0000:04:00.0")] {
+            fixture.write(
+                &format!("proc/{pid}/fdinfo/4"),
+                &format!(
+                    "drm-driver:\ti915\ndrm-client-id:\t{client_id}\ndrm-pdev:\t{pdev}\n\
+                     drm-resident-local0:\t512 MiB\n"
+                ),
+            );
+        }
+
+        let address = "0000:03:00.0".parse().ok();
+        let stats = KernelStats::read(&fixture.root(), &fixture.path(PCI_DEVICE), address).unwrap();
+        assert_eq!(stats.vram_used, Some(512 * 1024 * 1024));
+        assert_eq!(stats.gpu_busy, None);
+        assert_eq!(stats.vram_usage(1024 * 1024 * 1024), Some(0.5));
+    }
+
+    #[test]
+    fn test_integrated_i915_has_no_vram() {
+        let fixture = Fixture::new("kernel-stats-i915-integrated");
+        fixture.mkdir("sys/bus/pci/drivers/i915");
+        fixture.symlink(&format!("{PCI_DEVICE}/driver"), "../../drivers/i915");
+        fixture.write(
+            "proc/10/fdinfo/4",
+            "drm-driver:\ti915\ndrm-client-id:\t1\ndrm-pdev:\t0000:03:00.0\n\
+             drm-resident-system0:\t64 MiB\n",
+        );
+
+        let address = "0000:03:00.0".parse().ok();
+        assert_eq!(
+            KernelStats::read(&fixture.root(), &fixture.path(PCI_DEVICE), address),
+            None
+        );
+    }
+
+    #[test]
+    fn test_unknown_driver() {
+        let fixture = Fixture::new("kernel-stats-none");
+        fixture.mkdir(PCI_DEVICE);
+        assert_eq!(
+            KernelStats::read(&fixture.root(), &fixture.path(PCI_DEVICE), None),
+            None
+        );
+    }
+}
@@ -1,15 +1,49 @@
+pub mod architecture;
 pub mod ascii_art;
+pub mod cli;
+pub mod details;
 pub mod device;
+mod diagnose;
+pub mod drm;
+pub mod environment;
+pub mod estimate;
+pub mod exporter;
+pub mod fdinfo;
+pub mod import;
+pub mod kernel_stats;
+pub mod loader;
+pub mod pcie;
+pub mod profiles;
+pub mod record;
+pub mod sensors;
+pub mod snapshot;
+pub mod sysfs;
+pub mod troubleshoot;
+mod tui;
+pub mod validation;
 pub mod vendor;
+pub mod virtualization;
+pub mod vulkan14;
+pub mod vulkaninfo;
+mod watch;
 
 use ascii_art::{BRIGHT_GREEN, BRIGHT_RED, BRIGHT_YELLOW};
 use ash::{self, Entry, Instance, vk};
+use cli::Options;
 use device::Device;
+use estimate::ThroughputEstimate;
+use fdinfo::ProcessUsage;
+use kernel_stats::KernelStats;
+use loader::LoaderEnv;
+use sensors::Sensors;
 use std::{
     error::Error,
     ffi::CStr,
     io::{self, Write},
 };
+use sysfs::SysRoot;
+use troubleshoot::{Cause, Failure};
+use validation::{Messenger, VALIDATION_LAYER, ValidationFailed, ValidationSupport};
 use vt::enable_virtual_terminal_processing;
 
 const BOLD: &str = "\x1B[1m";
@@ -23,12 +57,42 @@ const EMPTY: &str = "";
 /// Fetches and prints information for a given physical device.
 pub fn fetch_device(
     instance: &Instance,
+    instance_version: u32,
     device_handle: vk::PhysicalDevice,
+    options: &Options,
 ) -> Result<(), Box<dyn Error>> {
+    print_device(
+        &Device::new(instance, instance_version, device_handle),
+        options,
+    )
+}
+
+/// Prints the fetch view of a device, live or imported from a report.
+fn print_device(device: &Device, options: &Options) -> Result<(), Box<dyn Error>> {
     let _ = enable_virtual_terminal_processing();
     let use_ansi = is_ansi_supported();
 
-    let device = Device::new(instance, device_handle);
+    let lines = render_device(device, options, use_ansi);
+
+    if use_ansi {
+        print!("{}", WRAP_OFF);
+        io::stdout().flush()?;
+    }
+    for line in lines {
+        println!("{}", line);
+    }
+    if use_ansi {
+        print!("{}", WRAP_ON);
+        io::stdout().flush()?;
+    }
+
+    println!();
+    Ok(())
+}
+
+/// Returns the ASCII art next to the device info, followed by the
+/// process table if it was requested.
+fn render_device(device: &Device, options: &Options, use_ansi: bool) -> Vec<String> {
     let vendor = device.vendor;
     let art = vendor.get_ascii_art_with_ansi(use_ansi);
     let blank_art = " ".repeat(vendor.ascii_art_width());
@@ -38,25 +102,125 @@ pub fn fetch_device(
     } else {
         EMPTY
     };
-    let info = get_device_info(&device, accent, use_ansi);
+    let info = get_device_info(device, accent, use_ansi);
 
-    if use_ansi {
-        print!("{}", WRAP_OFF);
-        io::stdout().flush()?;
-    }
+    let mut lines = Vec::new();
     for i in 0..art.len().max(info.len()) {
         let art_line = art.get(i).map(String::as_str).unwrap_or(&blank_art);
         let info_line = info.get(i).map(String::as_str).unwrap_or(EMPTY);
 
-        println!(" {} {}", art_line, info_line);
+        lines.push(format!(" {} {}", art_line, info_line));
     }
-    if use_ansi {
-        print!("{}", WRAP_ON);
-        io::stdout().flush()?;
+    if let (Some(limit), Some(pci_address)) = (options.processes, device.pci_address) {
+        let processes = fdinfo::top_processes(&SysRoot::from_env().proc(), pci_address, limit);
+        for line in get_process_table(&processes, accent, use_ansi) {
+            lines.push(format!(" {} {}", blank_art, line));
+        }
     }
+    lines
+}
 
-    println!();
-    Ok(())
+/// Returns the per-process usage table printed below a device.
+fn get_process_table(processes: &[ProcessUsage], color: &str, use_ansi: bool) -> Vec<String> {
+    let reset = if use_ansi { RESET } else { EMPTY };
+    let mut lines = vec![String::new()];
+    if processes.is_empty() {
+        lines.push(format!(
+            "{}{}Processes{}: none visible",
+            ALIGNMENT, color, reset
+        ));
+        return lines;
+    }
+
+    lines.push(format!(
+        "{}{}{:>8}  {:>12}  {:>10}  Command{}",
+        ALIGNMENT, color, "PID", "VRAM", "GPU Time", reset
+    ));
+    for process in processes {
+        lines.push(format!(
+            "{}{:>8}  {:>12}  {:>9.1}s  {}",
+            ALIGNMENT,
+            process.pid,
+            format_bytes(process.vram_bytes),
+            process.engine_ns as f64 / 1_000_000_000.0,
+            process.command
+        ));
+    }
+    lines
+}
+
+/// Shows the devices in the way selected on the command line.
+fn run_mode(
+    instance: &Instance,
+    instance_version: u32,
+    devices: &[vk::PhysicalDevice],
+    options: &Options,
+) -> Result<(), Box<dyn Error>> {
+    let selected;
+    let devices = if options.devices.is_empty() {
+        devices
+    } else {
+        selected = options
+            .devices
+            .iter()
+            .map(|&index| {
+                devices.get(index).copied().ok_or_else(|| {
+                    format!("no device at index {} ({} found)", index, devices.len())
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        &selected[..]
+    };
+
+    if options.diagnose {
+        diagnose::print_device_drivers(instance, instance_version, devices, &LoaderEnv::from_env());
+        Ok(())
+    } else if let Some(path) = &options.export {
+        vulkaninfo::export_devices(instance, instance_version, devices, path)
+    } else if let Some(path) = &options.diff {
+        snapshot::diff_devices(
+            instance,
+            instance_version,
+            devices,
+            path,
+            options.json,
+            is_ansi_supported(),
+        )
+    } else if !options.profiles.is_empty() {
+        let profiles = options
+            .profiles
+            .iter()
+            .map(|path| profiles::Profile::load(path))
+            .collect::<Result<Vec<_>, _>>()?
+            .concat();
+        profiles::check_devices(
+            instance,
+            instance_version,
+            devices,
+            &profiles,
+            is_ansi_supported(),
+        )
+    } else if options.tui {
+        tui::run(instance, instance_version, devices)
+    } else if let Some(address) = &options.serve {
+        exporter::serve_metrics(instance, instance_version, devices, address)
+    } else if let Some(path) = &options.record {
+        let format = options
+            .record_format
+            .unwrap_or_else(|| record::RecordFormat::from_path(path));
+        let limits = record::RecordLimits {
+            interval: options.interval.unwrap_or(cli::DEFAULT_RECORD_INTERVAL),
+            duration: options.duration,
+            samples: options.samples,
+        };
+        record::record_devices(instance, instance_version, devices, path, format, limits)
+    } else if let Some(interval) = options.watch {
+        watch::watch_devices(instance, instance_version, devices, options, interval)
+    } else {
+        devices
+            .iter()
+            .try_for_each(|&device| fetch_device(instance, instance_version, device, options))
+    }
 }
 
 /// Returns a vector of formatted strings representing the device info,
@@ -67,6 +231,7 @@ fn get_device_info(device: &Device, color: &str, use_ansi: bool) -> Vec<String>
     let bold = if use_ansi { BOLD } else { EMPTY };
     let reset = if use_ansi { RESET } else { EMPTY };
     let value_color = if use_ansi { "\x1B[37m" } else { EMPTY };
+    let dim = if use_ansi { DIM } else { EMPTY };
 
     let title = format!(
         "{}{}{}{}: {}",
@@ -95,26 +260,115 @@ fn get_device_info(device: &Device, color: &str, use_ansi: bool) -> Vec<String>
         reset,
         device.vendor.name(),
     ));
+    if let Some(architecture) = device.architecture {
+        lines.push(format!(
+            "{}{}Architecture{}: {}{}{}",
+            ALIGNMENT, color, reset, value_color, architecture, reset
+        ));
+    }
     push_driver_info(&mut lines, device, color, value_color, reset);
+    if let Some(layered_driver) = &device.layered_driver {
+        lines.push(format!(
+            "{}{}Backend{}: {}{} on {}{}",
+            ALIGNMENT,
+            color,
+            reset,
+            value_color,
+            layered_driver.kind.name(),
+            layered_driver.kind.backend(),
+            reset
+        ));
+        if let Some(host_gpu) = &layered_driver.host_gpu {
+            lines.push(format!(
+                "{}{}Host GPU{}: {}{}{}",
+                ALIGNMENT, color, reset, value_color, host_gpu, reset
+            ));
+        }
+    }
+    if let Some(drm) = &device.drm {
+        let nodes = drm.node_paths().collect::<Vec<_>>().join(", ");
+        match &drm.kernel_driver {
+            Some(kernel_driver) => lines.push(format!(
+                "{}{}DRM{}: {}{}{} | {}{}{}",
+                ALIGNMENT,
+                color,
+                reset,
+                value_color,
+                nodes,
+                reset,
+                value_color,
+                kernel_driver,
+                reset
+            )),
+            None => lines.push(format!(
+                "{}{}DRM{}: {}{}{}",
+                ALIGNMENT, color, reset, value_color, nodes, reset
+            )),
+        }
+    }
+    if let Some(link) = &device.pcie_link {
+        // A degraded link is drawn in the accent color.
+        let (link_color, degraded) = if link.is_degraded() {
+            (color, " (degraded)")
+        } else {
+            (value_color, EMPTY)
+        };
+        lines.push(format!(
+            "{}{}PCIe{}: {}{}{} / {}{}",
+            ALIGNMENT, color, reset, link_color, link.current, degraded, link.max, reset
+        ));
+    }
     lines.push(format!(
         "{}{}API{}: {}{}{}",
         ALIGNMENT, color, reset, value_color, device.api_version, reset
     ));
 
+    if let Some(busy) = device.kernel_stats.and_then(|stats| stats.gpu_busy) {
+        lines.push(format!(
+            "{}{}GPU Load{}: {} {}{:.0} %{}",
+            ALIGNMENT,
+            color,
+            reset,
+            format_meter(30, Some(busy), use_ansi),
+            memory_pressure_color(Some(busy), use_ansi),
+            busy * 100.0,
+            reset
+        ));
+    }
+    if let Some(busy) = device.kernel_stats.and_then(|stats| stats.memory_busy) {
+        lines.push(format!(
+            "{}{}Memory Load{}: {}{:.0} %{}",
+            ALIGNMENT,
+            color,
+            reset,
+            value_color,
+            busy * 100.0,
+            reset
+        ));
+    }
+
     let pressure_color = memory_pressure_color(device.characteristics.memory_pressure, use_ansi);
-    let heap_budget = device
-        .heapbudget
-        .map(format_bytes)
-        .unwrap_or_else(|| "???".to_string());
+    // The kernel reports system-wide usage, Vulkan only this process' budget.
+    let (vram_used, vram_total) = match device.kernel_stats {
+        Some(KernelStats {
+            vram_used: Some(used),
+            vram_total,
+            ..
+        }) => (
+            format_bytes(used),
+            format_bytes(vram_total.unwrap_or(device.heapsize)),
+        ),
+        _ => (
+            device
+                .heapbudget
+                .map(format_bytes)
+                .unwrap_or_else(|| "???".to_string()),
+            format_bytes(device.heapsize),
+        ),
+    };
     lines.push(format!(
         "{}{}VRAM{}: {}{}{} / {}",
-        ALIGNMENT,
-        color,
-        reset,
-        pressure_color,
-        heap_budget,
-        reset,
-        format_bytes(device.heapsize)
+        ALIGNMENT, color, reset, pressure_color, vram_used, reset, vram_total
     ));
 
     let pressure = device.characteristics.memory_pressure;
@@ -187,6 +441,125 @@ fn get_device_info(device: &Device, color: &str, use_ansi: bool) -> Vec<String>
             ALIGNMENT, color, reset, value_color, wps, reset
         ));
     }
+    if let Some(cores) = device.characteristics.shader_cores {
+        lines.push(format!(
+            "{}{}Shader Cores{}: {}{}{}",
+            ALIGNMENT, color, reset, value_color, cores, reset
+        ));
+    }
+    if let Some(wpc) = device.characteristics.warps_per_core {
+        lines.push(format!(
+            "{}{}Warps per Core{}: {}{}{}",
+            ALIGNMENT, color, reset, value_color, wpc, reset
+        ));
+    }
+    if let Some(pixels) = device.characteristics.pixel_rate {
+        lines.push(format!(
+            "{}{}Pixels per Clock per Core{}: {}{}{}",
+            ALIGNMENT, color, reset, value_color, pixels, reset
+        ));
+    }
+    if let Some(texels) = device.characteristics.texel_rate {
+        lines.push(format!(
+            "{}{}Texels per Clock per Core{}: {}{}{}",
+            ALIGNMENT, color, reset, value_color, texels, reset
+        ));
+    }
+    if let Some(fma) = device.characteristics.fma_rate {
+        lines.push(format!(
+            "{}{}FMAs per Clock per Core{}: {}{}{}",
+            ALIGNMENT, color, reset, value_color, fma, reset
+        ));
+    }
+    if let Some(eus) = device.characteristics.execution_units {
+        lines.push(format!(
+            "{}{}Execution Units{}: {}{}{}",
+            ALIGNMENT, color, reset, value_color, eus, reset
+        ));
+    }
+    if let Some(xe_cores) = device.characteristics.xe_cores {
+        lines.push(format!(
+            "{}{}Xe-cores{}: {}{}{}",
+            ALIGNMENT, color, reset, value_color, xe_cores, reset
+        ));
+    }
+    if let (Some(min), Some(max)) = (
+        device.characteristics.min_subgroup_size,
+        device.characteristics.max_subgroup_size,
+    ) {
+        lines.push(format!(
+            "{}{}Subgroup Size{}: {}{} - {}{}",
+            ALIGNMENT, color, reset, value_color, min, max, reset
+        ));
+    }
+    if let Some(tile_memory) = device.characteristics.tile_memory_size {
+        lines.push(format!(
+            "{}{}Tile Memory{}: {}{}{}",
+            ALIGNMENT,
+            color,
+            reset,
+            value_color,
+            format_bytes(tile_memory),
+            reset
+        ));
+    }
+    if let Some(tile_properties) = device.characteristics.supports_tile_properties {
+        lines.push(format!(
+            "{}{}Tile Properties{}: {}",
+            ALIGNMENT,
+            color,
+            reset,
+            if tile_properties { "[x]" } else { "[ ]" }
+        ));
+    }
+    if let Some(unsupported) = &device.characteristics.unsupported_portability_features {
+        let unsupported = if unsupported.is_empty() {
+            "none".to_string()
+        } else {
+            unsupported.join(", ")
+        };
+        lines.push(format!(
+            "{}{}Unsupported Portability Features{}: {}{}{}",
+            ALIGNMENT, color, reset, value_color, unsupported, reset
+        ));
+    }
+    if let Some(alignment) = device
+        .characteristics
+        .min_vertex_input_binding_stride_alignment
+    {
+        lines.push(format!(
+            "{}{}Min Vertex Input Binding Stride Alignment{}: {}{}{}",
+            ALIGNMENT, color, reset, value_color, alignment, reset
+        ));
+    }
+
+    // Live sensor readings.
+    if let Some(sensors) = &device.sensors {
+        push_sensor_info(&mut lines, sensors, color, value_color, reset);
+    }
+
+    // Estimated peak throughput.
+    if let Some(estimate) = ThroughputEstimate::for_device(device) {
+        let fp16 = estimate
+            .fp16_tflops
+            .map(|fp16| {
+                format!(
+                    " | {}Est. FP16{}: {}~{:.2} TFLOPS{}",
+                    color, reset, value_color, fp16, reset
+                )
+            })
+            .unwrap_or_default();
+        lines.push(format!(
+            "{}{}Est. FP32{}: {}~{:.2} TFLOPS{}{} {}(estimate){}",
+            ALIGNMENT, color, reset, value_color, estimate.fp32_tflops, reset, fp16, dim, reset
+        ));
+        if let Some(bandwidth) = estimate.bandwidth_gb_per_s {
+            lines.push(format!(
+                "{}{}Est. Bandwidth{}: {}~{:.1} GB/s{} {}(estimate){}",
+                ALIGNMENT, color, reset, value_color, bandwidth, reset, dim, reset
+            ));
+        }
+    }
 
     // General device limits.
     // lines.push(format!(
@@ -256,6 +629,69 @@ fn push_driver_info(
     }
 }
 
+fn push_sensor_info(
+    lines: &mut Vec<String>,
+    sensors: &Sensors,
+    color: &str,
+    value_color: &str,
+    reset: &str,
+) {
+    if !sensors.temperatures.is_empty() {
+        let temperatures = sensors
+            .temperatures
+            .iter()
+            .map(|temperature| match &temperature.label {
+                Some(label) => format!("{:.1} °C ({})", temperature.celsius, label),
+                None => format!("{:.1} °C", temperature.celsius),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        lines.push(format!(
+            "{}{}Temperature{}: {}{}{}",
+            ALIGNMENT, color, reset, value_color, temperatures, reset
+        ));
+    }
+    if let Some(rpm) = sensors.fan_rpm {
+        lines.push(format!(
+            "{}{}Fan{}: {}{} RPM{}",
+            ALIGNMENT, color, reset, value_color, rpm, reset
+        ));
+    }
+    if let Some(power) = sensors.power_watts {
+        let power = match sensors.power_cap_watts {
+            Some(cap) => format!("{:.1} W / {:.1} W", power, cap),
+            None => format!("{:.1} W", power),
+        };
+        lines.push(format!(
+            "{}{}Power{}: {}{}{}",
+            ALIGNMENT, color, reset, value_color, power, reset
+        ));
+    }
+    for (label, clock) in [
+        ("Core Clock", sensors.core_clock),
+        ("Memory Clock", sensors.memory_clock),
+    ] {
+        let Some(clock) = clock else {
+            continue;
+        };
+        let format_mhz = |mhz: Option<u32>| {
+            mhz.map(|mhz| mhz.to_string())
+                .unwrap_or_else(|| "???".to_string())
+        };
+        lines.push(format!(
+            "{}{}{}{}: {}{} / {} MHz{}",
+            ALIGNMENT,
+            color,
+            label,
+            reset,
+            value_color,
+            format_mhz(clock.current_mhz),
+            format_mhz(clock.max_mhz),
+            reset
+        ));
+    }
+}
+
 fn memory_pressure_color(pressure: Option<f32>, use_ansi: bool) -> &'static str {
     if !use_ansi {
         return EMPTY;
@@ -326,7 +762,20 @@ fn format_bytes(bytes: u64) -> String {
 }
 
 /// Iterates through API versions and prints info for every physical device
-pub fn iterate_devices() -> Result<(), Box<dyn Error>> {
+pub fn iterate_devices(options: &Options) -> Result<(), Box<dyn Error>> {
+    // Imported reports need neither a loader nor the GPU.
+    if let Some(path) = &options.import {
+        if let Some(snapshot) = &options.diff {
+            return snapshot::diff_files(snapshot, path, options.json, is_ansi_supported());
+        }
+        return import::read_devices(path)?
+            .iter()
+            .try_for_each(|device| print_device(device, options));
+    }
+    if options.diagnose {
+        diagnose::print_loader_report(&LoaderEnv::from_env());
+    }
+
     let entry = {
         #[cfg(not(feature = "loaded"))]
         {
@@ -334,17 +783,30 @@ pub fn iterate_devices() -> Result<(), Box<dyn Error>> {
         }
         #[cfg(feature = "loaded")]
         {
-            unsafe { Entry::load().map_err(|err| io::Error::other(format!("{err:?}")))? }
+            match unsafe { Entry::load() } {
+                Ok(entry) => entry,
+                Err(error) => {
+                    return Err(troubleshoot(Cause::LoadLoader(error.to_string()), false));
+                }
+            }
         }
     };
+    let portability_enumeration =
+        supports_instance_extension(&entry, vk::KHR_PORTABILITY_ENUMERATION_NAME);
+    let validation = options.validate.then(|| ValidationSupport::detect(&entry));
+    // Declared before any instance so it outlives the messengers using it.
+    let collector = validation::Collector::default();
 
-    let mut last_create_error = None;
-    for api_version in [
-        vk::API_VERSION_1_3,
-        vk::API_VERSION_1_2,
-        vk::API_VERSION_1_1,
-        vk::API_VERSION_1_0,
-    ] {
+    // A 1.0 loader has no `vkEnumerateInstanceVersion`.
+    let instance_version = unsafe { entry.try_enumerate_instance_version() }
+        .ok()
+        .flatten()
+        .unwrap_or(vk::API_VERSION_1_0);
+
+    // Request the highest version the loader supports, still stepping down
+    // when instance creation fails.
+    let mut last_create_error = vk::Result::ERROR_INITIALIZATION_FAILED;
+    for api_version in requested_api_versions(instance_version) {
         let app_info = vk::ApplicationInfo::default()
             .application_name(c"vkfetch-rs")
             .application_version(package_version())
@@ -354,53 +816,122 @@ pub fn iterate_devices() -> Result<(), Box<dyn Error>> {
 
         let mut extension_names = Vec::new();
         let mut flags = vk::InstanceCreateFlags::empty();
-        if supports_instance_extension(&entry, vk::KHR_PORTABILITY_ENUMERATION_NAME) {
+        if portability_enumeration {
             extension_names.push(vk::KHR_PORTABILITY_ENUMERATION_NAME.as_ptr());
             flags |= vk::InstanceCreateFlags::ENUMERATE_PORTABILITY_KHR;
         }
 
-        let create_info = vk::InstanceCreateInfo::default()
+        let mut layer_names = Vec::new();
+        let mut messenger_info = collector.messenger_create_info();
+        let debug_utils = validation.is_some_and(|support| support.debug_utils);
+        if validation.is_some_and(|support| support.layer) {
+            layer_names.push(VALIDATION_LAYER.as_ptr());
+        }
+        if debug_utils {
+            extension_names.push(vk::EXT_DEBUG_UTILS_NAME.as_ptr());
+        }
+
+        let mut create_info = vk::InstanceCreateInfo::default()
             .application_info(&app_info)
+            .enabled_layer_names(&layer_names)
             .enabled_extension_names(&extension_names)
             .flags(flags);
+        // Chained so that instance creation and destruction are checked too.
+        if debug_utils {
+            create_info = create_info.push_next(&mut messenger_info);
+        }
 
-        match unsafe { entry.create_instance(&create_info, None) } {
-            Ok(instance) => {
-                match unsafe { instance.enumerate_physical_devices() } {
-                    Ok(devices) => {
-                        for device in devices {
-                            if let Err(error) = fetch_device(&instance, device) {
-                                unsafe {
-                                    instance.destroy_instance(None);
-                                }
-                                return Err(error);
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        eprintln!("Failed to enumerate physical devices: {:?}", e);
-                    }
-                };
-                unsafe {
-                    instance.destroy_instance(None);
-                }
-            }
-            Err(e) => {
-                eprintln!("Failed to create instance: {:?}", e);
-                last_create_error = Some(e);
+        let instance = match unsafe { entry.create_instance(&create_info, None) } {
+            Ok(instance) => instance,
+            Err(error) => {
+                last_create_error = error;
                 continue;
             }
         };
+        let messenger = debug_utils
+            .then(|| Messenger::new(&entry, &instance, &collector.messenger_create_info()))
+            .flatten();
+        let result = match unsafe { instance.enumerate_physical_devices() } {
+            Ok(devices) if devices.is_empty() => Err(Cause::NoDevices),
+            Ok(devices) => Ok(run_mode(&instance, api_version, &devices, options)),
+            Err(error) => Err(Cause::EnumerateDevices(error)),
+        };
+        if let Some(messenger) = messenger {
+            messenger.destroy();
+        }
+        unsafe {
+            instance.destroy_instance(None);
+        }
 
-        return Ok(());
+        let validation_result =
+            validation.map_or(Ok(()), |support| report_validation(&collector, support));
+        return match result {
+            Ok(result) => result.and(validation_result),
+            Err(cause) => Err(troubleshoot(cause, portability_enumeration)),
+        };
     }
 
-    match last_create_error {
-        Some(error) => {
-            Err(io::Error::other(format!("failed to create Vulkan instance: {error:?}")).into())
-        }
-        None => Ok(()),
+    if let Some(support) = validation {
+        let _ = report_validation(&collector, support);
     }
+    Err(troubleshoot(
+        Cause::CreateInstance(last_create_error),
+        portability_enumeration,
+    ))
+}
+
+/// Prints the messages collected during a `--validate` run and fails the
+/// run if any of them is an error.
+fn report_validation(
+    collector: &validation::Collector,
+    support: ValidationSupport,
+) -> Result<(), Box<dyn Error>> {
+    let messages = collector.take();
+    for line in validation::report(&messages, support, is_ansi_supported()) {
+        println!("{}", line);
+    }
+    let errors = messages
+        .iter()
+        .filter(|message| message.severity == validation::Severity::Error)
+        .count();
+    if errors > 0 {
+        return Err(Box::new(ValidationFailed { errors }));
+    }
+    Ok(())
+}
+
+/// API versions to try creating an instance with, newest first, limited to
+/// what the loader reports as its instance version.
+fn requested_api_versions(instance_version: u32) -> Vec<u32> {
+    let supported = (
+        vk::api_version_major(instance_version),
+        vk::api_version_minor(instance_version),
+    );
+    [
+        vulkan14::API_VERSION_1_4,
+        vk::API_VERSION_1_3,
+        vk::API_VERSION_1_2,
+        vk::API_VERSION_1_1,
+        vk::API_VERSION_1_0,
+    ]
+    .into_iter()
+    .filter(|&version| {
+        version == vk::API_VERSION_1_0
+            || (
+                vk::api_version_major(version),
+                vk::api_version_minor(version),
+            ) <= supported
+    })
+    .collect()
+}
+
+/// Explains why no device could be printed; see [`Failure`].
+fn troubleshoot(cause: Cause, portability_enumeration: bool) -> Box<dyn Error> {
+    Box::new(Failure::diagnose(
+        cause,
+        &LoaderEnv::from_env(),
+        portability_enumeration,
+    ))
 }
 
 fn supports_instance_extension(entry: &Entry, extension_name: &CStr) -> bool {
@@ -489,8 +1020,14 @@ fn is_ansi_supported() -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::architecture::Architecture;
     use crate::device::{Device, GPUCharacteristics};
+    use crate::drm::{DrmInfo, DrmNode};
+    use crate::pcie::{LinkState, PcieLink};
+    use crate::sensors::{Clock, Temperature};
+    use crate::sysfs::PciAddress;
     use crate::vendor::Vendor;
+    use crate::virtualization::{LayeredDriver, LayeredDriverKind};
 
     /// For testing purposes we use the Unknown vendor variant.
     impl Vendor {
@@ -500,7 +1037,7 @@ mod tests {
     }
 
     /// Creates a dummy PhysicalDevice instance for tests.
-    fn dummy_physical_device() -> Device {
+    pub(crate) fn dummy_physical_device() -> Device {
         Device {
             vendor: Vendor::dummy(),
             device_name: "TestDevice".to_string(),
@@ -508,8 +1045,60 @@ mod tests {
             device_id: 0xDEADBEEF,
             vendor_id: 0xBEEF,
             driver_name: "TestDriver".to_string(),
+            driver_id: vk::DriverId::MESA_RADV,
             driver_info: "TestDriverInfo\nSecond line".to_string(),
             api_version: "1.2.3.4".to_string(),
+            architecture: Some(Architecture::Rdna2),
+            layered_driver: Some(LayeredDriver {
+                kind: LayeredDriverKind::Venus,
+                host_gpu: Some("Host GPU".to_string()),
+            }),
+            drm: Some(DrmInfo {
+                primary: None,
+                render: Some(DrmNode {
+                    major: 226,
+                    minor: 128,
+                    path: Some("/dev/dri/renderD128".into()),
+                }),
+                kernel_driver: Some("amdgpu".to_string()),
+            }),
+            pci_address: Some(PciAddress {
+                domain: 0,
+                bus: 3,
+                device: 0,
+                function: 0,
+            }),
+            sysfs_dir: None,
+            sensors: Some(Sensors {
+                temperatures: vec![Temperature {
+                    label: Some("edge".to_string()),
+                    celsius: 45.0,
+                }],
+                fan_rpm: Some(1200),
+                power_watts: Some(35.0),
+                power_cap_watts: Some(200.0),
+                core_clock: Some(Clock {
+                    current_mhz: Some(500),
+                    max_mhz: Some(2500),
+                }),
+                memory_clock: None,
+            }),
+            kernel_stats: Some(KernelStats {
+                gpu_busy: Some(0.37),
+                memory_busy: None,
+                vram_used: Some(2 * 1024 * 1024 * 1024),
+                vram_total: None,
+            }),
+            pcie_link: Some(PcieLink {
+                current: LinkState {
+                    speed_gts: Some(8.0),
+                    width: Some(16),
+                },
+                max: LinkState {
+                    speed_gts: Some(16.0),
+                    width: Some(16),
+                },
+            }),
             heapbudget: Some(8 * 1024 * 1024 * 1024), // 8 GiB
             heapsize: 10 * 1024 * 1024 * 1024,        // 10 GB
             characteristics: GPUCharacteristics {
@@ -524,6 +1113,19 @@ mod tests {
                 wavefront_size: Some(32),
                 streaming_multiprocessors: Some(46),
                 warps_per_sm: Some(32),
+                shader_cores: Some(12),
+                warps_per_core: Some(64),
+                pixel_rate: Some(8),
+                texel_rate: Some(8),
+                fma_rate: Some(64),
+                execution_units: Some(96),
+                xe_cores: Some(6),
+                min_subgroup_size: Some(8),
+                max_subgroup_size: Some(32),
+                tile_memory_size: Some(1024 * 1024),
+                supports_tile_properties: Some(true),
+                unsupported_portability_features: Some(vec!["triangleFans"]),
+                min_vertex_input_binding_stride_alignment: Some(4),
                 max_image_dimension_2d: 16384,
                 max_compute_shared_memory_size: 65536,
                 max_compute_work_group_invocations: 1024,
@@ -554,18 +1156,126 @@ mod tests {
         assert!(info[2].contains("0xDEADBEEF"));
         assert!(info[2].contains("0xBEEF"));
         assert!(info.iter().any(|line| line.contains("Second line")));
+        assert!(info.iter().any(|line| line.contains("RDNA 2")));
+        assert!(
+            info.iter()
+                .any(|line| line.contains("Venus on host Vulkan"))
+        );
+        assert!(info.iter().any(|line| line.contains("Host GPU")));
+        assert!(
+            info.iter()
+                .any(|line| line.contains("/dev/dri/renderD128") && line.contains("amdgpu"))
+        );
+        assert!(info.iter().any(|line| line.contains("Shader Cores")));
+        assert!(
+            info.iter()
+                .any(|line| line.contains("FMAs per Clock per Core"))
+        );
         assert!(info.iter().any(|line| line.contains("8 / 10")));
         assert!(info.iter().any(|line| line.contains("32")));
+        assert!(info.iter().any(|line| line.contains("45.0 °C (edge)")));
+        assert!(info.iter().any(|line| line.contains("35.0 W / 200.0 W")));
+        assert!(info.iter().any(|line| line.contains("500 / 2500 MHz")));
+        assert!(
+            info.iter()
+                .any(|line| line.contains("Gen3 x16 (degraded) / Gen4 x16"))
+        );
+        assert!(
+            info.iter()
+                .any(|line| line.contains("GPU Load") && line.contains("37 %"))
+        );
+        assert!(
+            info.iter()
+                .any(|line| line.contains("VRAM") && line.contains("2.000 GiB"))
+        );
     }
 
     #[test]
     fn test_get_device_info_without_ansi() {
-        let device = dummy_physical_device();
+        let mut device = dummy_physical_device();
+        // Without kernel statistics the Vulkan budget is shown.
+        device.kernel_stats = None;
         let info = get_device_info(&device, EMPTY, false);
         assert!(!info.iter().any(|line| line.contains("\x1B")));
         assert!(info.iter().any(|line| line.contains("8.000 GiB")));
     }
 
+    #[test]
+    fn test_get_device_info_kernel_vram() {
+        let mut device = dummy_physical_device();
+        // Kernel statistics take precedence over the Vulkan budget.
+        device.kernel_stats = Some(KernelStats {
+            vram_used: Some(3 * 1024 * 1024 * 1024),
+            vram_total: Some(12 * 1024 * 1024 * 1024),
+            ..Default::default()
+        });
+        let info = get_device_info(&device, EMPTY, false);
+        assert!(
+            info.iter()
+                .any(|line| line.contains("VRAM: 3.000 GiB / 12.000 GiB"))
+        );
+    }
+
+    #[test]
+    fn test_get_device_info_estimate() {
+        let mut device = dummy_physical_device();
+        device.vendor = Vendor::AMD;
+        device.device_id = 0x744C;
+        device.architecture = Some(Architecture::Rdna3);
+        device.characteristics.compute_units = Some(96);
+        device.characteristics.active_compute_units = Some(96);
+        device.sensors = None;
+        let info = get_device_info(&device, EMPTY, false);
+        assert!(
+            info.iter()
+                .any(|line| line.contains("Est. FP32: ~61.44 TFLOPS"))
+        );
+        assert!(info.iter().any(|line| line.contains("~960.0 GB/s")));
+    }
+
+    #[test]
+    fn test_requested_api_versions() {
+        assert_eq!(
+            requested_api_versions(vk::make_api_version(0, 1, 4, 309)),
+            [
+                vulkan14::API_VERSION_1_4,
+                vk::API_VERSION_1_3,
+                vk::API_VERSION_1_2,
+                vk::API_VERSION_1_1,
+                vk::API_VERSION_1_0,
+            ]
+        );
+        assert_eq!(
+            requested_api_versions(vk::make_api_version(0, 1, 2, 198)),
+            [
+                vk::API_VERSION_1_2,
+                vk::API_VERSION_1_1,
+                vk::API_VERSION_1_0
+            ]
+        );
+        assert_eq!(
+            requested_api_versions(vk::API_VERSION_1_0),
+            [vk::API_VERSION_1_0]
+        );
+    }
+
+    #[test]
+    fn test_process_table() {
+        let processes = [ProcessUsage {
+            pid: 4242,
+            command: "game".to_string(),
+            vram_bytes: 512 * 1024 * 1024,
+            engine_ns: 2_500_000_000,
+        }];
+        let table = get_process_table(&processes, EMPTY, false);
+        assert!(table[1].contains("PID") && table[1].contains("Command"));
+        assert!(table[2].contains("4242"));
+        assert!(table[2].contains("512.000 MiB"));
+        assert!(table[2].contains("2.5s"));
+        assert!(table[2].ends_with("game"));
+        assert!(get_process_table(&[], EMPTY, false)[1].contains("none visible"));
+    }
+
     #[test]
     fn test_unknown_memory_pressure_meter() {
         let meter = format_meter(6, None, false);
@@ -0,0 +1,914 @@
+use ash::vk;
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    env,
+    ffi::{OsStr, OsString},
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use crate::sysfs::SysRoot;
+
+/// Replaces the driver search entirely; `VK_ICD_FILENAMES` is its older name.
+pub const DRIVER_FILES_VARS: [&str; 2] = ["VK_DRIVER_FILES", "VK_ICD_FILENAMES"];
+/// Adds drivers in front of the standard search paths.
+pub const ADD_DRIVER_FILES_VAR: &str = "VK_ADD_DRIVER_FILES";
+/// Explicit layers every instance enables, separated like `PATH`.
+pub const INSTANCE_LAYERS_VAR: &str = "VK_INSTANCE_LAYERS";
+/// Comma-separated layer filters, e.g. `~implicit~` or `*MangoHud*`.
+pub const LAYERS_ENABLE_VAR: &str = "VK_LOADER_LAYERS_ENABLE";
+pub const LAYERS_DISABLE_VAR: &str = "VK_LOADER_LAYERS_DISABLE";
+
+/// What the Vulkan loader sees when it looks for manifests: the system root
+/// plus the environment variables that redirect or extend the search.
+#[derive(Debug, Clone, Default)]
+pub struct LoaderEnv {
+    root: SysRoot,
+    vars: HashMap<String, OsString>,
+}
+
+/// A file or directory the loader reads manifests from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchPath {
+    pub path: PathBuf,
+    /// The environment variable that added this path, if any.
+    pub origin: Option<&'static str>,
+}
+
+/// A manifest that exists but could not be used.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestError {
+    pub path: PathBuf,
+    pub message: String,
+}
+
+impl fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path.display(), self.message)
+    }
+}
+
+impl std::error::Error for ManifestError {}
+
+/// An installable client driver manifest from an `icd.d` directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IcdManifest {
+    pub path: PathBuf,
+    pub origin: Option<&'static str>,
+    pub file_format_version: Option<String>,
+    /// `library_path` as written in the manifest.
+    pub library_path: String,
+    /// Where the library was found, if it was.
+    pub library: Option<PathBuf>,
+    pub api_version: Option<String>,
+    /// `"32"` or `"64"` when the manifest restricts the driver to one.
+    pub library_arch: Option<String>,
+    pub is_portability_driver: bool,
+}
+
+/// Implicit layers load on their own; explicit layers only on request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayerKind {
+    Implicit,
+    Explicit,
+}
+
+/// A layer from an `implicit_layer.d` or `explicit_layer.d` manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayerManifest {
+    pub path: PathBuf,
+    pub origin: Option<&'static str>,
+    pub kind: LayerKind,
+    pub name: String,
+    /// `None` for meta layers, which only group `component_layers`.
+    pub library_path: Option<String>,
+    pub library: Option<PathBuf>,
+    pub library_arch: Option<String>,
+    pub api_version: Option<String>,
+    pub implementation_version: Option<String>,
+    pub description: Option<String>,
+    pub component_layers: Vec<String>,
+    /// Variables and the values that turn an implicit layer on.
+    pub enable_environment: Vec<(String, String)>,
+    /// Variables that turn an implicit layer off when set to anything.
+    pub disable_environment: Vec<(String, String)>,
+}
+
+/// Whether and why a layer would be loaded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LayerActivation {
+    /// An implicit layer that loads into every instance.
+    Active,
+    /// Loaded because of this environment variable.
+    EnabledBy(String),
+    /// Not loaded because of this environment variable.
+    DisabledBy(String),
+    /// An implicit layer whose enable variable is not set to its value.
+    NotTriggered,
+    /// An explicit layer that nothing requests.
+    Available,
+}
+
+impl fmt::Display for LayerActivation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayerActivation::Active => write!(f, "active"),
+            LayerActivation::EnabledBy(name) => write!(f, "enabled by {}", name),
+            LayerActivation::DisabledBy(name) => write!(f, "disabled by {}", name),
+            LayerActivation::NotTriggered => write!(f, "not triggered"),
+            LayerActivation::Available => write!(f, "available"),
+        }
+    }
+}
+
+impl LoaderEnv {
+    pub fn new(root: SysRoot, vars: impl IntoIterator<Item = (String, OsString)>) -> Self {
+        Self {
+            root,
+            vars: vars.into_iter().collect(),
+        }
+    }
+
+    /// Uses the process environment and `VKFETCH_SYSROOT`.
+    pub fn from_env() -> Self {
+        Self::new(
+            SysRoot::from_env(),
+            env::vars_os().filter_map(|(name, value)| Some((name.into_string().ok()?, value))),
+        )
+    }
+
+    pub fn root(&self) -> &SysRoot {
+        &self.root
+    }
+
+    /// Returns a variable unless it is unset or empty, as the loader does.
+    pub fn var(&self, name: &str) -> Option<&OsStr> {
+        self.vars
+            .get(name)
+            .map(OsString::as_os_str)
+            .filter(|value| !value.is_empty())
+    }
+
+    /// Every set, non-empty variable in name order.
+    pub fn vars(&self) -> impl Iterator<Item = (&str, &OsStr)> {
+        let mut vars = self
+            .vars
+            .iter()
+            .filter(|(_, value)| !value.is_empty())
+            .map(|(name, value)| (name.as_str(), value.as_os_str()))
+            .collect::<Vec<_>>();
+        vars.sort();
+        vars.into_iter()
+    }
+
+    fn path_list(&self, name: &str) -> Vec<PathBuf> {
+        self.var(name)
+            .map(|value| env::split_paths(value).filter(|path| !path.as_os_str().is_empty()))
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    /// The XDG and system directories whose `vulkan/` subdirectories hold
+    /// manifests, in the order the loader searches them.
+    fn search_roots(&self) -> Vec<PathBuf> {
+        let home = self.var("HOME").map(PathBuf::from);
+        let user_dir = |var: &str, fallback: &str| {
+            self.var(var)
+                .map(PathBuf::from)
+                .or_else(|| home.as_ref().map(|home| home.join(fallback)))
+        };
+        let system_dirs = |var: &str, fallback: &[&str]| {
+            let dirs = self.path_list(var);
+            if dirs.is_empty() {
+                fallback.iter().map(|dir| self.root.join(dir)).collect()
+            } else {
+                dirs
+            }
+        };
+
+        let mut roots = Vec::new();
+        roots.extend(user_dir("XDG_CONFIG_HOME", ".config"));
+        roots.extend(system_dirs("XDG_CONFIG_DIRS", &["/etc/xdg"]));
+        roots.push(self.root.join("/etc"));
+        roots.extend(user_dir("XDG_DATA_HOME", ".local/share"));
+        roots.extend(system_dirs(
+            "XDG_DATA_DIRS",
+            &["/usr/local/share", "/usr/share"],
+        ));
+
+        let mut unique = Vec::new();
+        for root in roots {
+            if !unique.contains(&root) {
+                unique.push(root);
+            }
+        }
+        unique
+    }
+
+    /// Where one kind of manifest is searched, in loader order. The first
+    /// set override variable replaces the standard directories; the add
+    /// variable goes in front of them.
+    fn search_paths(
+        &self,
+        overrides: &[&'static str],
+        add: &'static str,
+        subdir: &str,
+    ) -> Vec<SearchPath> {
+        let from_var = |name: &'static str| {
+            self.path_list(name)
+                .into_iter()
+                .map(move |path| SearchPath {
+                    path,
+                    origin: Some(name),
+                })
+        };
+
+        if let Some(&name) = overrides.iter().find(|name| self.var(name).is_some()) {
+            return from_var(name).collect();
+        }
+
+        from_var(add)
+            .chain(self.search_roots().into_iter().map(|root| SearchPath {
+                path: root.join(subdir),
+                origin: None,
+            }))
+            .collect()
+    }
+
+    /// Where driver manifests are searched, in loader order.
+    pub fn driver_search_paths(&self) -> Vec<SearchPath> {
+        self.search_paths(&DRIVER_FILES_VARS, ADD_DRIVER_FILES_VAR, "vulkan/icd.d")
+    }
+
+    /// Where layer manifests of the given kind are searched, in loader order.
+    pub fn layer_search_paths(&self, kind: LayerKind) -> Vec<SearchPath> {
+        match kind {
+            LayerKind::Implicit => self.search_paths(
+                &["VK_IMPLICIT_LAYER_PATH"],
+                "VK_ADD_IMPLICIT_LAYER_PATH",
+                "vulkan/implicit_layer.d",
+            ),
+            LayerKind::Explicit => self.search_paths(
+                &["VK_LAYER_PATH"],
+                "VK_ADD_LAYER_PATH",
+                "vulkan/explicit_layer.d",
+            ),
+        }
+    }
+
+    /// Finds and parses every driver manifest the loader would consider.
+    pub fn driver_manifests(&self) -> Vec<Result<IcdManifest, ManifestError>> {
+        read_manifests(&self.driver_search_paths(), |path, origin, contents| {
+            let mut manifest = IcdManifest::parse(path, contents)?;
+            manifest.origin = origin;
+            manifest.library = self.resolve_library(
+                path,
+                &manifest.library_path,
+                manifest.library_arch.as_deref(),
+            );
+            Ok(vec![manifest])
+        })
+    }
+
+    /// Finds and parses every layer manifest of the given kind.
+    pub fn layer_manifests(&self, kind: LayerKind) -> Vec<Result<LayerManifest, ManifestError>> {
+        read_manifests(&self.layer_search_paths(kind), |path, origin, contents| {
+            let mut layers = LayerManifest::parse(path, kind, contents)?;
+            for layer in &mut layers {
+                layer.origin = origin;
+                layer.library = layer.library_path.as_deref().and_then(|library_path| {
+                    self.resolve_library(path, library_path, layer.library_arch.as_deref())
+                });
+            }
+            Ok(layers)
+        })
+    }
+
+    /// Whether the loader would load `layer` into an instance that enables
+    /// no layers itself.
+    pub fn layer_activation(&self, layer: &LayerManifest) -> LayerActivation {
+        let filter = |name: &str| {
+            self.var(name)
+                .and_then(OsStr::to_str)
+                .is_some_and(|filter| filter_matches(filter, &layer.name, layer.kind))
+        };
+        // The enable filter wins over the disable filter.
+        if filter(LAYERS_ENABLE_VAR) {
+            return LayerActivation::EnabledBy(LAYERS_ENABLE_VAR.to_string());
+        }
+
+        match layer.kind {
+            LayerKind::Implicit => {
+                if filter(LAYERS_DISABLE_VAR) {
+                    return LayerActivation::DisabledBy(LAYERS_DISABLE_VAR.to_string());
+                }
+                // Any value disables; only the declared value enables.
+                if let Some((name, _)) = layer
+                    .disable_environment
+                    .iter()
+                    .find(|(name, _)| self.vars.contains_key(name))
+                {
+                    return LayerActivation::DisabledBy(name.clone());
+                }
+                if !layer.enable_environment.is_empty()
+                    && !layer
+                        .enable_environment
+                        .iter()
+                        .any(|(name, value)| self.var(name) == Some(OsStr::new(value)))
+                {
+                    return LayerActivation::NotTriggered;
+                }
+                LayerActivation::Active
+            }
+            LayerKind::Explicit => {
+                let requested = self
+                    .path_list(INSTANCE_LAYERS_VAR)
+                    .iter()
+                    .any(|name| name.as_os_str() == layer.name.as_str());
+                if requested {
+                    LayerActivation::EnabledBy(INSTANCE_LAYERS_VAR.to_string())
+                } else {
+                    LayerActivation::Available
+                }
+            }
+        }
+    }
+
+    /// Disables every implicit layer for instances created afterwards,
+    /// through `VK_LOADER_LAYERS_DISABLE` and, for loaders too old to know
+    /// it, each layer's own `disable_environment` variable.
+    ///
+    /// # Safety
+    ///
+    /// Modifies the process environment, so no other thread may be reading
+    /// or writing it; see [`env::set_var`].
+    pub unsafe fn disable_implicit_layers(&self) {
+        let filter = match self.var(LAYERS_DISABLE_VAR) {
+            Some(existing) => {
+                let mut filter = existing.to_os_string();
+                filter.push(",~implicit~");
+                filter
+            }
+            None => OsString::from("~implicit~"),
+        };
+        unsafe { env::set_var(LAYERS_DISABLE_VAR, filter) };
+
+        for layer in self
+            .layer_manifests(LayerKind::Implicit)
+            .into_iter()
+            .flatten()
+        {
+            for (name, value) in &layer.disable_environment {
+                unsafe { env::set_var(name, value) };
+            }
+        }
+    }
+
+    /// Resolves a manifest's library the way `dlopen` would: relative to the
+    /// manifest if the path contains a slash, otherwise through
+    /// `LD_LIBRARY_PATH`, `ld.so.conf` and the default library directories.
+    pub fn resolve_library(
+        &self,
+        manifest: &Path,
+        library_path: &str,
+        arch: Option<&str>,
+    ) -> Option<PathBuf> {
+        let matches_arch = |path: &Path| {
+            path.is_file()
+                && arch
+                    .zip(elf_arch(path))
+                    .is_none_or(|(wanted, found)| wanted == found)
+        };
+
+        if library_path.contains('/') {
+            let path = manifest
+                .parent()
+                .unwrap_or(Path::new(""))
+                .join(library_path);
+            return matches_arch(&path).then_some(path);
+        }
+        self.library_dirs()
+            .into_iter()
+            .map(|dir| dir.join(library_path))
+            .find(|path| matches_arch(path))
+    }
+
+    fn library_dirs(&self) -> Vec<PathBuf> {
+        let mut dirs = self.path_list("LD_LIBRARY_PATH");
+
+        let conf_dir = self.root.join("/etc/ld.so.conf.d");
+        let mut conf_files = vec![self.root.join("/etc/ld.so.conf")];
+        conf_files.extend(files_with_extension(&conf_dir, "conf"));
+        for contents in conf_files
+            .iter()
+            .filter_map(|path| fs::read_to_string(path).ok())
+        {
+            dirs.extend(
+                contents
+                    .lines()
+                    .map(|line| line.split('#').next().unwrap_or_default().trim())
+                    .filter(|line| line.starts_with('/'))
+                    .map(|dir| self.root.join(dir)),
+            );
+        }
+
+        dirs.extend(
+            ["/lib64", "/usr/lib64", "/lib", "/usr/lib"]
+                .iter()
+                .map(|dir| self.root.join(dir)),
+        );
+        dirs
+    }
+}
+
+impl IcdManifest {
+    /// Parses the contents of a driver manifest. The library is resolved by
+    /// [`LoaderEnv::driver_manifests`].
+    pub fn parse(path: &Path, contents: &str) -> Result<Self, String> {
+        let json: Value = serde_json::from_str(contents).map_err(|error| error.to_string())?;
+        let icd = json.get("ICD").ok_or("missing \"ICD\" object")?;
+        let library_path =
+            string_field(icd, "library_path").ok_or("missing \"ICD.library_path\"")?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            origin: None,
+            file_format_version: string_field(&json, "file_format_version"),
+            library_path,
+            library: None,
+            api_version: string_field(icd, "api_version"),
+            library_arch: string_field(icd, "library_arch"),
+            is_portability_driver: icd
+                .get("is_portability_driver")
+                .and_then(Value::as_bool)
+                .unwrap_or(false),
+        })
+    }
+
+    /// Whether this manifest's library is the driver that reports `driver_id`.
+    pub fn provides(&self, driver_id: vk::DriverId) -> bool {
+        let file_name = Path::new(&self.library_path)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_ascii_lowercase())
+            .unwrap_or_default();
+        library_hint(driver_id).is_some_and(|hint| file_name.contains(hint))
+    }
+}
+
+impl LayerManifest {
+    /// Parses a layer manifest, which holds either one `layer` or an array
+    /// of `layers`. Libraries are resolved by [`LoaderEnv::layer_manifests`].
+    pub fn parse(path: &Path, kind: LayerKind, contents: &str) -> Result<Vec<Self>, String> {
+        let json: Value = serde_json::from_str(contents).map_err(|error| error.to_string())?;
+        let layers = match (
+            json.get("layer"),
+            json.get("layers").and_then(Value::as_array),
+        ) {
+            (Some(layer), _) => vec![layer],
+            (None, Some(layers)) => layers.iter().collect(),
+            (None, None) => return Err("missing \"layer\" or \"layers\"".to_string()),
+        };
+
+        layers
+            .into_iter()
+            .map(|layer| {
+                let name = string_field(layer, "name").ok_or("layer without \"name\"")?;
+                let library_path = string_field(layer, "library_path");
+                let component_layers = layer
+                    .get("component_layers")
+                    .and_then(Value::as_array)
+                    .map(|layers| {
+                        layers
+                            .iter()
+                            .filter_map(Value::as_str)
+                            .map(str::to_string)
+                            .collect::<Vec<_>>()
+                    })
+                    .unwrap_or_default();
+                if library_path.is_none() && component_layers.is_empty() {
+                    return Err(format!(
+                        "{} has neither \"library_path\" nor \"component_layers\"",
+                        name
+                    ));
+                }
+
+                Ok(Self {
+                    path: path.to_path_buf(),
+                    origin: None,
+                    kind,
+                    name,
+                    library_path,
+                    library: None,
+                    library_arch: string_field(layer, "library_arch"),
+                    api_version: string_field(layer, "api_version"),
+                    implementation_version: string_field(layer, "implementation_version"),
+                    description: string_field(layer, "description"),
+                    component_layers,
+                    enable_environment: environment(layer, "enable_environment"),
+                    disable_environment: environment(layer, "disable_environment"),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Picks the manifest that most likely produced a device with `driver_id`,
+/// preferring one whose library was found for this process's architecture.
+pub fn match_driver(manifests: &[IcdManifest], driver_id: vk::DriverId) -> Option<&IcdManifest> {
+    let own_arch = if cfg!(target_pointer_width = "64") {
+        "64"
+    } else {
+        "32"
+    };
+    manifests
+        .iter()
+        .filter(|manifest| manifest.provides(driver_id))
+        .min_by_key(|manifest| {
+            (
+                manifest.library.is_none(),
+                manifest
+                    .library_arch
+                    .as_deref()
+                    .is_some_and(|arch| arch != own_arch),
+            )
+        })
+}
+
+/// A fragment of the library file name each known driver ships as.
+fn library_hint(driver_id: vk::DriverId) -> Option<&'static str> {
+    Some(match driver_id {
+        vk::DriverId::MESA_RADV => "radeon",
+        vk::DriverId::AMD_OPEN_SOURCE | vk::DriverId::AMD_PROPRIETARY => "amdvlk",
+        vk::DriverId::NVIDIA_PROPRIETARY => "nvidia",
+        vk::DriverId::INTEL_OPEN_SOURCE_MESA => "intel",
+        vk::DriverId::MESA_NVK => "nouveau",
+        vk::DriverId::MESA_LLVMPIPE => "lvp",
+        vk::DriverId::MESA_TURNIP => "freedreno",
+        vk::DriverId::MESA_V3DV => "broadcom",
+        vk::DriverId::MESA_PANVK => "panfrost",
+        vk::DriverId::MESA_VENUS => "virtio",
+        vk::DriverId::MESA_DOZEN => "dzn",
+        vk::DriverId::MESA_AGXV => "asahi",
+        vk::DriverId::IMAGINATION_OPEN_SOURCE_MESA => "powervr",
+        vk::DriverId::GOOGLE_SWIFTSHADER => "swiftshader",
+        vk::DriverId::MOLTENVK => "moltenvk",
+        vk::DriverId::ARM_PROPRIETARY => "mali",
+        _ => return None,
+    })
+}
+
+fn string_field(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(Value::as_str).map(str::to_string)
+}
+
+/// Reads an object of variable names to values, e.g. `enable_environment`.
+fn environment(layer: &Value, key: &str) -> Vec<(String, String)> {
+    layer
+        .get(key)
+        .and_then(Value::as_object)
+        .map(|variables| {
+            variables
+                .iter()
+                .map(|(name, value)| {
+                    let value = value
+                        .as_str()
+                        .map(str::to_string)
+                        .unwrap_or_else(|| value.to_string());
+                    (name.clone(), value)
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Whether a `VK_LOADER_LAYERS_ENABLE`/`_DISABLE` filter selects a layer.
+/// Names compare case-insensitively and may start or end with `*`.
+fn filter_matches(filter: &str, name: &str, kind: LayerKind) -> bool {
+    let name = name.to_ascii_lowercase();
+    filter
+        .split(',')
+        .map(str::trim)
+        .any(|pattern| match pattern {
+            "~all~" => true,
+            "~implicit~" => kind == LayerKind::Implicit,
+            "~explicit~" => kind == LayerKind::Explicit,
+            _ => {
+                let pattern = pattern.to_ascii_lowercase();
+                let core = pattern.trim_matches('*');
+                match (pattern.starts_with('*'), pattern.ends_with('*')) {
+                    (true, true) => name.contains(core),
+                    (true, false) => name.ends_with(core),
+                    (false, true) => name.starts_with(core),
+                    (false, false) => name == core,
+                }
+            }
+        })
+}
+
+/// Reads and parses the manifests in `search`; one file may hold several.
+fn read_manifests<T>(
+    search: &[SearchPath],
+    mut parse: impl FnMut(&Path, Option<&'static str>, &str) -> Result<Vec<T>, String>,
+) -> Vec<Result<T, ManifestError>> {
+    let mut manifests = Vec::new();
+    for (path, origin) in manifest_files(search) {
+        let parsed = fs::read_to_string(&path)
+            .map_err(|error| error.to_string())
+            .and_then(|contents| parse(&path, origin, &contents));
+        match parsed {
+            Ok(parsed) => manifests.extend(parsed.into_iter().map(Ok)),
+            Err(message) => manifests.push(Err(ManifestError { path, message })),
+        }
+    }
+    manifests
+}
+
+/// Expands directories to their manifests, sorted by name, and keeps files.
+/// Paths that do not exist are skipped, as the loader does.
+fn manifest_files(search: &[SearchPath]) -> Vec<(PathBuf, Option<&'static str>)> {
+    let mut files = Vec::new();
+    for entry in search {
+        if entry.path.is_dir() {
+            files.extend(
+                files_with_extension(&entry.path, "json")
+                    .into_iter()
+                    .map(|path| (path, entry.origin)),
+            );
+        } else if entry.path.is_file() {
+            files.push((entry.path.clone(), entry.origin));
+        }
+    }
+    files
+}
+
+/// Returns the files in `dir` with the given extension, sorted by name.
+fn files_with_extension(dir: &Path, extension: &str) -> Vec<PathBuf> {
+    let mut files = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension() == Some(OsStr::new(extension)) && path.is_file())
+        .collect::<Vec<_>>();
+    files.sort();
+    files
+}
+
+/// Returns `"32"` or `"64"` from an ELF header, the values of `library_arch`.
+fn elf_arch(path: &Path) -> Option<&'static str> {
+    use std::io::Read;
+
+    let mut header = [0; 5];
+    fs::File::open(path).ok()?.read_exact(&mut header).ok()?;
+    match header {
+        [0x7F, b'E', b'L', b'F', 1] => Some("32"),
+        [0x7F, b'E', b'L', b'F', 2] => Some("64"),
+        _ => None,
+    }
+}
+
+#[cfg(all(test, unix))]
+pub(crate) mod tests {
+    use super::*;
+    use crate::sysfs::fixture::Fixture;
+
+    pub(crate) fn loader_env(fixture: &Fixture, vars: &[(&str, &str)]) -> LoaderEnv {
+        LoaderEnv::new(
+            fixture.root(),
+            vars.iter()
+                .map(|(name, value)| (name.to_string(), OsString::from(value))),
+        )
+    }
+
+    const RADV: &str = r#"{
+        "file_format_version": "1.0.1",
+        "ICD": {
+            "library_path": "libvulkan_radeon.so",
+            "library_arch": "64",
+            "api_version": "1.3.278"
+        }
+    }"#;
+
+    #[test]
+    fn test_standard_search_paths() {
+        let fixture = Fixture::new("loader-search");
+        let env = loader_env(&fixture, &[("HOME", "/home/user")]);
+        let paths = env
+            .driver_search_paths()
+            .into_iter()
+            .map(|search| search.path)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            [
+                PathBuf::from("/home/user/.config/vulkan/icd.d"),
+                fixture.path("etc/xdg/vulkan/icd.d"),
+                fixture.path("etc/vulkan/icd.d"),
+                PathBuf::from("/home/user/.local/share/vulkan/icd.d"),
+                fixture.path("usr/local/share/vulkan/icd.d"),
+                fixture.path("usr/share/vulkan/icd.d"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_driver_overrides() {
+        let fixture = Fixture::new("loader-overrides");
+        let env = loader_env(
+            &fixture,
+            &[
+                ("VK_ICD_FILENAMES", "/old.json"),
+                ("VK_DRIVER_FILES", "/a.json:/b"),
+                ("VK_ADD_DRIVER_FILES", "/extra.json"),
+            ],
+        );
+        assert_eq!(
+            env.driver_search_paths(),
+            [
+                SearchPath {
+                    path: "/a.json".into(),
+                    origin: Some("VK_DRIVER_FILES"),
+                },
+                SearchPath {
+                    path: "/b".into(),
+                    origin: Some("VK_DRIVER_FILES"),
+                },
+            ]
+        );
+
+        let env = loader_env(&fixture, &[("VK_ADD_DRIVER_FILES", "/extra.json")]);
+        let paths = env.driver_search_paths();
+        assert_eq!(paths[0].path, Path::new("/extra.json"));
+        assert_eq!(paths[0].origin, Some(ADD_DRIVER_FILES_VAR));
+        assert!(paths[1..].iter().all(|search| search.origin.is_none()));
+    }
+
+    #[test]
+    fn test_driver_manifests() {
+        let fixture = Fixture::new("loader-manifests");
+        fixture.write("usr/share/vulkan/icd.d/radeon_icd.x86_64.json", RADV);
+        fixture.write("usr/share/vulkan/icd.d/broken.json", "{\"ICD\": {}}");
+        fixture.write("usr/share/vulkan/icd.d/README", "not a manifest");
+        fixture.write(
+            "etc/vulkan/icd.d/lvp_icd.json",
+            r#"{"ICD": {"library_path": "../lib/libvulkan_lvp.so"}}"#,
+        );
+        fixture.write("etc/vulkan/lib/libvulkan_lvp.so", "\x7fELF\x02");
+        fixture.write("usr/lib/libvulkan_radeon.so", "\x7fELF\x01");
+        fixture.write("usr/lib64/libvulkan_radeon.so", "\x7fELF\x02");
+
+        let manifests = loader_env(&fixture, &[]).driver_manifests();
+        assert_eq!(manifests.len(), 3);
+
+        let lvp = manifests[0].as_ref().unwrap();
+        assert_eq!(lvp.library_path, "../lib/libvulkan_lvp.so");
+        assert_eq!(
+            lvp.library,
+            Some(fixture.path("etc/vulkan/icd.d/../lib/libvulkan_lvp.so"))
+        );
+        assert_eq!(lvp.api_version, None);
+
+        let error = manifests[1].as_ref().unwrap_err();
+        assert_eq!(error.message, "missing \"ICD.library_path\"");
+
+        // The 32-bit build in /usr/lib is skipped for a 64-bit manifest.
+        let radv = manifests[2].as_ref().unwrap();
+        assert_eq!(radv.file_format_version.as_deref(), Some("1.0.1"));
+        assert_eq!(radv.api_version.as_deref(), Some("1.3.278"));
+        assert_eq!(
+            radv.library,
+            Some(fixture.path("usr/lib64/libvulkan_radeon.so"))
+        );
+    }
+
+    const MANGOHUD: &str = r#"{
+        "file_format_version": "1.0.0",
+        "layer": {
+            "name": "VK_LAYER_MANGOHUD_overlay_x86_64",
+            "type": "GLOBAL",
+            "library_path": "libMangoHud.so",
+            "api_version": "1.3.0",
+            "implementation_version": "1",
+            "description": "Vulkan Hud Overlay",
+            "enable_environment": { "MANGOHUD": "1" },
+            "disable_environment": { "DISABLE_MANGOHUD": "1" }
+        }
+    }"#;
+
+    #[test]
+    fn test_layer_manifests() {
+        let fixture = Fixture::new("loader-layers");
+        fixture.write("usr/share/vulkan/implicit_layer.d/MangoHud.json", MANGOHUD);
+        fixture.write(
+            "usr/share/vulkan/explicit_layer.d/layers.json",
+            r#"{"file_format_version": "1.0.1", "layers": [
+                {"name": "VK_LAYER_KHRONOS_validation", "library_path": "libVkLayer_khronos_validation.so"},
+                {"name": "VK_LAYER_meta", "component_layers": ["VK_LAYER_KHRONOS_validation"]},
+                {"name": "VK_LAYER_broken"}
+            ]}"#,
+        );
+        let env = loader_env(&fixture, &[]);
+
+        let implicit = env.layer_manifests(LayerKind::Implicit);
+        assert_eq!(implicit.len(), 1);
+        let mangohud = implicit[0].as_ref().unwrap();
+        assert_eq!(mangohud.name, "VK_LAYER_MANGOHUD_overlay_x86_64");
+        assert_eq!(mangohud.library_path.as_deref(), Some("libMangoHud.so"));
+        assert_eq!(mangohud.api_version.as_deref(), Some("1.3.0"));
+        assert_eq!(
+            mangohud.enable_environment,
+            [("MANGOHUD".to_string(), "1".to_string())]
+        );
+
+        // A broken layer in a `layers` array fails the whole file.
+        let explicit = env.layer_manifests(LayerKind::Explicit);
+        assert_eq!(explicit.len(), 1);
+        assert_eq!(
+            explicit[0].as_ref().unwrap_err().message,
+            "VK_LAYER_broken has neither \"library_path\" nor \"component_layers\""
+        );
+
+        fixture.write(
+            "usr/share/vulkan/explicit_layer.d/layers.json",
+            r#"{"layers": [{"name": "VK_LAYER_meta", "component_layers": ["A", "B"]}]}"#,
+        );
+        let explicit = env.layer_manifests(LayerKind::Explicit);
+        let meta = explicit[0].as_ref().unwrap();
+        assert_eq!(meta.library_path, None);
+        assert_eq!(meta.component_layers, ["A", "B"]);
+    }
+
+    #[test]
+    fn test_layer_activation() {
+        let fixture = Fixture::new("loader-activation");
+        let mangohud = LayerManifest::parse(Path::new("m.json"), LayerKind::Implicit, MANGOHUD)
+            .unwrap()
+            .remove(0);
+        let activation =
+            |vars: &[(&str, &str)]| loader_env(&fixture, vars).layer_activation(&mangohud);
+
+        assert_eq!(activation(&[]), LayerActivation::NotTriggered);
+        assert_eq!(
+            activation(&[("MANGOHUD", "0")]),
+            LayerActivation::NotTriggered
+        );
+        assert_eq!(activation(&[("MANGOHUD", "1")]), LayerActivation::Active);
+        assert_eq!(
+            activation(&[("MANGOHUD", "1"), ("DISABLE_MANGOHUD", "0")]),
+            LayerActivation::DisabledBy("DISABLE_MANGOHUD".to_string())
+        );
+        assert_eq!(
+            activation(&[("MANGOHUD", "1"), (LAYERS_DISABLE_VAR, "~implicit~")]),
+            LayerActivation::DisabledBy(LAYERS_DISABLE_VAR.to_string())
+        );
+        assert_eq!(
+            activation(&[
+                (LAYERS_DISABLE_VAR, "~all~"),
+                (LAYERS_ENABLE_VAR, "*mangohud*")
+            ]),
+            LayerActivation::EnabledBy(LAYERS_ENABLE_VAR.to_string())
+        );
+
+        let mut validation = mangohud.clone();
+        validation.kind = LayerKind::Explicit;
+        validation.name = "VK_LAYER_KHRONOS_validation".to_string();
+        let env = loader_env(
+            &fixture,
+            &[(INSTANCE_LAYERS_VAR, "VK_LAYER_KHRONOS_validation")],
+        );
+        assert_eq!(
+            env.layer_activation(&validation),
+            LayerActivation::EnabledBy(INSTANCE_LAYERS_VAR.to_string())
+        );
+        assert_eq!(
+            loader_env(&fixture, &[]).layer_activation(&validation),
+            LayerActivation::Available
+        );
+    }
+
+    #[test]
+    fn test_match_driver() {
+        let manifest = |path: &str, arch: Option<&str>, found: bool| IcdManifest {
+            path: path.into(),
+            origin: None,
+            file_format_version: None,
+            library_path: "libvulkan_radeon.so".to_string(),
+            library: found.then(|| "/usr/lib/libvulkan_radeon.so".into()),
+            api_version: None,
+            library_arch: arch.map(str::to_string),
+            is_portability_driver: false,
+        };
+        let manifests = [
+            manifest("radeon_icd.i686.json", Some("32"), true),
+            manifest("radeon_icd.x86_64.json", Some("64"), true),
+        ];
+
+        let matched = match_driver(&manifests, vk::DriverId::MESA_RADV).unwrap();
+        #[cfg(target_pointer_width = "64")]
+        assert_eq!(matched.path, Path::new("radeon_icd.x86_64.json"));
+        assert!(match_driver(&manifests, vk::DriverId::NVIDIA_PROPRIETARY).is_none());
+        assert!(match_driver(&manifests, vk::DriverId::QUALCOMM_PROPRIETARY).is_none());
+    }
+}
@@ -1,3 +1,44 @@
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    vkfetch_rs::iterate_devices()
+use std::process::ExitCode;
+use vkfetch_rs::cli::{Options, USAGE};
+use vkfetch_rs::loader::LoaderEnv;
+use vkfetch_rs::profiles::{PROFILE_EXIT_CODE, ProfileCheckFailed};
+use vkfetch_rs::troubleshoot::Failure;
+use vkfetch_rs::validation::{VALIDATION_EXIT_CODE, ValidationFailed};
+
+fn main() -> Result<ExitCode, Box<dyn std::error::Error>> {
+    let options = match Options::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("vkfetch-rs: {error}\n\n{USAGE}");
+            std::process::exit(2);
+        }
+    };
+    if options.help {
+        println!("{USAGE}");
+        return Ok(ExitCode::SUCCESS);
+    }
+    if options.version {
+        println!("vkfetch-rs {}", env!("CARGO_PKG_VERSION"));
+        return Ok(ExitCode::SUCCESS);
+    }
+    if options.no_implicit_layers {
+        // SAFETY: nothing else has started a thread yet.
+        unsafe { LoaderEnv::from_env().disable_implicit_layers() };
+    }
+
+    let error = match vkfetch_rs::iterate_devices(&options) {
+        Ok(()) => return Ok(ExitCode::SUCCESS),
+        Err(error) => error,
+    };
+    let code = if let Some(failure) = error.downcast_ref::<Failure>() {
+        failure.kind.exit_code()
+    } else if error.is::<ValidationFailed>() {
+        VALIDATION_EXIT_CODE
+    } else if error.is::<ProfileCheckFailed>() {
+        PROFILE_EXIT_CODE
+    } else {
+        return Err(error);
+    };
+    eprintln!("{error}");
+    Ok(ExitCode::from(code))
 }
@@ -0,0 +1,140 @@
+use std::{fmt, path::Path};
+
+use crate::sysfs::{read_trimmed, read_u64};
+
+/// A PCIe link's speed and lane count.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinkState {
+    /// Transfer rate per lane in GT/s, e.g. `16.0` for PCIe 4.0.
+    pub speed_gts: Option<f32>,
+    pub width: Option<u32>,
+}
+
+/// The negotiated PCIe link of a device next to what it is capable of.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PcieLink {
+    pub current: LinkState,
+    pub max: LinkState,
+}
+
+impl LinkState {
+    /// Returns the PCIe generation matching the transfer rate.
+    pub fn generation(&self) -> Option<u32> {
+        let speed = self.speed_gts?;
+        [2.5, 5.0, 8.0, 16.0, 32.0, 64.0]
+            .iter()
+            .position(|&gts| (speed - gts).abs() < 0.1)
+            .map(|index| index as u32 + 1)
+    }
+}
+
+impl fmt::Display for LinkState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.generation(), self.speed_gts) {
+            (Some(generation), _) => write!(f, "Gen{generation}")?,
+            (None, Some(speed)) => write!(f, "{speed} GT/s")?,
+            (None, None) => write!(f, "???")?,
+        }
+        match self.width {
+            Some(width) => write!(f, " x{width}"),
+            None => write!(f, " x???"),
+        }
+    }
+}
+
+impl PcieLink {
+    /// Reads the link attributes of the PCI device whose sysfs directory is
+    /// `device_dir`. Returns `None` for devices that are not PCIe.
+    pub fn read(device_dir: &Path) -> Option<Self> {
+        let link = PcieLink {
+            current: LinkState {
+                speed_gts: read_speed(&device_dir.join("current_link_speed")),
+                width: read_u64(device_dir.join("current_link_width")).map(|width| width as u32),
+            },
+            max: LinkState {
+                speed_gts: read_speed(&device_dir.join("max_link_speed")),
+                width: read_u64(device_dir.join("max_link_width")).map(|width| width as u32),
+            },
+        };
+        (link.current.speed_gts.is_some() || link.current.width.is_some()).then_some(link)
+    }
+
+    /// Returns whether the link runs slower or narrower than the device supports.
+    /// GPUs commonly drop to a lower speed while idle to save power.
+    pub fn is_degraded(&self) -> bool {
+        let slower = matches!(
+            (self.current.speed_gts, self.max.speed_gts),
+            (Some(current), Some(max)) if current + 0.1 < max
+        );
+        let narrower = matches!(
+            (self.current.width, self.max.width),
+            (Some(current), Some(max)) if current < max
+        );
+        slower || narrower
+    }
+}
+
+/// Parses `16.0 GT/s PCIe`, `8 GT/s` or `2.5 GT/s`.
+fn read_speed(path: &Path) -> Option<f32> {
+    read_trimmed(path)?.split_whitespace().next()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn link(current: (f32, u32), max: (f32, u32)) -> PcieLink {
+        PcieLink {
+            current: LinkState {
+                speed_gts: Some(current.0),
+                width: Some(current.1),
+            },
+            max: LinkState {
+                speed_gts: Some(max.0),
+                width: Some(max.1),
+            },
+        }
+    }
+
+    #[test]
+    fn test_generation_and_display() {
+        let full = link((16.0, 16), (16.0, 16));
+        assert_eq!(full.current.generation(), Some(4));
+        assert_eq!(full.current.to_string(), "Gen4 x16");
+        assert!(!full.is_degraded());
+        let unknown = LinkState {
+            speed_gts: Some(20.0),
+            width: None,
+        };
+        assert_eq!(unknown.to_string(), "20 GT/s x???");
+    }
+
+    #[test]
+    fn test_degraded_links() {
+        assert!(link((16.0, 4), (16.0, 16)).is_degraded());
+        assert!(link((2.5, 16), (16.0, 16)).is_degraded());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_read_from_sysfs() {
+        use crate::sysfs::fixture::Fixture;
+
+        let fixture = Fixture::new("pcie");
+        let device = "sys/bus/pci/devices/0000:03:00.0";
+        fixture.write(&format!("{device}/current_link_speed"), "8.0 GT/s PCIe\n");
+        fixture.write(&format!("{device}/current_link_width"), "8\n");
+        fixture.write(&format!("{device}/max_link_speed"), "16.0 GT/s PCIe\n");
+        fixture.write(&format!("{device}/max_link_width"), "16\n");
+
+        let read = PcieLink::read(&fixture.path(device)).unwrap();
+        assert_eq!(read, link((8.0, 8), (16.0, 16)));
+        assert!(read.is_degraded());
+
+        fixture.mkdir("sys/devices/platform/gpu");
+        assert_eq!(
+            PcieLink::read(&fixture.path("sys/devices/platform/gpu")),
+            None
+        );
+    }
+}
@@ -0,0 +1,861 @@
+//! Checks devices against [Vulkan Profiles] JSON files, e.g. the Khronos
+//! roadmap profiles or a project's own minimum requirements.
+//!
+//! [Vulkan Profiles]: https://github.com/KhronosGroup/Vulkan-Profiles
+
+use ash::{Instance, vk};
+use serde_json::Value;
+use std::{fmt, fs, path::Path};
+
+use crate::ascii_art::{BRIGHT_GREEN, BRIGHT_RED, BRIGHT_YELLOW};
+use crate::details::{DeviceDetails, PropertyValue, flag_names, is_queried_format};
+use crate::device::cstring_to_string;
+use crate::{ALIGNMENT, BOLD, EMPTY, RESET};
+
+/// Exit code when a device misses a profile; see [`ProfileCheckFailed`].
+pub const PROFILE_EXIT_CODE: u8 = 11;
+
+/// Instance extensions, which profiles list with the device extensions but
+/// which a physical device does not report.
+const INSTANCE_EXTENSIONS: [&str; 9] = [
+    "VK_KHR_get_physical_device_properties2",
+    "VK_KHR_get_surface_capabilities2",
+    "VK_KHR_external_memory_capabilities",
+    "VK_KHR_external_semaphore_capabilities",
+    "VK_KHR_external_fence_capabilities",
+    "VK_KHR_device_group_creation",
+    "VK_KHR_portability_enumeration",
+    "VK_EXT_swapchain_colorspace",
+    "VK_EXT_debug_utils",
+];
+
+/// A single requirement of a profile capability.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Requirement {
+    Extension(String),
+    Feature {
+        structure: String,
+        name: String,
+    },
+    Property {
+        structure: String,
+        name: String,
+        value: Value,
+    },
+    Format {
+        format: String,
+        /// `linearTilingFeatures`, `optimalTilingFeatures` or `bufferFeatures`.
+        usage: String,
+        features: Vec<String>,
+    },
+    /// Parts of the schema vkfetch-rs does not compare, e.g. queue families.
+    Unsupported(String),
+}
+
+/// A named block of requirements from the file's `capabilities`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Capability {
+    pub name: String,
+    pub requirements: Vec<Requirement>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Profile {
+    pub name: String,
+    pub label: Option<String>,
+    pub api_version: Option<u32>,
+    /// Every entry must be met; an entry with several capabilities is met
+    /// when any one of them is.
+    pub capabilities: Vec<Vec<Capability>>,
+}
+
+/// The outcome of checking one device against one profile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileReport {
+    pub profile: String,
+    pub unmet: Vec<String>,
+    /// Requirements that could not be compared, so a pass does not cover them.
+    pub unchecked: Vec<String>,
+}
+
+impl ProfileReport {
+    pub fn passed(&self) -> bool {
+        self.unmet.is_empty()
+    }
+}
+
+impl Profile {
+    /// Parses every profile of a profiles file.
+    pub fn parse_file(contents: &str) -> Result<Vec<Self>, String> {
+        let json: Value = serde_json::from_str(contents).map_err(|error| error.to_string())?;
+        let capabilities = json
+            .get("capabilities")
+            .and_then(Value::as_object)
+            .ok_or("missing \"capabilities\" object")?;
+        let profiles = json
+            .get("profiles")
+            .and_then(Value::as_object)
+            .ok_or("missing \"profiles\" object")?;
+
+        let capability = |name: &Value| {
+            let name = name.as_str().ok_or("capability names must be strings")?;
+            let block = capabilities
+                .get(name)
+                .ok_or_else(|| format!("unknown capability \"{name}\""))?;
+            Ok::<_, String>(Capability {
+                name: name.to_string(),
+                requirements: requirements(block),
+            })
+        };
+
+        profiles
+            .iter()
+            .map(|(name, profile)| {
+                let capabilities = profile
+                    .get("capabilities")
+                    .and_then(Value::as_array)
+                    .ok_or_else(|| format!("profile {name} has no \"capabilities\""))?
+                    .iter()
+                    .map(|entry| match entry.as_array() {
+                        Some(alternatives) => alternatives.iter().map(capability).collect(),
+                        None => capability(entry).map(|capability| vec![capability]),
+                    })
+                    .collect::<Result<_, _>>()?;
+                Ok(Self {
+                    name: name.clone(),
+                    label: profile
+                        .get("label")
+                        .and_then(Value::as_str)
+                        .map(str::to_string),
+                    api_version: profile
+                        .get("api-version")
+                        .and_then(Value::as_str)
+                        .and_then(parse_api_version),
+                    capabilities,
+                })
+            })
+            .collect()
+    }
+
+    /// Reads every profile of the profiles file at `path`.
+    pub fn load(path: &Path) -> Result<Vec<Self>, String> {
+        let contents =
+            fs::read_to_string(path).map_err(|error| format!("{}: {}", path.display(), error))?;
+        Self::parse_file(&contents).map_err(|error| format!("{}: {}", path.display(), error))
+    }
+
+    /// Compares a device against this profile.
+    pub fn check(&self, api_version: u32, details: &DeviceDetails) -> ProfileReport {
+        let mut report = ProfileReport {
+            profile: self.name.clone(),
+            unmet: Vec::new(),
+            unchecked: Vec::new(),
+        };
+        if let Some(required) = self.api_version
+            && !version_at_least(api_version, required)
+        {
+            report.unmet.push(format!(
+                "Vulkan {} is required, the device supports {}",
+                format_version(required),
+                format_version(api_version)
+            ));
+        }
+
+        for alternatives in &self.capabilities {
+            let outcomes = alternatives
+                .iter()
+                .map(|capability| check_capability(capability, details))
+                .collect::<Vec<_>>();
+            match outcomes.iter().position(|(unmet, _)| unmet.is_empty()) {
+                Some(index) => report.unchecked.extend(outcomes[index].1.clone()),
+                None => {
+                    for (capability, (unmet, unchecked)) in alternatives.iter().zip(outcomes) {
+                        let prefix = if alternatives.len() > 1 {
+                            format!("{}: ", capability.name)
+                        } else {
+                            String::new()
+                        };
+                        report
+                            .unmet
+                            .extend(unmet.into_iter().map(|line| format!("{prefix}{line}")));
+                        report.unchecked.extend(unchecked);
+                    }
+                }
+            }
+        }
+        report.unchecked.sort();
+        report.unchecked.dedup();
+        report
+    }
+}
+
+/// Returned when a device did not meet a profile, so the run fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProfileCheckFailed {
+    pub failed: usize,
+}
+
+impl fmt::Display for ProfileCheckFailed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "vkfetch-rs: {} profile checks did not pass", self.failed)
+    }
+}
+
+impl std::error::Error for ProfileCheckFailed {}
+
+/// Prints a pass or fail line per device and profile, listing every unmet
+/// requirement.
+pub fn check_devices(
+    instance: &Instance,
+    instance_version: u32,
+    devices: &[vk::PhysicalDevice],
+    profiles: &[Profile],
+    use_ansi: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut failed = 0;
+    for &device in devices {
+        let properties = unsafe { instance.get_physical_device_properties(device) };
+        let details = DeviceDetails::query(instance, instance_version, device);
+        let name = cstring_to_string(properties.device_name_as_c_str().unwrap_or(c"Unknown"));
+        let reports = profiles
+            .iter()
+            .map(|profile| profile.check(properties.api_version, &details))
+            .collect::<Vec<_>>();
+        failed += reports.iter().filter(|report| !report.passed()).count();
+        for line in report(&name, &reports, use_ansi) {
+            println!("{}", line);
+        }
+        println!();
+    }
+
+    if failed > 0 {
+        Err(Box::new(ProfileCheckFailed { failed }))
+    } else {
+        Ok(())
+    }
+}
+
+pub(crate) fn report(device: &str, reports: &[ProfileReport], use_ansi: bool) -> Vec<String> {
+    let style = |style: &'static str| if use_ansi { style } else { EMPTY };
+    let (bold, reset) = (style(BOLD), style(RESET));
+
+    let mut lines = vec![format!("{}{}{}", bold, device, reset)];
+    for report in reports {
+        let status = if report.passed() {
+            format!("{}pass{}", style(BRIGHT_GREEN), reset)
+        } else {
+            format!(
+                "{}fail{} ({} unmet)",
+                style(BRIGHT_RED),
+                reset,
+                report.unmet.len()
+            )
+        };
+        lines.push(format!("{}{}: {}", ALIGNMENT, report.profile, status));
+        for unmet in &report.unmet {
+            lines.push(format!("{0}{0}{1}", ALIGNMENT, unmet));
+        }
+        for unchecked in &report.unchecked {
+            lines.push(format!(
+                "{0}{0}{1}not checked{2}: {3}",
+                ALIGNMENT,
+                style(BRIGHT_YELLOW),
+                reset,
+                unchecked
+            ));
+        }
+    }
+    lines
+}
+
+fn requirements(block: &Value) -> Vec<Requirement> {
+    let object = |key| {
+        block
+            .get(key)
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+    };
+    let mut requirements = Vec::new();
+
+    for (name, _) in object("extensions") {
+        requirements.push(Requirement::Extension(name.clone()));
+    }
+    for (structure, members) in object("features") {
+        for (name, value) in members.as_object().into_iter().flatten() {
+            // Features set to false are not required.
+            if value.as_bool() == Some(true) {
+                requirements.push(Requirement::Feature {
+                    structure: structure.clone(),
+                    name: name.clone(),
+                });
+            }
+        }
+    }
+    for (structure, members) in object("properties") {
+        flatten_properties(structure, members, &mut requirements);
+    }
+    for (format, structures) in object("formats") {
+        for (structure, usages) in structures.as_object().into_iter().flatten() {
+            for (usage, features) in usages.as_object().into_iter().flatten() {
+                let features = features
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(Value::as_str)
+                    .map(str::to_string)
+                    .collect::<Vec<_>>();
+                if !features.is_empty() {
+                    requirements.push(Requirement::Format {
+                        format: format.clone(),
+                        usage: format!("{structure}.{usage}"),
+                        features,
+                    });
+                }
+            }
+        }
+    }
+    for key in ["queueFamiliesProperties", "videoProfiles"] {
+        if block.get(key).is_some() {
+            requirements.push(Requirement::Unsupported(key.to_string()));
+        }
+    }
+    requirements
+}
+
+/// Nested members such as `VkPhysicalDeviceProperties.limits` are looked up
+/// by their own name, like [`DeviceDetails::limit`] does.
+fn flatten_properties(structure: &str, members: &Value, requirements: &mut Vec<Requirement>) {
+    for (name, value) in members.as_object().into_iter().flatten() {
+        if value.is_object() {
+            flatten_properties(structure, value, requirements);
+        } else {
+            requirements.push(Requirement::Property {
+                structure: structure.to_string(),
+                name: name.clone(),
+                value: value.clone(),
+            });
+        }
+    }
+}
+
+/// Returns the unmet and unchecked requirements of a capability.
+fn check_capability(
+    capability: &Capability,
+    details: &DeviceDetails,
+) -> (Vec<String>, Vec<String>) {
+    let mut unmet = Vec::new();
+    let mut unchecked = Vec::new();
+    for requirement in &capability.requirements {
+        match requirement {
+            Requirement::Extension(name) => {
+                if is_instance_extension(name) {
+                    unchecked.push(format!("instance extension {name}"));
+                } else if !details.has_extension(name) {
+                    unmet.push(format!("extension {name} is not supported"));
+                }
+            }
+            // Structures vkfetch-rs does not query, including the ones the
+            // device predates, may still expose the member through an
+            // extension structure, so they are not checked.
+            Requirement::Feature { structure, name } => match details.feature_in(structure, name) {
+                Some(true) => {}
+                Some(false) => unmet.push(format!("feature {structure}.{name} is not supported")),
+                None => unchecked.push(format!("feature {structure}.{name}")),
+            },
+            Requirement::Property {
+                structure,
+                name,
+                value,
+            } => match details.property_in(structure, name) {
+                Some(actual) => {
+                    if let Err(problem) = compare_property(name, value, actual) {
+                        unmet.push(format!("{structure}.{name} {problem}"));
+                    }
+                }
+                None => unchecked.push(format!("property {structure}.{name}")),
+            },
+            Requirement::Format {
+                format,
+                usage,
+                features,
+            } => {
+                if !is_queried_format(format) {
+                    unchecked.push(format!("format {format}"));
+                    continue;
+                }
+                let (missing, extended) = missing_format_features(details, format, usage, features);
+                if !missing.is_empty() {
+                    unmet.push(format!("{format} {usage} lacks {}", missing.join(", ")));
+                }
+                for feature in extended {
+                    unchecked.push(format!("{format} {usage} {feature}"));
+                }
+            }
+            Requirement::Unsupported(key) => {
+                unchecked.push(format!("{key} of {}", capability.name));
+            }
+        }
+    }
+    (unmet, unchecked)
+}
+
+fn is_instance_extension(name: &str) -> bool {
+    name.ends_with("_surface") || INSTANCE_EXTENSIONS.contains(&name)
+}
+
+/// Compares a property against a profile value. Limits named `min*`,
+/// alignments and granularities must be at most the profile value, ranges
+/// must contain it, and other numbers must be at least the profile value.
+fn compare_property(name: &str, required: &Value, actual: &PropertyValue) -> Result<(), String> {
+    let lower_is_better = name.starts_with("min")
+        || name.contains("Alignment")
+        || name.ends_with("Granularity")
+        || name == "nonCoherentAtomSize";
+    let compare = |required: f64, actual: f64, at_most: bool| {
+        let met = if at_most {
+            actual <= required
+        } else {
+            actual >= required
+        };
+        let bound = if at_most { "at most" } else { "at least" };
+        met.then_some(())
+            .ok_or_else(|| format!("is {actual}, the profile requires {bound} {required}"))
+    };
+
+    match (required, actual) {
+        (Value::Bool(required), PropertyValue::Bool(actual)) => {
+            if *required && !actual {
+                Err("is not supported".to_string())
+            } else {
+                Ok(())
+            }
+        }
+        (Value::Number(required), _) => {
+            let required = required.as_f64().unwrap_or_default();
+            let actual = match actual {
+                PropertyValue::Unsigned(value) => *value as f64,
+                PropertyValue::Signed(value) => *value as f64,
+                PropertyValue::Float(value) => f64::from(*value),
+                PropertyValue::Version(version) => f64::from(*version),
+                _ => return Err(format!("is {actual}, the profile requires {required}")),
+            };
+            compare(required, actual, lower_is_better)
+        }
+        (
+            Value::Array(required),
+            PropertyValue::UnsignedArray(_) | PropertyValue::FloatArray(_),
+        ) => {
+            let actual = match actual {
+                PropertyValue::UnsignedArray(values) => {
+                    values.iter().map(|&value| value as f64).collect::<Vec<_>>()
+                }
+                PropertyValue::FloatArray(values) => {
+                    values.iter().map(|&value| f64::from(value)).collect()
+                }
+                _ => unreachable!(),
+            };
+            let is_range = name.ends_with("Range") && required.len() == 2;
+            required
+                .iter()
+                .zip(&actual)
+                .enumerate()
+                .try_for_each(|(index, (required, &actual))| {
+                    let at_most = if is_range {
+                        index == 0
+                    } else {
+                        lower_is_better
+                    };
+                    compare(required.as_f64().unwrap_or_default(), actual, at_most)
+                        .map_err(|problem| format!("[{index}] {problem}"))
+                })
+        }
+        (Value::Array(required), PropertyValue::SampleCounts(_) | PropertyValue::Flags(_)) => {
+            let actual = match actual {
+                PropertyValue::SampleCounts(counts) => (0..7)
+                    .map(|bit| 1u32 << bit)
+                    .filter(|&count| counts.as_raw() & count != 0)
+                    .map(|count| format!("VK_SAMPLE_COUNT_{count}_BIT"))
+                    .collect(),
+                PropertyValue::Flags(names) => names.clone(),
+                _ => unreachable!(),
+            };
+            let missing = required
+                .iter()
+                .filter_map(Value::as_str)
+                .filter(|name| !actual.iter().any(|actual| actual == name))
+                .collect::<Vec<_>>();
+            if missing.is_empty() {
+                Ok(())
+            } else {
+                Err(format!("lacks {}", missing.join(", ")))
+            }
+        }
+        (Value::String(required), PropertyValue::Text(actual)) => {
+            if required == actual || required.ends_with(&format!("_{actual}")) {
+                Ok(())
+            } else {
+                Err(format!("is {actual}, the profile requires {required}"))
+            }
+        }
+        _ => Err(format!("is {actual}, the profile requires {required}")),
+    }
+}
+
+/// Format features the device lacks, by Vulkan name, followed by the
+/// `VkFormatFeatureFlags2` features that cannot be compared.
+fn missing_format_features(
+    details: &DeviceDetails,
+    format: &str,
+    usage: &str,
+    features: &[String],
+) -> (Vec<String>, Vec<String>) {
+    let name = format.strip_prefix("VK_FORMAT_").unwrap_or(format);
+    let supported = details
+        .formats
+        .iter()
+        .find(|support| format!("{:?}", support.format) == name)
+        .map(|support| {
+            if usage.ends_with("linearTilingFeatures") {
+                support.linear_tiling
+            } else if usage.ends_with("optimalTilingFeatures") {
+                support.optimal_tiling
+            } else {
+                support.buffer
+            }
+        })
+        .unwrap_or_default();
+    let supported = flag_names(
+        "VK_FORMAT_FEATURE_",
+        supported.as_raw(),
+        vk::FormatFeatureFlags::from_raw,
+    );
+    // The first 32 bits of `VkFormatFeatureFlags2` match
+    // `VkFormatFeatureFlags`; the others, such as
+    // `STORAGE_READ_WITHOUT_FORMAT`, need `VkFormatProperties3`.
+    let known = flag_names(
+        "VK_FORMAT_FEATURE_",
+        u32::MAX,
+        vk::FormatFeatureFlags::from_raw,
+    );
+    let mut missing = Vec::new();
+    let mut extended = Vec::new();
+    for feature in features {
+        let name = feature.replacen("VK_FORMAT_FEATURE_2_", "VK_FORMAT_FEATURE_", 1);
+        if !known.contains(&name) {
+            extended.push(feature.clone());
+        } else if !supported.contains(&name) {
+            missing.push(feature.clone());
+        }
+    }
+    (missing, extended)
+}
+
+/// Parses `1.3.204` into a packed Vulkan version.
+fn parse_api_version(version: &str) -> Option<u32> {
+    let mut parts = version.split('.').map(|part| part.parse::<u32>().ok());
+    let major = parts.next()??;
+    let minor = parts.next()??;
+    let patch = parts.next().flatten().unwrap_or(0);
+    Some(vk::make_api_version(0, major, minor, patch))
+}
+
+fn version_at_least(version: u32, required: u32) -> bool {
+    let key = |version| {
+        (
+            vk::api_version_major(version),
+            vk::api_version_minor(version),
+            vk::api_version_patch(version),
+        )
+    };
+    key(version) >= key(required)
+}
+
+fn format_version(version: u32) -> String {
+    format!(
+        "{}.{}.{}",
+        vk::api_version_major(version),
+        vk::api_version_minor(version),
+        vk::api_version_patch(version)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::details::{Extension, FeatureGroup, FormatSupport, PropertyGroup};
+    use crate::sysfs::fixture::Fixture;
+
+    const ENGINE: &str = r#"{
+        "capabilities": {
+            "base": {
+                "extensions": { "VK_KHR_swapchain": 1, "VK_KHR_surface": 1 },
+                "features": {
+                    "VkPhysicalDeviceFeatures": { "samplerAnisotropy": true, "geometryShader": false }
+                },
+                "properties": {
+                    "VkPhysicalDeviceProperties": {
+                        "limits": {
+                            "maxImageDimension2D": 16384,
+                            "minUniformBufferOffsetAlignment": 256,
+                            "pointSizeRange": [1.0, 64.0],
+                            "framebufferColorSampleCounts": ["VK_SAMPLE_COUNT_1_BIT", "VK_SAMPLE_COUNT_8_BIT"]
+                        }
+                    }
+                },
+                "formats": {
+                    "VK_FORMAT_R8G8B8A8_UNORM": {
+                        "VkFormatProperties": {
+                            "optimalTilingFeatures": ["VK_FORMAT_FEATURE_SAMPLED_IMAGE_BIT", "VK_FORMAT_FEATURE_STORAGE_IMAGE_BIT"]
+                        }
+                    }
+                },
+                "queueFamiliesProperties": []
+            },
+            "mesh_nv": { "extensions": { "VK_NV_mesh_shader": 1 } },
+            "mesh_ext": { "extensions": { "VK_EXT_mesh_shader": 1 } },
+            "modern": {
+                "features": { "VkPhysicalDeviceVulkan13Features": { "dynamicRendering": true } }
+            }
+        },
+        "profiles": {
+            "VP_ENGINE_minimum": {
+                "api-version": "1.2.0",
+                "capabilities": ["base", ["mesh_nv", "mesh_ext"]]
+            },
+            "VP_ENGINE_modern": { "api-version": "1.3.0", "capabilities": ["modern"] }
+        }
+    }"#;
+
+    fn details() -> DeviceDetails {
+        DeviceDetails {
+            extensions: ["VK_KHR_swapchain", "VK_EXT_mesh_shader"]
+                .into_iter()
+                .map(|name| Extension {
+                    name: name.to_string(),
+                    spec_version: 1,
+                })
+                .collect(),
+            features: vec![FeatureGroup {
+                name: "VkPhysicalDeviceFeatures".to_string(),
+                features: vec![
+                    ("samplerAnisotropy".to_string(), true),
+                    ("geometryShader".to_string(), false),
+                ],
+            }],
+            properties: vec![PropertyGroup {
+                name: "VkPhysicalDeviceLimits".to_string(),
+                properties: vec![
+                    (
+                        "maxImageDimension2D".to_string(),
+                        PropertyValue::Unsigned(16384),
+                    ),
+                    (
+                        "minUniformBufferOffsetAlignment".to_string(),
+                        PropertyValue::Unsigned(64),
+                    ),
+                    (
+                        "pointSizeRange".to_string(),
+                        PropertyValue::FloatArray(vec![1.0, 2047.0]),
+                    ),
+                    (
+                        "framebufferColorSampleCounts".to_string(),
+                        PropertyValue::SampleCounts(
+                            vk::SampleCountFlags::TYPE_1 | vk::SampleCountFlags::TYPE_4,
+                        ),
+                    ),
+                ],
+            }],
+            formats: vec![FormatSupport {
+                format: vk::Format::R8G8B8A8_UNORM,
+                linear_tiling: vk::FormatFeatureFlags::empty(),
+                optimal_tiling: vk::FormatFeatureFlags::SAMPLED_IMAGE,
+                buffer: vk::FormatFeatureFlags::empty(),
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_check() {
+        let profiles = Profile::parse_file(ENGINE).unwrap();
+        let report = profiles[0].check(vk::make_api_version(0, 1, 2, 170), &details());
+        assert_eq!(report.profile, "VP_ENGINE_minimum");
+        assert_eq!(
+            report.unmet,
+            [
+                "VkPhysicalDeviceProperties.framebufferColorSampleCounts lacks VK_SAMPLE_COUNT_8_BIT",
+                "VK_FORMAT_R8G8B8A8_UNORM VkFormatProperties.optimalTilingFeatures lacks VK_FORMAT_FEATURE_STORAGE_IMAGE_BIT",
+            ]
+        );
+        assert_eq!(
+            report.unchecked,
+            [
+                "instance extension VK_KHR_surface",
+                "queueFamiliesProperties of base"
+            ]
+        );
+
+        let report = profiles[1].check(vk::make_api_version(0, 1, 2, 170), &details());
+        assert_eq!(
+            report.unmet,
+            ["Vulkan 1.3.0 is required, the device supports 1.2.170"]
+        );
+        assert_eq!(
+            report.unchecked,
+            ["feature VkPhysicalDeviceVulkan13Features.dynamicRendering"]
+        );
+    }
+
+    #[test]
+    fn test_structure_names() {
+        let profiles = Profile::parse_file(
+            r#"{
+                "capabilities": {
+                    "promoted": {
+                        "features": {
+                            "VkPhysicalDeviceVulkan11Features": { "multiview": true },
+                            "VkPhysicalDeviceShaderFloat16Int8Features": { "shaderInt8": true },
+                            "VkPhysicalDeviceFeatures": { "samplerAnisotropy": true }
+                        },
+                        "properties": {
+                            "VkPhysicalDeviceMaintenance3Properties": { "maxPerSetDescriptors": 1024 }
+                        }
+                    },
+                    "again": {
+                        "features": { "VkPhysicalDeviceVulkan11Features": { "multiview": true } }
+                    }
+                },
+                "profiles": { "VP_PROMOTED": { "capabilities": ["promoted", "again"] } }
+            }"#,
+        )
+        .unwrap();
+        let mut details = details();
+        details.features.push(FeatureGroup {
+            name: "VkPhysicalDeviceVulkan12Features".to_string(),
+            features: vec![("shaderInt8".to_string(), false)],
+        });
+        details.properties.push(PropertyGroup {
+            name: "VkPhysicalDeviceVulkan11Properties".to_string(),
+            properties: vec![(
+                "maxPerSetDescriptors".to_string(),
+                PropertyValue::Unsigned(256),
+            )],
+        });
+
+        // A Vulkan 1.1 device may expose these through structures
+        // vkfetch-rs does not query, so they are not checked rather than
+        // unmet, and members of other structures are not matched by name.
+        let report = profiles[0].check(vk::make_api_version(0, 1, 1, 0), &details);
+        assert!(report.passed());
+        assert_eq!(
+            report.unchecked,
+            [
+                "feature VkPhysicalDeviceShaderFloat16Int8Features.shaderInt8",
+                "feature VkPhysicalDeviceVulkan11Features.multiview",
+                "property VkPhysicalDeviceMaintenance3Properties.maxPerSetDescriptors",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_features_2() {
+        let profiles = Profile::parse_file(
+            r#"{
+                "capabilities": {
+                    "formats": {
+                        "formats": {
+                            "VK_FORMAT_R8G8B8A8_UNORM": {
+                                "VkFormatProperties3": {
+                                    "optimalTilingFeatures": [
+                                        "VK_FORMAT_FEATURE_2_SAMPLED_IMAGE_BIT",
+                                        "VK_FORMAT_FEATURE_2_STORAGE_IMAGE_BIT",
+                                        "VK_FORMAT_FEATURE_2_STORAGE_READ_WITHOUT_FORMAT_BIT"
+                                    ]
+                                }
+                            },
+                            "VK_FORMAT_A4R4G4B4_UNORM_PACK16": {
+                                "VkFormatProperties": {
+                                    "optimalTilingFeatures": ["VK_FORMAT_FEATURE_SAMPLED_IMAGE_BIT"]
+                                }
+                            }
+                        }
+                    }
+                },
+                "profiles": { "VP_FORMATS": { "capabilities": ["formats"] } }
+            }"#,
+        )
+        .unwrap();
+        let report = profiles[0].check(vk::make_api_version(0, 1, 3, 0), &details());
+        assert_eq!(
+            report.unmet,
+            [
+                "VK_FORMAT_R8G8B8A8_UNORM VkFormatProperties3.optimalTilingFeatures lacks VK_FORMAT_FEATURE_2_STORAGE_IMAGE_BIT"
+            ]
+        );
+        assert_eq!(
+            report.unchecked,
+            [
+                "VK_FORMAT_R8G8B8A8_UNORM VkFormatProperties3.optimalTilingFeatures \
+                 VK_FORMAT_FEATURE_2_STORAGE_READ_WITHOUT_FORMAT_BIT",
+                "format VK_FORMAT_A4R4G4B4_UNORM_PACK16",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compare_property() {
+        let unsigned = PropertyValue::Unsigned;
+        assert!(compare_property("maxImageDimension2D", &16384.into(), &unsigned(8192)).is_err());
+        assert!(compare_property("bufferImageGranularity", &4096.into(), &unsigned(1024)).is_ok());
+        assert!(
+            compare_property("minTexelOffset", &(-8).into(), &PropertyValue::Signed(-8)).is_ok()
+        );
+        assert!(
+            compare_property(
+                "pointClippingBehavior",
+                &"VK_POINT_CLIPPING_BEHAVIOR_ALL_CLIP_PLANES".into(),
+                &PropertyValue::Text("ALL_CLIP_PLANES".to_string())
+            )
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_load() {
+        let fixture = Fixture::new("profiles");
+        fixture.write("engine.json", ENGINE);
+        let profiles = Profile::load(&fixture.path("engine.json")).unwrap();
+        assert_eq!(profiles, Profile::parse_file(ENGINE).unwrap());
+        assert!(Profile::load(&fixture.path("VP_KHR_roadmap_2022.json")).is_err());
+    }
+
+    #[test]
+    fn test_report() {
+        let reports = [
+            ProfileReport {
+                profile: "VP_A".to_string(),
+                unmet: Vec::new(),
+                unchecked: vec![
+                    "property VkPhysicalDeviceMeshShaderPropertiesEXT.maxMeshOutputVertices"
+                        .to_string(),
+                ],
+            },
+            ProfileReport {
+                profile: "VP_B".to_string(),
+                unmet: vec!["extension VK_KHR_maintenance5 is not supported".to_string()],
+                unchecked: Vec::new(),
+            },
+        ];
+        assert_eq!(
+            report("GPU", &reports, false),
+            [
+                "GPU",
+                "    VP_A: pass",
+                "        not checked: property VkPhysicalDeviceMeshShaderPropertiesEXT.maxMeshOutputVertices",
+                "    VP_B: fail (1 unmet)",
+                "        extension VK_KHR_maintenance5 is not supported",
+            ]
+        );
+    }
+}
@@ -0,0 +1,303 @@
+use ash::{Instance, vk};
+use serde_json::json;
+use std::{
+    error::Error,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+    sync::atomic::Ordering,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use crate::device::Device;
+use crate::watch::{running_until_ctrl_c, sleep_while_running};
+
+/// Output format of `--record`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordFormat {
+    Csv,
+    Ndjson,
+}
+
+impl RecordFormat {
+    /// Picks NDJSON for `.json`, `.jsonl` and `.ndjson` files, CSV otherwise.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json" | "jsonl" | "ndjson") => RecordFormat::Ndjson,
+            _ => RecordFormat::Csv,
+        }
+    }
+}
+
+/// When a recording stops, besides Ctrl-C.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RecordLimits {
+    pub interval: Duration,
+    pub duration: Option<Duration>,
+    pub samples: Option<u64>,
+}
+
+/// One row of a recording.
+#[derive(Debug, Clone, PartialEq)]
+struct Sample {
+    /// Seconds since the Unix epoch.
+    timestamp: f64,
+    device: usize,
+    name: String,
+    heap_size: u64,
+    budget: Option<u64>,
+    used: Option<u64>,
+    memory_pressure: Option<f32>,
+}
+
+impl Sample {
+    fn new(timestamp: f64, index: usize, device: &Device) -> Self {
+        Sample {
+            timestamp,
+            device: index,
+            name: device.device_name.clone(),
+            heap_size: device.heapsize,
+            budget: device.heapbudget,
+            used: device.kernel_stats.and_then(|stats| stats.vram_used),
+            memory_pressure: device.characteristics.memory_pressure,
+        }
+    }
+
+    /// The values of [`METRICS`].
+    fn metrics(&self) -> [Option<f64>; 3] {
+        [
+            self.budget.map(|budget| budget as f64),
+            self.used.map(|used| used as f64),
+            self.memory_pressure.map(|pressure| pressure as f64),
+        ]
+    }
+}
+
+/// The metrics summarized when a recording stops.
+const METRICS: [&str; 3] = ["budget_bytes", "used_bytes", "memory_pressure"];
+
+const CSV_HEADER: &str =
+    "timestamp,device,name,heap_size_bytes,budget_bytes,used_bytes,memory_pressure";
+
+fn csv_row(sample: &Sample) -> String {
+    fn optional<T: ToString>(value: Option<T>) -> String {
+        value.map(|value| value.to_string()).unwrap_or_default()
+    }
+
+    format!(
+        "{:.3},{},\"{}\",{},{},{},{}",
+        sample.timestamp,
+        sample.device,
+        sample.name.replace('"', "\"\""),
+        sample.heap_size,
+        optional(sample.budget),
+        optional(sample.used),
+        optional(sample.memory_pressure)
+    )
+}
+
+fn ndjson_row(sample: &Sample) -> String {
+    json!({
+        "timestamp": sample.timestamp,
+        "device": sample.device,
+        "name": sample.name,
+        "heap_size_bytes": sample.heap_size,
+        "budget_bytes": sample.budget,
+        "used_bytes": sample.used,
+        "memory_pressure": sample.memory_pressure,
+    })
+    .to_string()
+}
+
+/// Running minimum, maximum and mean of one metric.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Summary {
+    min: f64,
+    max: f64,
+    sum: f64,
+    count: u64,
+}
+
+impl Summary {
+    fn add(summary: &mut Option<Summary>, value: f64) {
+        let summary = summary.get_or_insert(Summary {
+            min: value,
+            max: value,
+            sum: 0.0,
+            count: 0,
+        });
+        summary.min = summary.min.min(value);
+        summary.max = summary.max.max(value);
+        summary.sum += value;
+        summary.count += 1;
+    }
+
+    fn average(&self) -> f64 {
+        self.sum / self.count as f64
+    }
+}
+
+/// Collects the samples of a recording into per-device, per-metric summaries.
+#[derive(Debug, Default)]
+struct Summaries {
+    /// Device name and the summary of each metric in [`METRICS`] order.
+    devices: Vec<(String, [Option<Summary>; 3])>,
+}
+
+impl Summaries {
+    fn add(&mut self, sample: &Sample) {
+        if self.devices.len() <= sample.device {
+            self.devices
+                .resize_with(sample.device + 1, || (String::new(), [None; 3]));
+        }
+        let (name, summaries) = &mut self.devices[sample.device];
+        name.clone_from(&sample.name);
+        for (summary, value) in summaries.iter_mut().zip(sample.metrics()) {
+            if let Some(value) = value {
+                Summary::add(summary, value);
+            }
+        }
+    }
+
+    fn report(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        for (name, summaries) in &self.devices {
+            lines.push(format!("{}:", name));
+            for (metric, summary) in METRICS.iter().zip(summaries) {
+                if let Some(summary) = summary {
+                    lines.push(format!(
+                        "  {:<16} min {:<14} avg {:<14.3} max {}",
+                        metric,
+                        summary.min,
+                        summary.average(),
+                        summary.max
+                    ));
+                }
+            }
+        }
+        lines
+    }
+}
+
+/// Samples every device each interval until a limit is reached or Ctrl-C is
+/// pressed, then prints min/avg/max of each metric to stderr.
+pub(crate) fn record_devices(
+    instance: &Instance,
+    instance_version: u32,
+    device_handles: &[vk::PhysicalDevice],
+    path: &Path,
+    format: RecordFormat,
+    limits: RecordLimits,
+) -> Result<(), Box<dyn Error>> {
+    let running = running_until_ctrl_c()?;
+    let mut output: Box<dyn Write> = if path == Path::new("-") {
+        Box::new(io::stdout())
+    } else {
+        Box::new(BufWriter::new(File::create(path)?))
+    };
+    if format == RecordFormat::Csv {
+        writeln!(output, "{}", CSV_HEADER)?;
+    }
+
+    let mut devices = device_handles
+        .iter()
+        .map(|&handle| Device::new(instance, instance_version, handle))
+        .collect::<Vec<_>>();
+    let mut summaries = Summaries::default();
+    let start = Instant::now();
+    let mut taken = 0;
+    while running.load(Ordering::SeqCst) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs_f64();
+        for (index, device) in devices.iter().enumerate() {
+            let sample = Sample::new(timestamp, index, device);
+            let row = match format {
+                RecordFormat::Csv => csv_row(&sample),
+                RecordFormat::Ndjson => ndjson_row(&sample),
+            };
+            writeln!(output, "{}", row)?;
+            summaries.add(&sample);
+        }
+        output.flush()?;
+        taken += 1;
+
+        if limits.samples.is_some_and(|samples| taken >= samples)
+            || limits
+                .duration
+                .is_some_and(|duration| start.elapsed() + limits.interval > duration)
+        {
+            break;
+        }
+        sleep_while_running(&running, limits.interval);
+        for (device, &handle) in devices.iter_mut().zip(device_handles) {
+            device.refresh(instance, handle);
+        }
+    }
+
+    eprintln!("Recorded {} samples per device", taken);
+    for line in summaries.report() {
+        eprintln!("{}", line);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(timestamp: f64, budget: u64, pressure: f32) -> Sample {
+        Sample {
+            timestamp,
+            device: 0,
+            name: "Test \"GPU\"".to_string(),
+            heap_size: 1000,
+            budget: Some(budget),
+            used: None,
+            memory_pressure: Some(pressure),
+        }
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(
+            RecordFormat::from_path(Path::new("soak.ndjson")),
+            RecordFormat::Ndjson
+        );
+        assert_eq!(
+            RecordFormat::from_path(Path::new("soak.csv")),
+            RecordFormat::Csv
+        );
+        assert_eq!(RecordFormat::from_path(Path::new("-")), RecordFormat::Csv);
+    }
+
+    #[test]
+    fn test_rows() {
+        let sample = sample(1700000000.5, 800, 0.25);
+        assert_eq!(
+            csv_row(&sample),
+            "1700000000.500,0,\"Test \"\"GPU\"\"\",1000,800,,0.25"
+        );
+
+        let row: serde_json::Value = serde_json::from_str(&ndjson_row(&sample)).unwrap();
+        assert_eq!(row["name"], "Test \"GPU\"");
+        assert_eq!(row["budget_bytes"], 800);
+        assert!(row["used_bytes"].is_null());
+    }
+
+    #[test]
+    fn test_summaries() {
+        let mut summaries = Summaries::default();
+        summaries.add(&sample(0.0, 800, 0.25));
+        summaries.add(&sample(1.0, 600, 0.5));
+        summaries.add(&sample(2.0, 700, 0.75));
+
+        let report = summaries.report();
+        assert_eq!(report.len(), 3);
+        assert_eq!(report[0], "Test \"GPU\":");
+        assert!(report[1].contains("budget_bytes") && report[1].contains("min 600"));
+        assert!(report[1].contains("avg 700.000") && report[1].ends_with("max 800"));
+        assert!(report[2].contains("memory_pressure") && report[2].contains("avg 0.500"));
+    }
+}
@@ -0,0 +1,254 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::sysfs::{read_trimmed, read_u64};
+
+/// A clock domain's current frequency and the highest frequency it can reach.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Clock {
+    pub current_mhz: Option<u32>,
+    pub max_mhz: Option<u32>,
+}
+
+/// A labelled temperature reading, e.g. `edge` or `junction`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Temperature {
+    pub label: Option<String>,
+    pub celsius: f32,
+}
+
+/// Live health readings from the kernel's hwmon and DRM clock interfaces.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Sensors {
+    pub temperatures: Vec<Temperature>,
+    pub fan_rpm: Option<u32>,
+    pub power_watts: Option<f32>,
+    pub power_cap_watts: Option<f32>,
+    pub core_clock: Option<Clock>,
+    pub memory_clock: Option<Clock>,
+}
+
+impl Sensors {
+    /// Reads the sensors of the device whose sysfs directory is `device_dir`,
+    /// e.g. `/sys/bus/pci/devices/0000:01:00.0`.
+    /// Returns `None` if the device exposes none of them.
+    pub fn read(device_dir: &Path) -> Option<Self> {
+        let mut sensors = Sensors::default();
+
+        if let Some(hwmon) = first_subdir(&device_dir.join("hwmon")) {
+            sensors.temperatures = read_temperatures(&hwmon);
+            sensors.fan_rpm = read_u64(hwmon.join("fan1_input")).map(|rpm| rpm as u32);
+            sensors.power_watts = read_u64(hwmon.join("power1_average"))
+                .or_else(|| read_u64(hwmon.join("power1_input")))
+                .map(microwatts_to_watts);
+            sensors.power_cap_watts = read_u64(hwmon.join("power1_cap")).map(microwatts_to_watts);
+        }
+
+        // amdgpu exposes its DPM tables, i915 its GT frequencies per card and
+        // platform GPUs such as Mali and Adreno a devfreq device.
+        sensors.core_clock = read_dpm_clock(&device_dir.join("pp_dpm_sclk"))
+            .or_else(|| read_gt_clock(device_dir))
+            .or_else(|| read_devfreq_clock(device_dir));
+        sensors.memory_clock = read_dpm_clock(&device_dir.join("pp_dpm_mclk"));
+
+        (sensors != Sensors::default()).then_some(sensors)
+    }
+}
+
+fn microwatts_to_watts(microwatts: u64) -> f32 {
+    microwatts as f32 / 1_000_000.0
+}
+
+fn first_subdir(dir: &Path) -> Option<PathBuf> {
+    let mut entries = fs::read_dir(dir)
+        .ok()?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect::<Vec<_>>();
+    entries.sort();
+    entries.into_iter().next()
+}
+
+fn read_temperatures(hwmon: &Path) -> Vec<Temperature> {
+    (1..=8)
+        .filter_map(|index| {
+            let millidegrees = read_trimmed(hwmon.join(format!("temp{index}_input")))?
+                .parse::<i64>()
+                .ok()?;
+            Some(Temperature {
+                label: read_trimmed(hwmon.join(format!("temp{index}_label"))),
+                celsius: millidegrees as f32 / 1000.0,
+            })
+        })
+        .collect()
+}
+
+/// Parses an amdgpu `pp_dpm_*` table such as:
+///
+/// ```text
+/// 0: 500Mhz
+/// 1: 1800Mhz *
+/// 2: 2500Mhz
+/// ```
+fn parse_dpm_table(table: &str) -> Option<Clock> {
+    let mut clock = Clock::default();
+    for line in table.lines() {
+        let Some((_, level)) = line.split_once(':') else {
+            continue;
+        };
+        let Some(mhz) = level
+            .trim()
+            .trim_end_matches('*')
+            .trim()
+            .to_ascii_lowercase()
+            .strip_suffix("mhz")
+            .and_then(|mhz| mhz.trim().parse::<u32>().ok())
+        else {
+            continue;
+        };
+        if level.trim_end().ends_with('*') {
+            clock.current_mhz = Some(mhz);
+        }
+        clock.max_mhz = clock.max_mhz.max(Some(mhz));
+    }
+    (clock != Clock::default()).then_some(clock)
+}
+
+fn read_dpm_clock(path: &Path) -> Option<Clock> {
+    parse_dpm_table(&fs::read_to_string(path).ok()?)
+}
+
+fn read_gt_clock(device_dir: &Path) -> Option<Clock> {
+    let card = fs::read_dir(device_dir.join("drm"))
+        .ok()?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .find(|path| path.join("gt_cur_freq_mhz").exists())?;
+
+    let clock = Clock {
+        current_mhz: read_u64(card.join("gt_cur_freq_mhz")).map(|mhz| mhz as u32),
+        max_mhz: read_u64(card.join("gt_RP0_freq_mhz"))
+            .or_else(|| read_u64(card.join("gt_max_freq_mhz")))
+            .map(|mhz| mhz as u32),
+    };
+    (clock != Clock::default()).then_some(clock)
+}
+
+/// Reads `devfreq/*/cur_freq` and `max_freq`, which are in Hz.
+fn read_devfreq_clock(device_dir: &Path) -> Option<Clock> {
+    let devfreq = first_subdir(&device_dir.join("devfreq"))?;
+    let mhz = |name| read_u64(devfreq.join(name)).map(|hz| (hz / 1_000_000) as u32);
+
+    let clock = Clock {
+        current_mhz: mhz("cur_freq"),
+        max_mhz: mhz("max_freq"),
+    };
+    (clock != Clock::default()).then_some(clock)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(unix)]
+    use crate::sysfs::fixture::Fixture;
+
+    #[cfg(unix)]
+    const PCI_DEVICE: &str = "sys/bus/pci/devices/0000:03:00.0";
+
+    #[test]
+    fn test_parse_dpm_table() {
+        let clock = parse_dpm_table("0: 500Mhz\n1: 1800Mhz *\n2: 2500Mhz\n").unwrap();
+        assert_eq!(clock.current_mhz, Some(1800));
+        assert_eq!(clock.max_mhz, Some(2500));
+        assert_eq!(parse_dpm_table("garbage"), None);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_amdgpu_sensors() {
+        let fixture = Fixture::new("sensors-amdgpu");
+        let hwmon = format!("{PCI_DEVICE}/hwmon/hwmon4");
+        fixture.write(&format!("{hwmon}/temp1_input"), "45000\n");
+        fixture.write(&format!("{hwmon}/temp1_label"), "edge\n");
+        fixture.write(&format!("{hwmon}/temp2_input"), "52500\n");
+        fixture.write(&format!("{hwmon}/temp2_label"), "junction\n");
+        fixture.write(&format!("{hwmon}/fan1_input"), "1200\n");
+        fixture.write(&format!("{hwmon}/power1_average"), "35000000\n");
+        fixture.write(&format!("{hwmon}/power1_cap"), "200000000\n");
+        fixture.write(
+            &format!("{PCI_DEVICE}/pp_dpm_sclk"),
+            "0: 500Mhz\n1: 2500Mhz *\n",
+        );
+        fixture.write(
+            &format!("{PCI_DEVICE}/pp_dpm_mclk"),
+            "0: 96Mhz *\n1: 1000Mhz\n",
+        );
+
+        let sensors = Sensors::read(&fixture.path(PCI_DEVICE)).unwrap();
+        assert_eq!(sensors.temperatures.len(), 2);
+        assert_eq!(sensors.temperatures[1].label.as_deref(), Some("junction"));
+        assert_eq!(sensors.temperatures[1].celsius, 52.5);
+        assert_eq!(sensors.fan_rpm, Some(1200));
+        assert_eq!(sensors.power_watts, Some(35.0));
+        assert_eq!(sensors.power_cap_watts, Some(200.0));
+        assert_eq!(
+            sensors.core_clock,
+            Some(Clock {
+                current_mhz: Some(2500),
+                max_mhz: Some(2500)
+            })
+        );
+        assert_eq!(
+            sensors.memory_clock.and_then(|clock| clock.current_mhz),
+            Some(96)
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_i915_clocks() {
+        let fixture = Fixture::new("sensors-i915");
+        fixture.write(&format!("{PCI_DEVICE}/drm/card0/gt_cur_freq_mhz"), "350\n");
+        fixture.write(&format!("{PCI_DEVICE}/drm/card0/gt_RP0_freq_mhz"), "1300\n");
+
+        let sensors = Sensors::read(&fixture.path(PCI_DEVICE)).unwrap();
+        assert!(sensors.temperatures.is_empty());
+        assert_eq!(
+            sensors.core_clock,
+            Some(Clock {
+                current_mhz: Some(350),
+                max_mhz: Some(1300)
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_devfreq_clock() {
+        const PLATFORM_DEVICE: &str = "sys/devices/platform/fb000000.gpu";
+        let fixture = Fixture::new("sensors-devfreq");
+        let devfreq = format!("{PLATFORM_DEVICE}/devfreq/fb000000.gpu");
+        fixture.write(&format!("{devfreq}/cur_freq"), "300000000\n");
+        fixture.write(&format!("{devfreq}/max_freq"), "1000000000\n");
+
+        let sensors = Sensors::read(&fixture.path(PLATFORM_DEVICE)).unwrap();
+        assert_eq!(
+            sensors.core_clock,
+            Some(Clock {
+                current_mhz: Some(300),
+                max_mhz: Some(1000)
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_no_sensors() {
+        let fixture = Fixture::new("sensors-empty");
+        fixture.mkdir(PCI_DEVICE);
+        assert_eq!(Sensors::read(&fixture.path(PCI_DEVICE)), None);
+    }
+}
@@ -0,0 +1,616 @@
+//! Compares devices against a snapshot saved with `--export`, e.g. to see
+//! what a driver upgrade changed.
+//!
+//! Snapshots are `vulkaninfo --json` reports, so one written by vulkaninfo
+//! works as well. Both sides are flattened into the members of their
+//! extensions, features, properties and formats, which are then compared
+//! member by member. vulkaninfo writes many structures vkfetch-rs does not
+//! query, so structures found in only one snapshot are listed as not
+//! compared instead of as added or removed.
+
+use ash::{Instance, vk};
+use serde_json::{Map, Value, json};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    error::Error,
+    fs,
+    path::Path,
+};
+
+use crate::ascii_art::{BRIGHT_GREEN, BRIGHT_RED, BRIGHT_YELLOW};
+use crate::details::{self, DeviceDetails};
+use crate::vulkaninfo;
+use crate::{ALIGNMENT, BOLD, EMPTY, RESET};
+
+/// Sections of a capability block that are compared, with their headings.
+const SECTIONS: [(&str, &str); 4] = [
+    ("extensions", "Extensions"),
+    ("features", "Features"),
+    ("properties", "Properties"),
+    ("formats", "Formats"),
+];
+
+/// The capabilities of one device in a snapshot.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeviceSnapshot {
+    pub name: String,
+    /// Driver name, info string and raw `driverVersion`.
+    pub driver: String,
+    /// `vendorID` and `deviceID`, used to pair devices of two snapshots.
+    id: (Option<u64>, Option<u64>),
+    /// Flattened members per section, keyed like
+    /// `VkPhysicalDeviceProperties.limits.maxImageDimension2D`.
+    sections: [BTreeMap<String, Value>; 4],
+}
+
+/// The members of one section that differ between two snapshots.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SectionDiff {
+    pub added: Vec<(String, Value)>,
+    pub removed: Vec<(String, Value)>,
+    pub changed: Vec<(String, Value, Value)>,
+    /// Structures present in only one of the snapshots.
+    pub not_compared: Vec<String>,
+}
+
+/// The differences of one device present in both snapshots.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeviceDiff {
+    pub name: String,
+    pub old_driver: String,
+    pub new_driver: String,
+    /// One entry per [`SECTIONS`] entry.
+    pub sections: Vec<SectionDiff>,
+}
+
+/// The result of comparing two snapshots.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SnapshotDiff {
+    pub devices: Vec<DeviceDiff>,
+    /// Names of devices only in the new snapshot.
+    pub added_devices: Vec<String>,
+    /// Names of devices only in the old snapshot.
+    pub removed_devices: Vec<String>,
+}
+
+/// Compares the live devices against the snapshot at `path`.
+pub fn diff_devices(
+    instance: &Instance,
+    instance_version: u32,
+    devices: &[vk::PhysicalDevice],
+    path: &Path,
+    as_json: bool,
+    use_ansi: bool,
+) -> Result<(), Box<dyn Error>> {
+    let old = read(path)?;
+    let details = devices
+        .iter()
+        .map(|&device| DeviceDetails::query(instance, instance_version, device))
+        .collect::<Vec<_>>();
+    let new = from_report(&vulkaninfo::report(&details))?;
+    print_diff(&diff(&old, &new), as_json, use_ansi)
+}
+
+/// Compares the snapshot at `new` against the one at `old`.
+pub fn diff_files(
+    old: &Path,
+    new: &Path,
+    as_json: bool,
+    use_ansi: bool,
+) -> Result<(), Box<dyn Error>> {
+    print_diff(&diff(&read(old)?, &read(new)?), as_json, use_ansi)
+}
+
+fn print_diff(diff: &SnapshotDiff, as_json: bool, use_ansi: bool) -> Result<(), Box<dyn Error>> {
+    if as_json {
+        println!("{}", serde_json::to_string_pretty(&diff.to_json())?);
+    } else {
+        for line in diff.lines(use_ansi) {
+            println!("{}", line);
+        }
+    }
+    Ok(())
+}
+
+/// Reads the devices of a snapshot file.
+pub fn read(path: &Path) -> Result<Vec<DeviceSnapshot>, String> {
+    let contents =
+        fs::read_to_string(path).map_err(|error| format!("{}: {}", path.display(), error))?;
+    let json = serde_json::from_str(&contents)
+        .map_err(|error| format!("{}: {}", path.display(), error))?;
+    from_report(&json).map_err(|error| format!("{}: {}", path.display(), error))
+}
+
+/// Reads the devices of a `vulkaninfo --json` report, one per profile.
+pub fn from_report(json: &Value) -> Result<Vec<DeviceSnapshot>, String> {
+    let capabilities = json.get("capabilities").and_then(Value::as_object);
+    let profiles = json
+        .get("profiles")
+        .and_then(Value::as_object)
+        .zip(capabilities)
+        .ok_or("not a vulkaninfo --json or vkfetch-rs --export report")?;
+
+    Ok(profiles
+        .0
+        .iter()
+        .map(|(name, profile)| {
+            let blocks = profile
+                .get("capabilities")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(|capability| profiles.1.get(capability.as_str()?))
+                .collect::<Vec<_>>();
+            DeviceSnapshot::new(name, &blocks)
+        })
+        .collect())
+}
+
+impl DeviceSnapshot {
+    fn new(profile: &str, blocks: &[&Value]) -> Self {
+        let sections = SECTIONS.map(|(section, _)| {
+            let mut members = BTreeMap::new();
+            for block in blocks {
+                if let Some(value) = block.get(section) {
+                    flatten(String::new(), value, &mut members);
+                }
+            }
+            members
+        });
+        let properties = &sections[2];
+        let text = |name: &str| {
+            properties
+                .iter()
+                .find(|(key, _)| key.rsplit('.').next() == Some(name))
+                .map(|(_, value)| display(value))
+        };
+        let number = |name: &str| {
+            properties
+                .get(&format!("VkPhysicalDeviceProperties.{name}"))
+                .and_then(Value::as_u64)
+        };
+        let driver = [
+            text("driverName"),
+            text("driverInfo"),
+            number("driverVersion").map(|version| format!("({version})")),
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+        Self {
+            name: text("deviceName").unwrap_or_else(|| profile.to_string()),
+            driver,
+            id: (number("vendorID"), number("deviceID")),
+            sections,
+        }
+    }
+}
+
+/// Flattens nested objects into `members`, joining the keys with dots.
+/// Arrays and scalars are compared as a whole.
+fn flatten(prefix: String, value: &Value, members: &mut BTreeMap<String, Value>) {
+    match value {
+        Value::Object(object) if !object.is_empty() => {
+            for (key, value) in object {
+                let key = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                flatten(key, value, members);
+            }
+        }
+        _ => {
+            members.insert(prefix, value.clone());
+        }
+    }
+}
+
+/// Compares two snapshots. Devices are paired by vendor and device ID;
+/// identical GPUs are paired in the order of their profiles.
+pub fn diff(old: &[DeviceSnapshot], new: &[DeviceSnapshot]) -> SnapshotDiff {
+    let mut unpaired = old.iter().collect::<Vec<_>>();
+    let mut result = SnapshotDiff::default();
+    for device in new {
+        let Some(position) = unpaired.iter().position(|old| old.id == device.id) else {
+            result.added_devices.push(device.name.clone());
+            continue;
+        };
+        let old = unpaired.remove(position);
+        result.devices.push(DeviceDiff {
+            name: device.name.clone(),
+            old_driver: old.driver.clone(),
+            new_driver: device.driver.clone(),
+            sections: SECTIONS
+                .iter()
+                .zip(old.sections.iter().zip(&device.sections))
+                .map(|((section, _), (old, new))| diff_section(section, old, new))
+                .collect(),
+        });
+    }
+    result.removed_devices = unpaired.iter().map(|old| old.name.clone()).collect();
+    result
+}
+
+fn diff_section(
+    section: &str,
+    old: &BTreeMap<String, Value>,
+    new: &BTreeMap<String, Value>,
+) -> SectionDiff {
+    let [old_structures, new_structures] = [old, new].map(|members| {
+        members
+            .keys()
+            .filter_map(|name| structure(section, name))
+            .collect::<BTreeSet<_>>()
+    });
+    let compared = |name: &str| {
+        structure(section, name).is_none_or(|structure| {
+            old_structures.contains(structure) && new_structures.contains(structure)
+        })
+    };
+
+    let mut diff = SectionDiff::default();
+    for (name, value) in new {
+        match old.get(name) {
+            _ if !compared(name) => {}
+            None => diff.added.push((name.clone(), value.clone())),
+            Some(previous) if previous != value => {
+                diff.changed
+                    .push((name.clone(), previous.clone(), value.clone()))
+            }
+            Some(_) => {}
+        }
+    }
+    for (name, value) in old {
+        if compared(name) && !new.contains_key(name) {
+            diff.removed.push((name.clone(), value.clone()));
+        }
+    }
+    diff.not_compared = old_structures
+        .symmetric_difference(&new_structures)
+        .map(|structure| structure.to_string())
+        .collect();
+    diff
+}
+
+/// Returns the structure a flattened member belongs to, or `None` for
+/// members that both vulkaninfo and vkfetch-rs always write: extensions and
+/// the `VkFormatProperties` of the formats vkfetch-rs queries.
+fn structure<'a>(section: &str, name: &'a str) -> Option<&'a str> {
+    match section {
+        "extensions" => None,
+        "formats" => {
+            let mut parts = name.splitn(3, '.');
+            let (format, structure) = (parts.next()?, parts.next()?);
+            if structure == "VkFormatProperties" && details::is_queried_format(format) {
+                None
+            } else {
+                Some(&name[..format.len() + 1 + structure.len()])
+            }
+        }
+        _ => name.split('.').next(),
+    }
+}
+
+impl SectionDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl DeviceDiff {
+    pub fn is_empty(&self) -> bool {
+        self.old_driver == self.new_driver && self.sections.iter().all(SectionDiff::is_empty)
+    }
+}
+
+impl SnapshotDiff {
+    /// Returns the diff as text, one device after another.
+    pub fn lines(&self, use_ansi: bool) -> Vec<String> {
+        let style = |style: &'static str| if use_ansi { style } else { EMPTY };
+        let (bold, reset) = (style(BOLD), style(RESET));
+        let (green, red, yellow) = (style(BRIGHT_GREEN), style(BRIGHT_RED), style(BRIGHT_YELLOW));
+
+        let mut lines = Vec::new();
+        for device in &self.devices {
+            lines.push(format!("{}{}{}", bold, device.name, reset));
+            if device.old_driver == device.new_driver {
+                lines.push(format!("{}Driver: {}", ALIGNMENT, device.new_driver));
+            } else {
+                lines.push(format!(
+                    "{}Driver: {}{}{} -> {}{}{}",
+                    ALIGNMENT, red, device.old_driver, reset, green, device.new_driver, reset
+                ));
+            }
+            if device.is_empty() {
+                lines.push(format!("{}No changes", ALIGNMENT));
+            }
+            for ((_, heading), section) in SECTIONS.iter().zip(&device.sections) {
+                if !section.not_compared.is_empty() {
+                    lines.push(format!(
+                        "{}{} not compared, only in one snapshot: {}",
+                        ALIGNMENT,
+                        heading,
+                        section.not_compared.join(", ")
+                    ));
+                }
+                if section.is_empty() {
+                    continue;
+                }
+                lines.push(format!(
+                    "{}{}: {} added, {} removed, {} changed",
+                    ALIGNMENT,
+                    heading,
+                    section.added.len(),
+                    section.removed.len(),
+                    section.changed.len()
+                ));
+                for (name, value) in &section.added {
+                    lines.push(format!(
+                        "{0}{0}{1}+ {2}: {3}{4}",
+                        ALIGNMENT,
+                        green,
+                        name,
+                        display(value),
+                        reset
+                    ));
+                }
+                for (name, value) in &section.removed {
+                    lines.push(format!(
+                        "{0}{0}{1}- {2}: {3}{4}",
+                        ALIGNMENT,
+                        red,
+                        name,
+                        display(value),
+                        reset
+                    ));
+                }
+                for (name, old, new) in &section.changed {
+                    lines.push(format!(
+                        "{0}{0}{1}~ {2}: {3} -> {4}{5}",
+                        ALIGNMENT,
+                        yellow,
+                        name,
+                        display(old),
+                        display(new),
+                        reset
+                    ));
+                }
+            }
+            lines.push(String::new());
+        }
+        for name in &self.added_devices {
+            lines.push(format!(
+                "{}+ {}{} (not in the snapshot)",
+                green, name, reset
+            ));
+        }
+        for name in &self.removed_devices {
+            lines.push(format!("{}- {}{} (no longer present)", red, name, reset));
+        }
+        lines
+    }
+
+    /// Returns the diff as JSON, with the same sections as the text output.
+    pub fn to_json(&self) -> Value {
+        let devices = self
+            .devices
+            .iter()
+            .map(|device| {
+                let mut object = Map::new();
+                object.insert("name".into(), json!(device.name));
+                object.insert(
+                    "driver".into(),
+                    json!({ "old": device.old_driver, "new": device.new_driver }),
+                );
+                for ((section, _), diff) in SECTIONS.iter().zip(&device.sections) {
+                    let members = |members: &[(String, Value)]| {
+                        members.iter().cloned().collect::<Map<_, _>>()
+                    };
+                    let changed = diff
+                        .changed
+                        .iter()
+                        .map(|(name, old, new)| (name.clone(), json!({ "old": old, "new": new })))
+                        .collect::<Map<_, _>>();
+                    object.insert(
+                        section.to_string(),
+                        json!({
+                            "added": members(&diff.added),
+                            "removed": members(&diff.removed),
+                            "changed": changed,
+                            "notCompared": diff.not_compared,
+                        }),
+                    );
+                }
+                Value::Object(object)
+            })
+            .collect::<Vec<_>>();
+        json!({
+            "devices": devices,
+            "addedDevices": self.added_devices,
+            "removedDevices": self.removed_devices,
+        })
+    }
+}
+
+/// Formats a member value without JSON quoting.
+fn display(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Array(values) => format!(
+            "[{}]",
+            values.iter().map(display).collect::<Vec<_>>().join(", ")
+        ),
+        _ => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(driver_version: u32, extensions: Value, features: Value, limits: Value) -> Value {
+        json!({
+            "capabilities": {
+                "device": {
+                    "extensions": extensions,
+                    "features": { "VkPhysicalDeviceVulkan12Features": features },
+                    "properties": {
+                        "VkPhysicalDeviceProperties": {
+                            "deviceName": "AMD Radeon RX 7900 XTX",
+                            "driverVersion": driver_version,
+                            "vendorID": 4098,
+                            "deviceID": 29772,
+                            "limits": limits
+                        },
+                        "VkPhysicalDeviceVulkan12Properties": {
+                            "driverName": "radv",
+                            "driverInfo": "Mesa 24.1.0"
+                        }
+                    }
+                }
+            },
+            "profiles": {
+                "VP_VULKANINFO_AMD_Radeon_RX_7900_XTX": { "capabilities": ["device"] }
+            }
+        })
+    }
+
+    #[test]
+    fn test_diff() {
+        let old = from_report(&snapshot(
+            1,
+            json!({ "VK_KHR_swapchain": 70, "VK_EXT_removed": 1 }),
+            json!({ "shaderInt8": false, "shaderFloat16": true }),
+            json!({ "maxImageDimension2D": 16384, "maxViewports": 16 }),
+        ))
+        .unwrap();
+        let new = from_report(&snapshot(
+            2,
+            json!({ "VK_KHR_swapchain": 70, "VK_KHR_maintenance7": 1 }),
+            json!({ "shaderInt8": true, "shaderFloat16": true }),
+            json!({ "maxImageDimension2D": 32768, "maxViewports": 16 }),
+        ))
+        .unwrap();
+        assert_eq!(old[0].name, "AMD Radeon RX 7900 XTX");
+        assert_eq!(old[0].driver, "radv Mesa 24.1.0 (1)");
+
+        let diff = diff(&old, &new);
+        assert!(diff.added_devices.is_empty() && diff.removed_devices.is_empty());
+        let device = &diff.devices[0];
+        assert_eq!(device.new_driver, "radv Mesa 24.1.0 (2)");
+        assert_eq!(
+            device.sections[0].added,
+            [("VK_KHR_maintenance7".to_string(), json!(1))]
+        );
+        assert_eq!(
+            device.sections[0].removed,
+            [("VK_EXT_removed".to_string(), json!(1))]
+        );
+        assert_eq!(
+            device.sections[1].changed,
+            [(
+                "VkPhysicalDeviceVulkan12Features.shaderInt8".to_string(),
+                json!(false),
+                json!(true)
+            )]
+        );
+        let properties = device.sections[2]
+            .changed
+            .iter()
+            .map(|(name, _, _)| name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            properties,
+            [
+                "VkPhysicalDeviceProperties.driverVersion",
+                "VkPhysicalDeviceProperties.limits.maxImageDimension2D"
+            ]
+        );
+
+        let lines = diff.lines(false);
+        assert!(
+            lines.contains(&"    Driver: radv Mesa 24.1.0 (1) -> radv Mesa 24.1.0 (2)".to_string())
+        );
+        assert!(lines.contains(&"        + VK_KHR_maintenance7: 1".to_string()));
+        assert!(
+            lines.contains(
+                &"        ~ VkPhysicalDeviceProperties.limits.maxImageDimension2D: 16384 -> 32768"
+                    .to_string()
+            )
+        );
+
+        let json = diff.to_json();
+        assert_eq!(
+            json["devices"][0]["features"]["changed"]["VkPhysicalDeviceVulkan12Features.shaderInt8"],
+            json!({ "old": false, "new": true })
+        );
+    }
+
+    #[test]
+    fn test_structures_in_one_snapshot() {
+        let new = snapshot(1, json!({}), json!({ "shaderInt8": true }), json!({}));
+        let mut old = new.clone();
+        let device = &mut old["capabilities"]["device"];
+        // Structures vulkaninfo writes but vkfetch-rs does not query.
+        device["features"]["VkPhysicalDeviceMultiviewFeatures"] = json!({ "multiview": true });
+        device["properties"]["VkPhysicalDeviceDriverProperties"] = json!({ "driverID": 3 });
+        device["formats"] = json!({
+            "VK_FORMAT_R8_UNORM": {
+                "VkFormatProperties": { "bufferFeatures": ["VK_FORMAT_FEATURE_VERTEX_BUFFER_BIT"] },
+                "VkFormatProperties3": { "bufferFeatures": [] }
+            },
+            "VK_FORMAT_PVRTC1_2BPP_UNORM_BLOCK_IMG": {
+                "VkFormatProperties": { "bufferFeatures": [] }
+            }
+        });
+
+        let diff = diff(&from_report(&old).unwrap(), &from_report(&new).unwrap());
+        let device = &diff.devices[0];
+        assert_eq!(
+            device.sections[1].not_compared,
+            ["VkPhysicalDeviceMultiviewFeatures"]
+        );
+        assert!(device.sections[1].is_empty() && device.sections[2].is_empty());
+        // A queried format that disappears is still a change.
+        let formats = &device.sections[3];
+        assert_eq!(
+            formats.removed,
+            [(
+                "VK_FORMAT_R8_UNORM.VkFormatProperties.bufferFeatures".to_string(),
+                json!(["VK_FORMAT_FEATURE_VERTEX_BUFFER_BIT"])
+            )]
+        );
+        assert_eq!(
+            formats.not_compared,
+            [
+                "VK_FORMAT_PVRTC1_2BPP_UNORM_BLOCK_IMG.VkFormatProperties",
+                "VK_FORMAT_R8_UNORM.VkFormatProperties3"
+            ]
+        );
+        assert!(diff.lines(false).contains(
+            &"    Properties not compared, only in one snapshot: VkPhysicalDeviceDriverProperties"
+                .to_string()
+        ));
+        assert_eq!(
+            diff.to_json()["devices"][0]["properties"]["notCompared"],
+            json!(["VkPhysicalDeviceDriverProperties"])
+        );
+    }
+
+    #[test]
+    fn test_unchanged_and_unpaired_devices() {
+        let report = snapshot(1, json!({}), json!({}), json!({}));
+        let old = from_report(&report).unwrap();
+        let diff = diff(&old, &old);
+        assert!(diff.devices[0].is_empty());
+        assert!(diff.lines(false).contains(&"    No changes".to_string()));
+
+        let diff = super::diff(&old, &[]);
+        assert_eq!(diff.removed_devices, ["AMD Radeon RX 7900 XTX"]);
+        assert!(from_report(&json!({ "properties": {} })).is_err());
+    }
+}
@@ -0,0 +1,248 @@
+use std::{
+    env, fmt, fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// Environment variable that relocates every kernel interface lookup,
+/// e.g. to point vkfetch at a captured copy of `/sys` and `/dev`.
+pub const SYSROOT_ENV: &str = "VKFETCH_SYSROOT";
+
+/// Root directory under which `sys`, `dev` and `proc` are looked up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SysRoot {
+    root: PathBuf,
+}
+
+impl Default for SysRoot {
+    fn default() -> Self {
+        Self::new("/")
+    }
+}
+
+impl SysRoot {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Uses `VKFETCH_SYSROOT` if it is set, the real root otherwise.
+    pub fn from_env() -> Self {
+        env::var_os(SYSROOT_ENV)
+            .filter(|root| !root.is_empty())
+            .map(Self::new)
+            .unwrap_or_default()
+    }
+
+    pub fn sys(&self) -> PathBuf {
+        self.root.join("sys")
+    }
+
+    pub fn dev(&self) -> PathBuf {
+        self.root.join("dev")
+    }
+
+    pub fn proc(&self) -> PathBuf {
+        self.root.join("proc")
+    }
+
+    /// Relocates any absolute system path, e.g. `/etc/vulkan/icd.d`.
+    pub fn join(&self, path: impl AsRef<Path>) -> PathBuf {
+        let path = path.as_ref();
+        self.root.join(path.strip_prefix("/").unwrap_or(path))
+    }
+}
+
+/// A PCI address in `domain:bus:device.function` form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PciAddress {
+    pub domain: u32,
+    pub bus: u32,
+    pub device: u32,
+    pub function: u32,
+}
+
+impl PciAddress {
+    /// Returns the device directory under `/sys/bus/pci/devices`.
+    pub fn sysfs_dir(&self, root: &SysRoot) -> PathBuf {
+        root.sys().join("bus/pci/devices").join(self.to_string())
+    }
+}
+
+impl fmt::Display for PciAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:04x}:{:02x}:{:02x}.{:x}",
+            self.domain, self.bus, self.device, self.function
+        )
+    }
+}
+
+impl FromStr for PciAddress {
+    type Err = ();
+
+    /// Parses `0000:01:00.0`; the domain may be omitted.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (rest, function) = s.trim().rsplit_once('.').ok_or(())?;
+        let mut parts = rest.rsplitn(3, ':');
+        let device = parts.next().ok_or(())?;
+        let bus = parts.next().ok_or(())?;
+        let domain = parts.next().unwrap_or("0");
+        let hex = |value: &str| u32::from_str_radix(value, 16).map_err(|_| ());
+
+        Ok(Self {
+            domain: hex(domain)?,
+            bus: hex(bus)?,
+            device: hex(device)?,
+            function: hex(function)?,
+        })
+    }
+}
+
+/// Reads a sysfs attribute with surrounding whitespace removed.
+pub(crate) fn read_trimmed(path: impl AsRef<Path>) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|contents| contents.trim().to_string())
+}
+
+/// Reads a sysfs attribute holding a single unsigned integer.
+pub(crate) fn read_u64(path: impl AsRef<Path>) -> Option<u64> {
+    read_trimmed(path)?.parse().ok()
+}
+
+/// Returns the final path component a symlink points to,
+/// e.g. the driver name behind `device/driver`.
+pub(crate) fn link_name(path: impl AsRef<Path>) -> Option<String> {
+    fs::read_link(path)
+        .ok()?
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+}
+
+#[cfg(all(test, unix))]
+pub(crate) mod fixture {
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use super::SysRoot;
+
+    static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+    /// A throwaway directory tree that mimics `/sys`, `/dev` and `/proc`.
+    pub(crate) struct Fixture {
+        root: PathBuf,
+    }
+
+    impl Fixture {
+        pub(crate) fn new(name: &str) -> Self {
+            let root = std::env::temp_dir().join(format!(
+                "vkfetch-{}-{}-{}",
+                name,
+                std::process::id(),
+                NEXT_ID.fetch_add(1, Ordering::Relaxed)
+            ));
+            let _ = fs::remove_dir_all(&root);
+            fs::create_dir_all(&root).unwrap();
+            Self { root }
+        }
+
+        pub(crate) fn root(&self) -> SysRoot {
+            SysRoot::new(&self.root)
+        }
+
+        pub(crate) fn path(&self, relative: &str) -> PathBuf {
+            self.root.join(relative)
+        }
+
+        pub(crate) fn write(&self, relative: &str, contents: &str) {
+            let path = self.path(relative);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+
+        pub(crate) fn mkdir(&self, relative: &str) {
+            fs::create_dir_all(self.path(relative)).unwrap();
+        }
+
+        pub(crate) fn symlink(&self, relative: &str, target: impl AsRef<Path>) {
+            let path = self.path(relative);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::os::unix::fs::symlink(target, path).unwrap();
+        }
+    }
+
+    impl Drop for Fixture {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.root);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(unix)]
+    fn test_link_name() {
+        use super::fixture::Fixture;
+
+        let fixture = Fixture::new("sysfs");
+        fixture.symlink("sys/driver", "../bus/pci/drivers/amdgpu");
+        assert_eq!(
+            link_name(fixture.path("sys/driver")).as_deref(),
+            Some("amdgpu")
+        );
+        assert_eq!(link_name(fixture.path("sys/missing")), None);
+    }
+
+    #[test]
+    fn test_root_layout() {
+        let root = SysRoot::new("/tmp/capture");
+        assert_eq!(root.sys(), Path::new("/tmp/capture/sys"));
+        assert_eq!(root.dev(), Path::new("/tmp/capture/dev"));
+        assert_eq!(root.proc(), Path::new("/tmp/capture/proc"));
+        assert_eq!(
+            root.join("/etc/vulkan"),
+            Path::new("/tmp/capture/etc/vulkan")
+        );
+        assert_eq!(SysRoot::default().sys(), Path::new("/sys"));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_read_helpers() {
+        use super::fixture::Fixture;
+        let fixture = Fixture::new("sysfs");
+        fixture.write("sys/value", "42\n");
+        fixture.write("sys/text", "  amdgpu \n");
+        assert_eq!(read_u64(fixture.path("sys/value")), Some(42));
+        assert_eq!(
+            read_trimmed(fixture.path("sys/text")).as_deref(),
+            Some("amdgpu")
+        );
+        assert_eq!(read_u64(fixture.path("sys/text")), None);
+        assert_eq!(read_u64(fixture.path("sys/missing")), None);
+    }
+
+    #[test]
+    fn test_pci_address() {
+        let address: PciAddress = "0000:0a:00.1".parse().unwrap();
+        assert_eq!(address.bus, 0x0a);
+        assert_eq!(address.function, 1);
+        assert_eq!(address.to_string(), "0000:0a:00.1");
+        assert_eq!(
+            "01:00.0".parse::<PciAddress>().unwrap().to_string(),
+            "0000:01:00.0"
+        );
+        assert!("not-an-address".parse::<PciAddress>().is_err());
+        assert_eq!(
+            address.sysfs_dir(&SysRoot::new("/x")),
+            Path::new("/x/sys/bus/pci/devices/0000:0a:00.1")
+        );
+    }
+}
@@ -0,0 +1,421 @@
+use ash::vk;
+use std::{
+    fmt, fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use crate::loader::LoaderEnv;
+
+/// What went wrong before any device could be printed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Cause {
+    /// The Vulkan loader library could not be opened (`loaded` feature).
+    LoadLoader(String),
+    CreateInstance(vk::Result),
+    EnumerateDevices(vk::Result),
+    /// The instance was created but reported no physical devices.
+    NoDevices,
+}
+
+/// The likely reason behind a [`Cause`]. Each kind exits with its own code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureKind {
+    LoaderMissing,
+    NoDriver,
+    DriverLibraryMissing,
+    PortabilityRequired,
+    DeviceNodesUnavailable,
+    NoDevices,
+    Other,
+}
+
+impl FailureKind {
+    /// Exit code of the process; 1 and 2 are left to generic and usage errors.
+    pub const fn exit_code(self) -> u8 {
+        match self {
+            FailureKind::LoaderMissing => 3,
+            FailureKind::NoDriver => 4,
+            FailureKind::DriverLibraryMissing => 5,
+            FailureKind::PortabilityRequired => 6,
+            FailureKind::DeviceNodesUnavailable => 7,
+            FailureKind::NoDevices => 8,
+            FailureKind::Other => 9,
+        }
+    }
+
+    /// One sentence explaining the failure.
+    pub const fn explanation(self) -> &'static str {
+        match self {
+            FailureKind::LoaderMissing => "The Vulkan loader (libvulkan) is not installed.",
+            FailureKind::NoDriver => "No Vulkan driver (ICD) is installed.",
+            FailureKind::DriverLibraryMissing => {
+                "Vulkan drivers are registered, but none of their libraries could be found."
+            }
+            FailureKind::PortabilityRequired => {
+                "The only drivers available are portability drivers such as MoltenVK, \
+                 which this loader does not enumerate."
+            }
+            FailureKind::DeviceNodesUnavailable => {
+                "The GPU device nodes in /dev/dri, or /dev/nvidia* for the NVIDIA driver, \
+                 are missing or not accessible."
+            }
+            FailureKind::NoDevices => "The drivers loaded but none of them reported a device.",
+            FailureKind::Other => "The Vulkan loader reported an unexpected error.",
+        }
+    }
+
+    /// Things worth trying, most likely fix first.
+    pub const fn suggestions(self) -> &'static [&'static str] {
+        match self {
+            FailureKind::LoaderMissing => &[
+                "Install the Vulkan loader: libvulkan1 (Debian/Ubuntu), vulkan-loader (Fedora), \
+                 vulkan-icd-loader (Arch) or the LunarG SDK on macOS",
+                "If it is installed in a non-standard place, add that directory to \
+                 LD_LIBRARY_PATH (DYLD_LIBRARY_PATH on macOS)",
+            ],
+            FailureKind::NoDriver => &[
+                "Install a driver: mesa-vulkan-drivers (Debian/Ubuntu), vulkan-radeon, \
+                 vulkan-intel or nvidia-utils (Arch), or MoltenVK on macOS",
+                "If VK_DRIVER_FILES or VK_ICD_FILENAMES is set, check that it points at \
+                 existing manifests, or unset it",
+            ],
+            FailureKind::DriverLibraryMissing => &[
+                "Reinstall the driver package, or remove manifests left behind by an \
+                 uninstalled driver",
+                "On 64-bit systems, make sure the 64-bit build of the driver is installed, \
+                 not only the 32-bit one",
+            ],
+            FailureKind::PortabilityRequired => &[
+                "Update the Vulkan loader to 1.3.216 or newer, which supports \
+                 VK_KHR_portability_enumeration",
+                "Install the LunarG Vulkan SDK, which ships MoltenVK and a current loader",
+            ],
+            FailureKind::DeviceNodesUnavailable => &[
+                "In a container, pass the GPU through, e.g. `docker run --device /dev/dri` \
+                 or `podman run --device /dev/dri`",
+                "For the NVIDIA driver, use `docker run --gpus all` with the NVIDIA Container \
+                 Toolkit, or `podman run --device nvidia.com/gpu=all` with CDI",
+                "Add your user to the render and video groups and log in again",
+            ],
+            FailureKind::NoDevices => &[
+                "Check DRI_PRIME, MESA_VK_DEVICE_SELECT, __VK_LAYER_NV_optimus and \
+                 VK_LOADER_DRIVERS_SELECT, which can hide devices",
+                "Make sure the kernel driver for the GPU is loaded (see dmesg)",
+            ],
+            FailureKind::Other => &[
+                "Run with --no-implicit-layers to rule out a broken overlay or capture layer",
+                "Run with VK_LOADER_DEBUG=all to see what the loader is doing",
+            ],
+        }
+    }
+}
+
+/// A classified failure with the evidence that led to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure {
+    pub kind: FailureKind,
+    pub cause: Cause,
+    /// Observations backing the classification, e.g. missing libraries.
+    pub details: Vec<String>,
+}
+
+impl Failure {
+    /// Works out why `cause` happened from the installed manifests and
+    /// device nodes. `portability_enumeration` tells whether the loader
+    /// supports `VK_KHR_portability_enumeration`.
+    pub fn diagnose(cause: Cause, env: &LoaderEnv, portability_enumeration: bool) -> Self {
+        let mut details = Vec::new();
+        let kind = classify(&cause, env, portability_enumeration, &mut details);
+        Self {
+            kind,
+            cause,
+            details,
+        }
+    }
+}
+
+fn classify(
+    cause: &Cause,
+    env: &LoaderEnv,
+    portability_enumeration: bool,
+    details: &mut Vec<String>,
+) -> FailureKind {
+    if let Cause::LoadLoader(error) = cause {
+        details.push(error.clone());
+        return FailureKind::LoaderMissing;
+    }
+
+    // The NVIDIA driver uses /dev/nvidia* rather than the DRM nodes.
+    let mut nvidia_only = false;
+    // Windows drivers are registered in the registry, not in manifest
+    // directories, so missing manifests prove nothing there.
+    if cfg!(unix) {
+        let manifests = env.driver_manifests();
+        for error in manifests
+            .iter()
+            .filter_map(|manifest| manifest.as_ref().err())
+        {
+            details.push(format!("unusable manifest {}", error));
+        }
+        let manifests = manifests.into_iter().flatten().collect::<Vec<_>>();
+        if manifests.is_empty() {
+            let searched = env
+                .driver_search_paths()
+                .iter()
+                .map(|search| search.path.display().to_string())
+                .collect::<Vec<_>>();
+            details.push(format!("searched {}", searched.join(", ")));
+            return FailureKind::NoDriver;
+        }
+
+        for manifest in manifests
+            .iter()
+            .filter(|manifest| manifest.library.is_none())
+        {
+            details.push(format!(
+                "{} names {}, which was not found",
+                manifest.path.display(),
+                manifest.library_path
+            ));
+        }
+        if manifests.iter().all(|manifest| manifest.library.is_none()) {
+            return FailureKind::DriverLibraryMissing;
+        }
+
+        if !portability_enumeration
+            && manifests
+                .iter()
+                .filter(|manifest| manifest.library.is_some())
+                .all(|manifest| manifest.is_portability_driver)
+        {
+            details.push("every installed driver is a portability driver".to_string());
+            return FailureKind::PortabilityRequired;
+        }
+
+        nvidia_only = manifests
+            .iter()
+            .filter(|manifest| manifest.library.is_some())
+            .all(|manifest| {
+                manifest
+                    .library_path
+                    .to_ascii_lowercase()
+                    .contains("nvidia")
+            });
+    }
+
+    let dev = env.root().dev();
+    if cfg!(target_os = "linux")
+        && let Some(problem) = if nvidia_only {
+            nvidia_node_problem(&dev)
+        } else {
+            device_node_problem(&dev.join("dri"))
+        }
+    {
+        details.push(problem);
+        return FailureKind::DeviceNodesUnavailable;
+    }
+
+    match cause {
+        Cause::NoDevices => FailureKind::NoDevices,
+        _ => FailureKind::Other,
+    }
+}
+
+/// Describes why the DRM nodes in `dri_dir` cannot be used, if they cannot.
+fn device_node_problem(dri_dir: &Path) -> Option<String> {
+    let Ok(entries) = fs::read_dir(dri_dir) else {
+        return Some(format!("{} does not exist", dri_dir.display()));
+    };
+    let nodes = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("renderD") || name.starts_with("card"))
+        })
+        .collect::<Vec<PathBuf>>();
+    if nodes.is_empty() {
+        return Some(format!("{} has no card or render nodes", dri_dir.display()));
+    }
+
+    let denied = nodes.iter().all(|node| {
+        fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(node)
+            .is_err_and(|error| error.kind() == ErrorKind::PermissionDenied)
+    });
+    denied.then(|| {
+        format!(
+            "permission denied opening the nodes in {}",
+            dri_dir.display()
+        )
+    })
+}
+
+/// Describes why the NVIDIA device nodes in `dev_dir` cannot be used, if
+/// they cannot. `nvidiactl` alone does not make a GPU usable.
+fn nvidia_node_problem(dev_dir: &Path) -> Option<String> {
+    let has_gpu_node = fs::read_dir(dev_dir).ok()?.flatten().any(|entry| {
+        entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.strip_prefix("nvidia"))
+            .is_some_and(|index| !index.is_empty() && index.bytes().all(|b| b.is_ascii_digit()))
+    });
+    (!has_gpu_node).then(|| format!("{} has no nvidia0 or other GPU node", dev_dir.display()))
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let what = match &self.cause {
+            Cause::LoadLoader(_) => "could not load Vulkan".to_string(),
+            Cause::CreateInstance(result) => {
+                format!("could not create a Vulkan instance ({:?})", result)
+            }
+            Cause::EnumerateDevices(result) => {
+                format!("could not list Vulkan devices ({:?})", result)
+            }
+            Cause::NoDevices => "no Vulkan devices found".to_string(),
+        };
+        writeln!(f, "vkfetch-rs: {}", what)?;
+        writeln!(f)?;
+        writeln!(f, "Why: {}", self.kind.explanation())?;
+        for detail in &self.details {
+            writeln!(f, "    {}", detail)?;
+        }
+        writeln!(f)?;
+        writeln!(f, "Try:")?;
+        for suggestion in self.kind.suggestions() {
+            writeln!(f, "    - {}", suggestion)?;
+        }
+        write!(
+            f,
+            "    - Run vkfetch-rs --diagnose for the full driver and layer report"
+        )
+    }
+}
+
+impl std::error::Error for Failure {}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::loader::tests::loader_env;
+    use crate::sysfs::fixture::Fixture;
+
+    const RADV: &str = r#"{"ICD": {"library_path": "libvulkan_radeon.so"}}"#;
+    const MOLTENVK: &str =
+        r#"{"ICD": {"library_path": "libMoltenVK.dylib", "is_portability_driver": true}}"#;
+
+    fn kind(fixture: &Fixture, cause: Cause, portability_enumeration: bool) -> FailureKind {
+        Failure::diagnose(cause, &loader_env(fixture, &[]), portability_enumeration).kind
+    }
+
+    #[test]
+    fn test_missing_loader_and_drivers() {
+        let fixture = Fixture::new("troubleshoot-drivers");
+        assert_eq!(
+            kind(&fixture, Cause::LoadLoader("libvulkan.so.1".into()), true),
+            FailureKind::LoaderMissing
+        );
+
+        let incompatible = Cause::CreateInstance(vk::Result::ERROR_INCOMPATIBLE_DRIVER);
+        let failure = Failure::diagnose(incompatible.clone(), &loader_env(&fixture, &[]), true);
+        assert_eq!(failure.kind, FailureKind::NoDriver);
+        assert!(failure.details[0].starts_with("searched "));
+
+        fixture.write("usr/share/vulkan/icd.d/radeon_icd.json", RADV);
+        assert_eq!(
+            kind(&fixture, incompatible, true),
+            FailureKind::DriverLibraryMissing
+        );
+    }
+
+    #[test]
+    fn test_portability_required() {
+        let fixture = Fixture::new("troubleshoot-portability");
+        fixture.write("usr/share/vulkan/icd.d/MoltenVK_icd.json", MOLTENVK);
+        fixture.write("usr/lib/libMoltenVK.dylib", "");
+        fixture.write("dev/dri/renderD128", "");
+
+        let cause = Cause::CreateInstance(vk::Result::ERROR_INCOMPATIBLE_DRIVER);
+        assert_eq!(
+            kind(&fixture, cause.clone(), false),
+            FailureKind::PortabilityRequired
+        );
+        assert_eq!(kind(&fixture, cause, true), FailureKind::Other);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_device_nodes_and_no_devices() {
+        let fixture = Fixture::new("troubleshoot-nodes");
+        fixture.write("usr/share/vulkan/icd.d/radeon_icd.json", RADV);
+        fixture.write("usr/lib/libvulkan_radeon.so", "");
+
+        let failure = Failure::diagnose(Cause::NoDevices, &loader_env(&fixture, &[]), true);
+        assert_eq!(failure.kind, FailureKind::DeviceNodesUnavailable);
+        assert!(failure.details[0].ends_with("dev/dri does not exist"));
+
+        fixture.write("dev/dri/renderD128", "");
+        assert_eq!(
+            kind(&fixture, Cause::NoDevices, true),
+            FailureKind::NoDevices
+        );
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_nvidia_device_nodes() {
+        let fixture = Fixture::new("troubleshoot-nvidia");
+        fixture.write(
+            "usr/share/vulkan/icd.d/nvidia_icd.json",
+            r#"{"ICD": {"library_path": "libGLX_nvidia.so.0"}}"#,
+        );
+        fixture.write("usr/lib/libGLX_nvidia.so.0", "");
+        fixture.write("dev/nvidiactl", "");
+
+        let failure = Failure::diagnose(Cause::NoDevices, &loader_env(&fixture, &[]), true);
+        assert_eq!(failure.kind, FailureKind::DeviceNodesUnavailable);
+        assert!(failure.details[0].ends_with("has no nvidia0 or other GPU node"));
+
+        // Containers started with --gpus all have no /dev/dri.
+        fixture.write("dev/nvidia0", "");
+        assert_eq!(
+            kind(&fixture, Cause::NoDevices, true),
+            FailureKind::NoDevices
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let failure = Failure {
+            kind: FailureKind::NoDriver,
+            cause: Cause::CreateInstance(vk::Result::ERROR_INCOMPATIBLE_DRIVER),
+            details: vec!["searched /etc/vulkan/icd.d".to_string()],
+        };
+        let report = failure.to_string();
+        assert!(report.starts_with(
+            "vkfetch-rs: could not create a Vulkan instance (ERROR_INCOMPATIBLE_DRIVER)\n\n\
+             Why: No Vulkan driver (ICD) is installed.\n    searched /etc/vulkan/icd.d\n\nTry:\n"
+        ));
+        assert!(report.ends_with("--diagnose for the full driver and layer report"));
+
+        let kinds = [
+            FailureKind::LoaderMissing,
+            FailureKind::NoDriver,
+            FailureKind::DriverLibraryMissing,
+            FailureKind::PortabilityRequired,
+            FailureKind::DeviceNodesUnavailable,
+            FailureKind::NoDevices,
+            FailureKind::Other,
+        ];
+        let mut codes = kinds.map(FailureKind::exit_code).to_vec();
+        codes.sort_unstable();
+        codes.dedup();
+        assert_eq!(codes.len(), kinds.len());
+    }
+}
@@ -0,0 +1,480 @@
+use ash::{Instance, vk};
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute, queue, terminal,
+};
+use std::{
+    error::Error,
+    io::{self, Write},
+};
+
+use crate::details::DeviceDetails;
+use crate::device::Device;
+use crate::{BOLD, DIM, EMPTY, RESET, format_bytes, get_device_info};
+
+const REVERSE: &str = "\x1B[7m";
+const DEVICE_LIST_WIDTH: usize = 28;
+const HELP: &str = "←/→ tab  Tab device  ↑/↓ PgUp/PgDn scroll  / search  q quit";
+
+/// The categories shown as tabs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tab {
+    Overview,
+    Extensions,
+    Features,
+    Limits,
+    Formats,
+    Queues,
+    Memory,
+}
+
+impl Tab {
+    const ALL: [Tab; 7] = [
+        Tab::Overview,
+        Tab::Extensions,
+        Tab::Features,
+        Tab::Limits,
+        Tab::Formats,
+        Tab::Queues,
+        Tab::Memory,
+    ];
+
+    const fn name(&self) -> &'static str {
+        match self {
+            Tab::Overview => "Overview",
+            Tab::Extensions => "Extensions",
+            Tab::Features => "Features",
+            Tab::Limits => "Limits",
+            Tab::Formats => "Formats",
+            Tab::Queues => "Queues",
+            Tab::Memory => "Memory",
+        }
+    }
+}
+
+/// Returns the content of a tab as entries of one or more lines.
+/// Searching keeps or drops whole entries.
+fn tab_entries(device: &Device, details: &DeviceDetails, tab: Tab) -> Vec<Vec<String>> {
+    match tab {
+        Tab::Overview => get_device_info(device, EMPTY, false)
+            .into_iter()
+            .map(|line| vec![line.trim_start().to_string()])
+            .collect(),
+        Tab::Extensions => details
+            .extensions
+            .iter()
+            .map(|extension| {
+                vec![format!(
+                    "{:<56} rev {}",
+                    extension.name, extension.spec_version
+                )]
+            })
+            .collect(),
+        Tab::Features => details
+            .features
+            .iter()
+            .flat_map(|group| {
+                group.features.iter().map(|(name, supported)| {
+                    vec![format!("{:<56} {:<6} {}", name, supported, group.name)]
+                })
+            })
+            .collect(),
+        Tab::Limits => details
+            .properties
+            .iter()
+            .flat_map(|group| {
+                group.properties.iter().map(|(name, value)| {
+                    vec![format!(
+                        "{:<48} {:<24} {}",
+                        name,
+                        value.to_string(),
+                        group.name
+                    )]
+                })
+            })
+            .collect(),
+        Tab::Formats => details
+            .formats
+            .iter()
+            .map(|support| {
+                vec![
+                    format!("{:?}", support.format),
+                    format!("  linear:  {:?}", support.linear_tiling),
+                    format!("  optimal: {:?}", support.optimal_tiling),
+                    format!("  buffer:  {:?}", support.buffer),
+                ]
+            })
+            .collect(),
+        Tab::Queues => details
+            .queue_families
+            .iter()
+            .enumerate()
+            .map(|(index, family)| {
+                let [width, height, depth] = family.min_image_transfer_granularity;
+                vec![
+                    format!("Queue Family {}", index),
+                    format!("  flags: {:?}", family.flags),
+                    format!("  count: {}", family.count),
+                    format!("  timestamp bits: {}", family.timestamp_valid_bits),
+                    format!("  image transfer granularity: {width}x{height}x{depth}"),
+                ]
+            })
+            .collect(),
+        Tab::Memory => {
+            let heaps = details
+                .memory_heaps
+                .iter()
+                .enumerate()
+                .map(|(index, heap)| {
+                    vec![
+                        format!("Heap {}: {}", index, format_bytes(heap.size)),
+                        format!("  flags: {:?}", heap.flags),
+                    ]
+                });
+            let types = details
+                .memory_types
+                .iter()
+                .enumerate()
+                .map(|(index, memory_type)| {
+                    vec![
+                        format!("Type {}: heap {}", index, memory_type.heap_index),
+                        format!("  flags: {:?}", memory_type.flags),
+                    ]
+                });
+            heaps.chain(types).collect()
+        }
+    }
+}
+
+/// Flattens the entries that contain `query`, ignoring case.
+fn filter_entries(entries: Vec<Vec<String>>, query: &str) -> Vec<String> {
+    let query = query.to_lowercase();
+    entries
+        .into_iter()
+        .filter(|entry| {
+            query.is_empty()
+                || entry
+                    .iter()
+                    .any(|line| line.to_lowercase().contains(&query))
+        })
+        .flatten()
+        .collect()
+}
+
+/// Pads or cuts `text` to exactly `width` columns.
+fn fit(text: &str, width: usize) -> String {
+    let mut fitted = text.chars().take(width).collect::<String>();
+    let len = fitted.chars().count();
+    fitted.extend(std::iter::repeat_n(' ', width - len));
+    fitted
+}
+
+struct App {
+    devices: Vec<(Device, DeviceDetails)>,
+    selected: usize,
+    tab: usize,
+    scroll: usize,
+    query: String,
+    searching: bool,
+}
+
+impl App {
+    fn new(devices: Vec<(Device, DeviceDetails)>) -> Self {
+        Self {
+            devices,
+            selected: 0,
+            tab: 0,
+            scroll: 0,
+            query: String::new(),
+            searching: false,
+        }
+    }
+
+    fn lines(&self) -> Vec<String> {
+        let Some((device, details)) = self.devices.get(self.selected) else {
+            return vec!["No Vulkan devices found.".to_string()];
+        };
+        filter_entries(
+            tab_entries(device, details, Tab::ALL[self.tab]),
+            &self.query,
+        )
+    }
+
+    fn accent(&self) -> &'static str {
+        self.devices
+            .get(self.selected)
+            .map(|(device, _)| device.vendor.get_alternative_style()[0])
+            .unwrap_or(BOLD)
+    }
+
+    /// Applies a key press, returning `false` once the user quits.
+    fn handle_key(&mut self, key: KeyEvent, page: usize) -> bool {
+        // Raw mode swallows SIGINT, so Ctrl-C has to quit from any state.
+        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            return false;
+        }
+        if self.searching {
+            match key.code {
+                KeyCode::Enter => self.searching = false,
+                KeyCode::Esc => {
+                    self.searching = false;
+                    self.query.clear();
+                }
+                KeyCode::Backspace => {
+                    self.query.pop();
+                }
+                KeyCode::Char(c) => self.query.push(c),
+                _ => {}
+            }
+            self.scroll = 0;
+            return true;
+        }
+
+        let tab_count = Tab::ALL.len();
+        let device_count = self.devices.len().max(1);
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            // Scrolling keeps the position, everything else starts at the top.
+            KeyCode::Down | KeyCode::Char('j') => self.scroll += 1,
+            KeyCode::Up | KeyCode::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::PageDown => self.scroll += page,
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(page),
+            KeyCode::Home => self.scroll = 0,
+            KeyCode::End => self.scroll = usize::MAX,
+            code => {
+                match code {
+                    KeyCode::Char('/') => {
+                        self.searching = true;
+                        self.query.clear();
+                    }
+                    KeyCode::Right | KeyCode::Char('l') => self.tab = (self.tab + 1) % tab_count,
+                    KeyCode::Left | KeyCode::Char('h') => {
+                        self.tab = (self.tab + tab_count - 1) % tab_count
+                    }
+                    KeyCode::Tab => self.selected = (self.selected + 1) % device_count,
+                    KeyCode::BackTab => {
+                        self.selected = (self.selected + device_count - 1) % device_count
+                    }
+                    KeyCode::Char(c @ '1'..='7') => self.tab = c as usize - '1' as usize,
+                    _ => return true,
+                }
+                self.scroll = 0;
+            }
+        }
+        true
+    }
+
+    /// Draws the whole screen as `height` lines of `width` columns.
+    fn render(&mut self, width: usize, height: usize) -> Vec<String> {
+        let accent = self.accent();
+        let content_width = width.saturating_sub(DEVICE_LIST_WIDTH + 1);
+        // Tab bar and status line take one row each.
+        let body_height = height.saturating_sub(2);
+
+        let lines = self.lines();
+        self.scroll = self.scroll.min(lines.len().saturating_sub(body_height));
+
+        let mut tab_bar = String::new();
+        let mut tab_bar_width = 0;
+        for (index, tab) in Tab::ALL.iter().enumerate() {
+            let label = format!(" {} {} ", index + 1, tab.name());
+            if tab_bar_width + label.chars().count() > content_width {
+                break;
+            }
+            tab_bar_width += label.chars().count();
+            if index == self.tab {
+                tab_bar.push_str(&format!("{}{}{}{}", accent, REVERSE, label, RESET));
+            } else {
+                tab_bar.push_str(&label);
+            }
+        }
+
+        let mut screen = Vec::with_capacity(height);
+        for row in 0..height {
+            let list = match row.checked_sub(1).and_then(|index| self.devices.get(index)) {
+                Some((device, _)) => {
+                    let name = fit(&format!(" {}", device.device_name), DEVICE_LIST_WIDTH);
+                    if row - 1 == self.selected {
+                        format!(
+                            "{}{}{}{}",
+                            device.vendor.get_alternative_style()[0],
+                            REVERSE,
+                            name,
+                            RESET
+                        )
+                    } else {
+                        name
+                    }
+                }
+                None if row == 0 => {
+                    format!("{}{}{}", BOLD, fit(" Devices", DEVICE_LIST_WIDTH), RESET)
+                }
+                None => fit("", DEVICE_LIST_WIDTH),
+            };
+
+            let content = if row == 0 {
+                tab_bar.clone()
+            } else if row + 1 == height {
+                let status = if self.searching || !self.query.is_empty() {
+                    format!("/{}", self.query)
+                } else {
+                    HELP.to_string()
+                };
+                format!("{}{}{}", DIM, fit(&status, content_width), RESET)
+            } else {
+                lines
+                    .get(self.scroll + row - 1)
+                    .map(|line| fit(line, content_width))
+                    .unwrap_or_default()
+            };
+            screen.push(format!("{}{}│{}{}", list, DIM, RESET, content));
+        }
+        screen
+    }
+}
+
+/// Restores the terminal when dropped, even if drawing fails.
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(Self)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Runs the interactive browser until the user quits.
+pub(crate) fn run(
+    instance: &Instance,
+    instance_version: u32,
+    device_handles: &[vk::PhysicalDevice],
+) -> Result<(), Box<dyn Error>> {
+    let devices = device_handles
+        .iter()
+        .map(|&handle| {
+            (
+                Device::new(instance, instance_version, handle),
+                DeviceDetails::query(instance, instance_version, handle),
+            )
+        })
+        .collect();
+    let mut app = App::new(devices);
+
+    let _guard = TerminalGuard::enter()?;
+    let mut stdout = io::stdout();
+    loop {
+        let (width, height) = terminal::size()?;
+        for (row, line) in app
+            .render(width as usize, height as usize)
+            .iter()
+            .enumerate()
+        {
+            queue!(stdout, cursor::MoveTo(0, row as u16))?;
+            write!(stdout, "{}", line)?;
+        }
+        stdout.flush()?;
+
+        if let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+            && !app.handle_key(key, (height as usize).saturating_sub(2))
+        {
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::details::{Extension, PropertyGroup, PropertyValue};
+
+    fn app() -> App {
+        let details = DeviceDetails {
+            extensions: (0..40)
+                .map(|index| Extension {
+                    name: format!("VK_EXT_test_{index}"),
+                    spec_version: 1,
+                })
+                .chain([Extension {
+                    name: "VK_KHR_swapchain".to_string(),
+                    spec_version: 70,
+                }])
+                .collect(),
+            properties: vec![PropertyGroup {
+                name: "VkPhysicalDeviceLimits".to_string(),
+                properties: vec![(
+                    "maxImageDimension2D".to_string(),
+                    PropertyValue::Unsigned(16384),
+                )],
+            }],
+            ..Default::default()
+        };
+        App::new(vec![(crate::tests::dummy_physical_device(), details)])
+    }
+
+    fn press(app: &mut App, code: KeyCode) -> bool {
+        app.handle_key(KeyEvent::new(code, KeyModifiers::NONE), 10)
+    }
+
+    #[test]
+    fn test_tabs_and_search() {
+        let mut app = app();
+        assert!(app.lines()[0].contains("TestDevice"));
+
+        press(&mut app, KeyCode::Right);
+        assert_eq!(app.lines().len(), 41);
+        press(&mut app, KeyCode::Char('/'));
+        for c in "swap".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.lines().len(), 1);
+        assert!(app.lines()[0].contains("VK_KHR_swapchain") && app.lines()[0].contains("rev 70"));
+
+        press(&mut app, KeyCode::Char('4'));
+        press(&mut app, KeyCode::Char('/'));
+        press(&mut app, KeyCode::Esc);
+        assert!(app.lines()[0].contains("16384"));
+        assert!(!press(&mut app, KeyCode::Char('q')));
+    }
+
+    #[test]
+    fn test_ctrl_c_quits_while_searching() {
+        let mut app = app();
+        press(&mut app, KeyCode::Char('/'));
+        assert!(app.searching);
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert!(!app.handle_key(ctrl_c, 10));
+        assert!(app.query.is_empty());
+    }
+
+    #[test]
+    fn test_render_clamps_scroll() {
+        let mut app = app();
+        press(&mut app, KeyCode::Char('2'));
+        press(&mut app, KeyCode::End);
+        let screen = app.render(100, 12);
+        assert_eq!(screen.len(), 12);
+        // 41 extensions with 10 visible rows.
+        assert_eq!(app.scroll, 31);
+        assert!(screen[0].contains("Extensions"));
+        assert!(screen[1].contains("TestDevice"));
+        assert!(screen[10].contains("VK_KHR_swapchain"));
+        assert!(screen[11].contains("quit"));
+    }
+
+    #[test]
+    fn test_fit() {
+        assert_eq!(fit("abc", 5), "abc  ");
+        assert_eq!(fit("abcdef", 3), "abc");
+    }
+}
@@ -0,0 +1,292 @@
+use ash::{Entry, Instance, ext::debug_utils, vk};
+use std::{
+    ffi::{CStr, c_void},
+    fmt,
+    marker::PhantomData,
+    sync::Mutex,
+};
+
+use crate::ascii_art::{BRIGHT_RED, BRIGHT_YELLOW};
+use crate::{ALIGNMENT, BOLD, EMPTY, RESET};
+
+pub const VALIDATION_LAYER: &CStr = c"VK_LAYER_KHRONOS_validation";
+
+/// Exit code when validation reported errors; see [`ValidationFailed`].
+pub const VALIDATION_EXIT_CODE: u8 = 10;
+
+/// How severe a reported message is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A message received through `VK_EXT_debug_utils`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationMessage {
+    pub severity: Severity,
+    /// The VUID or other message identifier, when there is one.
+    pub id: Option<String>,
+    pub message: String,
+}
+
+/// Which parts of the self check the installed loader supports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValidationSupport {
+    pub layer: bool,
+    pub debug_utils: bool,
+}
+
+impl ValidationSupport {
+    pub fn detect(entry: &Entry) -> Self {
+        let has_extension = |layer: Option<&CStr>| {
+            unsafe { entry.enumerate_instance_extension_properties(layer) }
+                .unwrap_or_default()
+                .iter()
+                .any(|extension| {
+                    extension.extension_name_as_c_str() == Ok(vk::EXT_DEBUG_UTILS_NAME)
+                })
+        };
+        let layer = unsafe { entry.enumerate_instance_layer_properties() }
+            .unwrap_or_default()
+            .iter()
+            .any(|layer| layer.layer_name_as_c_str() == Ok(VALIDATION_LAYER));
+
+        Self {
+            layer,
+            // The validation layer provides the extension when the loader
+            // does not.
+            debug_utils: has_extension(None) || (layer && has_extension(Some(VALIDATION_LAYER))),
+        }
+    }
+}
+
+/// Collects the messages of a debug utils messenger.
+#[derive(Debug, Default)]
+pub(crate) struct Collector {
+    messages: Mutex<Vec<ValidationMessage>>,
+}
+
+impl Collector {
+    /// Messenger settings that report warnings and errors into this
+    /// collector. They borrow the collector, and so does every [`Messenger`]
+    /// created with them.
+    pub(crate) fn messenger_create_info(&self) -> vk::DebugUtilsMessengerCreateInfoEXT<'_> {
+        vk::DebugUtilsMessengerCreateInfoEXT::default()
+            .message_severity(
+                vk::DebugUtilsMessageSeverityFlagsEXT::WARNING
+                    | vk::DebugUtilsMessageSeverityFlagsEXT::ERROR,
+            )
+            .message_type(
+                vk::DebugUtilsMessageTypeFlagsEXT::GENERAL
+                    | vk::DebugUtilsMessageTypeFlagsEXT::VALIDATION
+                    | vk::DebugUtilsMessageTypeFlagsEXT::PERFORMANCE,
+            )
+            .pfn_user_callback(Some(collect_message))
+            .user_data(self as *const Self as *mut c_void)
+    }
+
+    pub(crate) fn take(&self) -> Vec<ValidationMessage> {
+        std::mem::take(
+            &mut self
+                .messages
+                .lock()
+                .unwrap_or_else(|error| error.into_inner()),
+        )
+    }
+}
+
+unsafe extern "system" fn collect_message(
+    severity: vk::DebugUtilsMessageSeverityFlagsEXT,
+    _types: vk::DebugUtilsMessageTypeFlagsEXT,
+    callback_data: *const vk::DebugUtilsMessengerCallbackDataEXT<'_>,
+    user_data: *mut c_void,
+) -> vk::Bool32 {
+    // SAFETY: `user_data` is the collector set in `messenger_create_info`,
+    // and the loader passes valid callback data for the call's duration.
+    let (collector, data) = unsafe { (&*(user_data as *const Collector), &*callback_data) };
+    let text = |text: Option<&CStr>| text.map(|text| text.to_string_lossy().into_owned());
+    let message = ValidationMessage {
+        severity: if severity.contains(vk::DebugUtilsMessageSeverityFlagsEXT::ERROR) {
+            Severity::Error
+        } else {
+            Severity::Warning
+        },
+        id: text(unsafe { data.message_id_name_as_c_str() }),
+        message: text(unsafe { data.message_as_c_str() }).unwrap_or_default(),
+    };
+    if let Ok(mut messages) = collector.messages.lock() {
+        messages.push(message);
+    }
+    // Never abort the call that triggered the message.
+    vk::FALSE
+}
+
+/// A messenger registered on an instance for the duration of a run. It
+/// cannot outlive the [`Collector`] its callback writes to.
+pub(crate) struct Messenger<'a> {
+    loader: debug_utils::Instance,
+    messenger: vk::DebugUtilsMessengerEXT,
+    _collector: PhantomData<&'a Collector>,
+}
+
+impl<'a> Messenger<'a> {
+    pub(crate) fn new(
+        entry: &Entry,
+        instance: &Instance,
+        create_info: &vk::DebugUtilsMessengerCreateInfoEXT<'a>,
+    ) -> Option<Self> {
+        let loader = debug_utils::Instance::new(entry, instance);
+        let messenger = unsafe { loader.create_debug_utils_messenger(create_info, None) }.ok()?;
+        Some(Self {
+            loader,
+            messenger,
+            _collector: PhantomData,
+        })
+    }
+
+    /// Must be called before the instance is destroyed.
+    pub(crate) fn destroy(self) {
+        unsafe {
+            self.loader
+                .destroy_debug_utils_messenger(self.messenger, None);
+        }
+    }
+}
+
+/// Returns the validation section printed after the devices.
+pub(crate) fn report(
+    messages: &[ValidationMessage],
+    support: ValidationSupport,
+    use_ansi: bool,
+) -> Vec<String> {
+    let (bold, reset) = if use_ansi {
+        (BOLD, RESET)
+    } else {
+        (EMPTY, EMPTY)
+    };
+    let count = |severity| {
+        messages
+            .iter()
+            .filter(|message| message.severity == severity)
+            .count()
+    };
+
+    let mut lines = vec![format!(
+        "{}Validation{}: {} errors, {} warnings",
+        bold,
+        reset,
+        count(Severity::Error),
+        count(Severity::Warning)
+    )];
+    if !support.layer {
+        lines.push(format!(
+            "{}{} is not installed; only loader messages were checked",
+            ALIGNMENT,
+            VALIDATION_LAYER.to_string_lossy()
+        ));
+    }
+    if !support.debug_utils {
+        lines.push(format!(
+            "{}VK_EXT_debug_utils is unavailable; no messages could be received",
+            ALIGNMENT
+        ));
+    }
+
+    let mut messages = messages.iter().collect::<Vec<_>>();
+    messages.sort_by_key(|message| message.severity);
+    for message in messages {
+        let (color, label) = match message.severity {
+            Severity::Error => (BRIGHT_RED, "error"),
+            Severity::Warning => (BRIGHT_YELLOW, "warning"),
+        };
+        let color = if use_ansi { color } else { EMPTY };
+        let id = message
+            .id
+            .as_deref()
+            .map(|id| format!(" [{}]", id))
+            .unwrap_or_default();
+        lines.push(format!(
+            "{}{}{}{}{}: {}",
+            ALIGNMENT, color, label, reset, id, message.message
+        ));
+    }
+    lines
+}
+
+/// Returned when validation reported errors, so the run fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValidationFailed {
+    pub errors: usize,
+}
+
+impl fmt::Display for ValidationFailed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "vkfetch-rs: validation reported {} errors", self.errors)
+    }
+}
+
+impl std::error::Error for ValidationFailed {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collects_messages() {
+        let collector = Collector::default();
+        let create_info = collector.messenger_create_info();
+        let data = vk::DebugUtilsMessengerCallbackDataEXT::default()
+            .message_id_name(c"VUID-vkGetPhysicalDeviceProperties2-pNext-pNext")
+            .message(c"unknown structure in pNext chain");
+
+        let callback = create_info.pfn_user_callback.unwrap();
+        let result = unsafe {
+            callback(
+                vk::DebugUtilsMessageSeverityFlagsEXT::ERROR,
+                vk::DebugUtilsMessageTypeFlagsEXT::VALIDATION,
+                &data,
+                create_info.p_user_data,
+            )
+        };
+        assert_eq!(result, vk::FALSE);
+        assert_eq!(
+            collector.take(),
+            [ValidationMessage {
+                severity: Severity::Error,
+                id: Some("VUID-vkGetPhysicalDeviceProperties2-pNext-pNext".to_string()),
+                message: "unknown structure in pNext chain".to_string(),
+            }]
+        );
+        assert!(collector.take().is_empty());
+    }
+
+    #[test]
+    fn test_report() {
+        let messages = [
+            ValidationMessage {
+                severity: Severity::Warning,
+                id: None,
+                message: "loader warning".to_string(),
+            },
+            ValidationMessage {
+                severity: Severity::Error,
+                id: Some("VUID-1".to_string()),
+                message: "bad call".to_string(),
+            },
+        ];
+        let support = ValidationSupport {
+            layer: false,
+            debug_utils: true,
+        };
+        assert_eq!(
+            report(&messages, support, false),
+            [
+                "Validation: 1 errors, 1 warnings",
+                "    VK_LAYER_KHRONOS_validation is not installed; only loader messages were checked",
+                "    error [VUID-1]: bad call",
+                "    warning: loader warning",
+            ]
+        );
+    }
+}
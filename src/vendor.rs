@@ -1,17 +1,43 @@
 use crate::ascii_art::*;
 
-/// Represents a GPU vendor.
-#[allow(clippy::upper_case_acronyms)]
-#[repr(u32)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum Vendor {
+/// Declares [`Vendor`] and [`Vendor::ALL`] from one table of vendor IDs, so
+/// neither can miss a vendor.
+macro_rules! vendors {
+    ($($vendor:ident = $id:literal,)*) => {
+        /// Represents a GPU vendor.
+        #[allow(clippy::upper_case_acronyms)]
+        #[repr(u32)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum Vendor {
+            $($vendor = $id,)*
+        }
+
+        impl Vendor {
+            /// Every known vendor; the enum discriminants are the vendor IDs.
+            pub const ALL: &'static [Vendor] = &[$(Vendor::$vendor,)*];
+        }
+    };
+}
+
+vendors! {
+    Loongson = 0x0014,
     AMD = 0x1002,
     ImgTec = 0x1010,
+    Matrox = 0x102B,
     Apple = 0x106B,
     Nvidia = 0x10DE,
     ARM = 0x13B5,
     Microsoft = 0x1414,
+    Samsung = 0x144D,
+    MediaTek = 0x14C3,
+    Broadcom = 0x14E4,
+    VMware = 0x15AD,
+    Huawei = 0x19E5,
     Google = 0x1AE0,
+    RedHat = 0x1AF4,
+    Zhaoxin = 0x1D17,
+    MooreThreads = 0x1ED5,
+    S3 = 0x5333,
     Qualcomm = 0x5143,
     Intel = 0x8086,
     Unknown = 0xFFFF,
@@ -74,7 +100,18 @@ impl Vendor {
             | Vendor::Kazan
             | Vendor::Codeplay
             | Vendor::Pocl
-            | Vendor::MobileEye => VULKAN,
+            | Vendor::MobileEye
+            | Vendor::Loongson
+            | Vendor::Matrox
+            | Vendor::Samsung
+            | Vendor::MediaTek
+            | Vendor::Broadcom
+            | Vendor::VMware
+            | Vendor::Huawei
+            | Vendor::RedHat
+            | Vendor::Zhaoxin
+            | Vendor::MooreThreads
+            | Vendor::S3 => VULKAN,
         }
     }
 
@@ -103,6 +140,9 @@ impl Vendor {
             Vendor::Microsoft => MICROSOFT_STYLE,
             Vendor::Qualcomm | Vendor::QualcommPartner => QUALCOMM_STYLE,
             Vendor::Mesa => VULKAN_STYLE,
+            Vendor::Samsung | Vendor::Zhaoxin | Vendor::Matrox => VULKAN_BLUE_STYLE,
+            Vendor::MediaTek | Vendor::MooreThreads => VULKAN_YELLOW_STYLE,
+            Vendor::VMware => VULKAN_WHITE_STYLE,
             Vendor::Unknown
             | Vendor::ImgTec
             | Vendor::VIV
@@ -110,7 +150,12 @@ impl Vendor {
             | Vendor::Kazan
             | Vendor::Codeplay
             | Vendor::Pocl
-            | Vendor::MobileEye => VULKAN_STYLE,
+            | Vendor::MobileEye
+            | Vendor::Loongson
+            | Vendor::Broadcom
+            | Vendor::Huawei
+            | Vendor::RedHat
+            | Vendor::S3 => VULKAN_STYLE,
         }
     }
 
@@ -135,59 +180,40 @@ impl Vendor {
             Vendor::VIV => "VIV",
             Vendor::VSI => "VSI",
             Vendor::Microsoft => "Microsoft",
+            Vendor::Loongson => "Loongson",
+            Vendor::Matrox => "Matrox",
+            Vendor::Samsung => "Samsung",
+            Vendor::MediaTek => "MediaTek",
+            Vendor::Broadcom => "Broadcom",
+            Vendor::VMware => "VMware",
+            Vendor::Huawei => "Huawei",
+            Vendor::RedHat => "Red Hat",
+            Vendor::Zhaoxin => "Zhaoxin",
+            Vendor::MooreThreads => "Moore Threads",
+            Vendor::S3 => "S3 Graphics",
         }
     }
 
     /// Constructs a Vendor from a vendor ID, if recognized.
-    pub fn from_vendor_id(id: u32) -> Option<Self> {
-        match id {
-            0x1002 => Some(Vendor::AMD),
-            0x1010 => Some(Vendor::ImgTec),
-            0x106B => Some(Vendor::Apple),
-            0x10DE => Some(Vendor::Nvidia),
-            0x13B5 => Some(Vendor::ARM),
-            0x1414 => Some(Vendor::Microsoft),
-            0x1AE0 => Some(Vendor::Google),
-            0x5143 => Some(Vendor::Qualcomm),
-            0x8086 => Some(Vendor::Intel),
-            0xFFFF => Some(Vendor::Unknown),
-            0x10001 => Some(Vendor::VIV),
-            0x10002 => Some(Vendor::VSI),
-            0x10003 => Some(Vendor::Kazan),
-            0x10004 => Some(Vendor::Codeplay),
-            0x10005 => Some(Vendor::Mesa),
-            0x10006 => Some(Vendor::Pocl),
-            0x10007 => Some(Vendor::MobileEye),
-            0x4D4F4351 => Some(Vendor::QualcommPartner),
-            _ => None,
+    pub const fn from_vendor_id(id: u32) -> Option<Self> {
+        let mut index = 0;
+        while index < Self::ALL.len() {
+            if Self::ALL[index] as u32 == id {
+                return Some(Self::ALL[index]);
+            }
+            index += 1;
         }
+        None
     }
 
     pub const fn from_vendor_id_or_unknown(id: u32) -> Self {
-        match id {
-            0x1002 => Vendor::AMD,
-            0x1010 => Vendor::ImgTec,
-            0x106B => Vendor::Apple,
-            0x10DE => Vendor::Nvidia,
-            0x13B5 => Vendor::ARM,
-            0x1414 => Vendor::Microsoft,
-            0x1AE0 => Vendor::Google,
-            0x5143 => Vendor::Qualcomm,
-            0x8086 => Vendor::Intel,
-            0xFFFF => Vendor::Unknown,
-            0x10001 => Vendor::VIV,
-            0x10002 => Vendor::VSI,
-            0x10003 => Vendor::Kazan,
-            0x10004 => Vendor::Codeplay,
-            0x10005 => Vendor::Mesa,
-            0x10006 => Vendor::Pocl,
-            0x10007 => Vendor::MobileEye,
-            0x4D4F4351 => Vendor::QualcommPartner,
-            _ => Vendor::Unknown,
+        match Self::from_vendor_id(id) {
+            Some(vendor) => vendor,
+            None => Vendor::Unknown,
         }
     }
 
-    pub const fn get_alternative_style(&self) -> [&str; LUT_SIZE] {
+    pub const fn get_alternative_style(&self) -> [&'static str; LUT_SIZE] {
         match self {
             Vendor::AMD => AMD_STYLE_ALT,
             Vendor::Apple => APPLE_STYLE_ALT,
@@ -198,6 +224,9 @@ impl Vendor {
             Vendor::Microsoft => MICROSOFT_STYLE_ALT,
             Vendor::Qualcomm | Vendor::QualcommPartner => QUALCOMM_STYLE_ALT,
             Vendor::Mesa => VULKAN_STYLE_ALT,
+            Vendor::Samsung | Vendor::Zhaoxin | Vendor::Matrox => VULKAN_BLUE_STYLE_ALT,
+            Vendor::MediaTek | Vendor::MooreThreads => VULKAN_YELLOW_STYLE_ALT,
+            Vendor::VMware => VULKAN_WHITE_STYLE_ALT,
             Vendor::Unknown
             | Vendor::ImgTec
             | Vendor::VIV
@@ -205,7 +234,12 @@ impl Vendor {
             | Vendor::Kazan
             | Vendor::Codeplay
             | Vendor::Pocl
-            | Vendor::MobileEye => VULKAN_STYLE_ALT,
+            | Vendor::MobileEye
+            | Vendor::Loongson
+            | Vendor::Broadcom
+            | Vendor::Huawei
+            | Vendor::RedHat
+            | Vendor::S3 => VULKAN_STYLE_ALT,
         }
     }
 }
@@ -236,10 +270,22 @@ mod tests {
             Vendor::from_vendor_id(0x4D4F4351),
             Some(Vendor::QualcommPartner)
         );
+        assert_eq!(Vendor::from_vendor_id(0x14E4), Some(Vendor::Broadcom));
+        assert_eq!(Vendor::from_vendor_id(0x1ED5), Some(Vendor::MooreThreads));
+        assert_eq!(Vendor::from_vendor_id(0x1AF4), Some(Vendor::RedHat));
         assert_eq!(Vendor::from_vendor_id(0x9999), None);
         assert_eq!(Vendor::from_vendor_id_or_unknown(0x9999), Vendor::Unknown);
     }
 
+    #[test]
+    fn test_all_vendors_round_trip() {
+        for vendor in Vendor::ALL {
+            assert_eq!(Vendor::from_vendor_id(u32::from(*vendor)), Some(*vendor));
+            assert_eq!(Vendor::from_vendor_id_or_unknown(*vendor as u32), *vendor);
+            assert!(!vendor.get_ascii_art_with_ansi(false).is_empty());
+        }
+    }
+
     #[test]
     fn test_name() {
         assert_eq!(Vendor::AMD.name(), "AMD");
@@ -0,0 +1,163 @@
+use ash::vk;
+
+/// Drivers that forward Vulkan to a host GPU, another graphics API, or the CPU.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayeredDriverKind {
+    Venus,
+    Dozen,
+    MoltenVK,
+    SwiftShader,
+    Lavapipe,
+}
+
+/// A recognized layered driver and, if the driver exposes it, the name of
+/// the GPU that ultimately executes the work.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayeredDriver {
+    pub kind: LayeredDriverKind,
+    pub host_gpu: Option<String>,
+}
+
+impl LayeredDriverKind {
+    /// Returns a human-readable name.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            LayeredDriverKind::Venus => "Venus",
+            LayeredDriverKind::Dozen => "Dozen",
+            LayeredDriverKind::MoltenVK => "MoltenVK",
+            LayeredDriverKind::SwiftShader => "SwiftShader",
+            LayeredDriverKind::Lavapipe => "lavapipe",
+        }
+    }
+
+    /// Returns the API or transport the driver is layered on top of.
+    pub const fn backend(&self) -> &'static str {
+        match self {
+            LayeredDriverKind::Venus => "host Vulkan over virtio-gpu",
+            LayeredDriverKind::Dozen => "Direct3D 12",
+            LayeredDriverKind::MoltenVK => "Metal",
+            LayeredDriverKind::SwiftShader => "CPU (Subzero/LLVM)",
+            LayeredDriverKind::Lavapipe => "CPU (LLVM)",
+        }
+    }
+}
+
+impl LayeredDriver {
+    /// Recognizes a layered driver from its `driverID`, falling back to the
+    /// driver and device names for implementations that predate `driverID`.
+    pub fn detect(driver_id: vk::DriverId, driver_name: &str, device_name: &str) -> Option<Self> {
+        let kind = match driver_id {
+            vk::DriverId::MESA_VENUS => LayeredDriverKind::Venus,
+            vk::DriverId::MESA_DOZEN => LayeredDriverKind::Dozen,
+            vk::DriverId::MOLTENVK => LayeredDriverKind::MoltenVK,
+            vk::DriverId::GOOGLE_SWIFTSHADER => LayeredDriverKind::SwiftShader,
+            vk::DriverId::MESA_LLVMPIPE => LayeredDriverKind::Lavapipe,
+            _ => kind_from_names(driver_name, device_name)?,
+        };
+
+        let host_gpu = match kind {
+            // "Virtio-GPU Venus (NVIDIA GeForce RTX 3080)"
+            // "Microsoft Direct3D12 (NVIDIA GeForce RTX 3080)"
+            LayeredDriverKind::Venus | LayeredDriverKind::Dozen => parenthesized(device_name),
+            // MoltenVK reports the Metal device name as-is.
+            LayeredDriverKind::MoltenVK => Some(device_name.to_string()),
+            LayeredDriverKind::SwiftShader | LayeredDriverKind::Lavapipe => None,
+        };
+
+        Some(Self { kind, host_gpu })
+    }
+}
+
+fn kind_from_names(driver_name: &str, device_name: &str) -> Option<LayeredDriverKind> {
+    let driver_name = driver_name.to_ascii_lowercase();
+    let device_name = device_name.to_ascii_lowercase();
+
+    if driver_name == "venus" || device_name.starts_with("virtio-gpu venus") {
+        Some(LayeredDriverKind::Venus)
+    } else if driver_name == "dozen" || device_name.starts_with("microsoft direct3d12") {
+        Some(LayeredDriverKind::Dozen)
+    } else if driver_name == "moltenvk" {
+        Some(LayeredDriverKind::MoltenVK)
+    } else if driver_name.contains("swiftshader") || device_name.starts_with("swiftshader") {
+        Some(LayeredDriverKind::SwiftShader)
+    } else if driver_name == "llvmpipe" || device_name.starts_with("llvmpipe") {
+        Some(LayeredDriverKind::Lavapipe)
+    } else {
+        None
+    }
+}
+
+/// Returns the text between the first `(` and the last `)`.
+fn parenthesized(name: &str) -> Option<String> {
+    let start = name.find('(')?;
+    let end = name.rfind(')')?;
+    let inner = name.get(start + 1..end)?.trim();
+    (!inner.is_empty()).then(|| inner.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_venus_host_gpu() {
+        let driver = LayeredDriver::detect(
+            vk::DriverId::MESA_VENUS,
+            "venus",
+            "Virtio-GPU Venus (Intel(R) UHD Graphics 620 (KBL GT2))",
+        )
+        .unwrap();
+        assert_eq!(driver.kind, LayeredDriverKind::Venus);
+        assert_eq!(
+            driver.host_gpu.as_deref(),
+            Some("Intel(R) UHD Graphics 620 (KBL GT2)")
+        );
+    }
+
+    #[test]
+    fn test_dozen_host_gpu() {
+        let driver = LayeredDriver::detect(
+            vk::DriverId::MESA_DOZEN,
+            "Dozen",
+            "Microsoft Direct3D12 (NVIDIA GeForce RTX 3080)",
+        )
+        .unwrap();
+        assert_eq!(driver.kind.backend(), "Direct3D 12");
+        assert_eq!(driver.host_gpu.as_deref(), Some("NVIDIA GeForce RTX 3080"));
+    }
+
+    #[test]
+    fn test_moltenvk_host_gpu() {
+        let driver =
+            LayeredDriver::detect(vk::DriverId::MOLTENVK, "MoltenVK", "Apple M1 Pro").unwrap();
+        assert_eq!(driver.kind.backend(), "Metal");
+        assert_eq!(driver.host_gpu.as_deref(), Some("Apple M1 Pro"));
+    }
+
+    #[test]
+    fn test_cpu_drivers_have_no_host_gpu() {
+        let driver = LayeredDriver::detect(
+            vk::DriverId::MESA_LLVMPIPE,
+            "llvmpipe",
+            "llvmpipe (LLVM 15.0.7, 256 bits)",
+        )
+        .unwrap();
+        assert_eq!(driver.kind, LayeredDriverKind::Lavapipe);
+        assert_eq!(driver.host_gpu, None);
+    }
+
+    #[test]
+    fn test_name_fallback() {
+        let driver = LayeredDriver::detect(
+            vk::DriverId::from_raw(0),
+            "Unknown",
+            "SwiftShader Device (Subzero)",
+        )
+        .unwrap();
+        assert_eq!(driver.kind, LayeredDriverKind::SwiftShader);
+        assert_eq!(
+            LayeredDriver::detect(vk::DriverId::MESA_RADV, "radv", "AMD Radeon RX 6800"),
+            None
+        );
+    }
+}
@@ -0,0 +1,180 @@
+//! Vulkan 1.4 structures, which the `ash` release in use predates. The
+//! layouts follow the 1.4 headers and are only chained when both the device
+//! and the instance support at least [`API_VERSION_1_4`].
+
+use ash::vk;
+use std::{ffi::c_void, marker::PhantomData, ptr};
+
+pub const API_VERSION_1_4: u32 = vk::make_api_version(0, 1, 4, 0);
+
+const STRUCTURE_TYPE_PHYSICAL_DEVICE_VULKAN_1_4_FEATURES: vk::StructureType =
+    vk::StructureType::from_raw(55);
+const STRUCTURE_TYPE_PHYSICAL_DEVICE_VULKAN_1_4_PROPERTIES: vk::StructureType =
+    vk::StructureType::from_raw(56);
+
+/// `VkPhysicalDeviceVulkan14Features`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct PhysicalDeviceVulkan14Features<'a> {
+    pub s_type: vk::StructureType,
+    pub p_next: *mut c_void,
+    pub global_priority_query: vk::Bool32,
+    pub shader_subgroup_rotate: vk::Bool32,
+    pub shader_subgroup_rotate_clustered: vk::Bool32,
+    pub shader_float_controls2: vk::Bool32,
+    pub shader_expect_assume: vk::Bool32,
+    pub rectangular_lines: vk::Bool32,
+    pub bresenham_lines: vk::Bool32,
+    pub smooth_lines: vk::Bool32,
+    pub stippled_rectangular_lines: vk::Bool32,
+    pub stippled_bresenham_lines: vk::Bool32,
+    pub stippled_smooth_lines: vk::Bool32,
+    pub vertex_attribute_instance_rate_divisor: vk::Bool32,
+    pub vertex_attribute_instance_rate_zero_divisor: vk::Bool32,
+    pub index_type_uint8: vk::Bool32,
+    pub dynamic_rendering_local_read: vk::Bool32,
+    pub maintenance5: vk::Bool32,
+    pub maintenance6: vk::Bool32,
+    pub pipeline_protected_access: vk::Bool32,
+    pub pipeline_robustness: vk::Bool32,
+    pub host_image_copy: vk::Bool32,
+    pub push_descriptor: vk::Bool32,
+    pub _marker: PhantomData<&'a ()>,
+}
+
+impl Default for PhysicalDeviceVulkan14Features<'_> {
+    fn default() -> Self {
+        Self {
+            s_type: STRUCTURE_TYPE_PHYSICAL_DEVICE_VULKAN_1_4_FEATURES,
+            p_next: ptr::null_mut(),
+            global_priority_query: vk::FALSE,
+            shader_subgroup_rotate: vk::FALSE,
+            shader_subgroup_rotate_clustered: vk::FALSE,
+            shader_float_controls2: vk::FALSE,
+            shader_expect_assume: vk::FALSE,
+            rectangular_lines: vk::FALSE,
+            bresenham_lines: vk::FALSE,
+            smooth_lines: vk::FALSE,
+            stippled_rectangular_lines: vk::FALSE,
+            stippled_bresenham_lines: vk::FALSE,
+            stippled_smooth_lines: vk::FALSE,
+            vertex_attribute_instance_rate_divisor: vk::FALSE,
+            vertex_attribute_instance_rate_zero_divisor: vk::FALSE,
+            index_type_uint8: vk::FALSE,
+            dynamic_rendering_local_read: vk::FALSE,
+            maintenance5: vk::FALSE,
+            maintenance6: vk::FALSE,
+            pipeline_protected_access: vk::FALSE,
+            pipeline_robustness: vk::FALSE,
+            host_image_copy: vk::FALSE,
+            push_descriptor: vk::FALSE,
+            _marker: PhantomData,
+        }
+    }
+}
+
+// SAFETY: the structure starts with `sType` and `pNext` like every Vulkan
+// output structure and may extend `VkPhysicalDeviceFeatures2` per the spec.
+unsafe impl vk::ExtendsPhysicalDeviceFeatures2 for PhysicalDeviceVulkan14Features<'_> {}
+
+/// `VkPhysicalDeviceVulkan14Properties`. The copy layout arrays are left
+/// null, so the implementation only fills in their counts.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct PhysicalDeviceVulkan14Properties<'a> {
+    pub s_type: vk::StructureType,
+    pub p_next: *mut c_void,
+    pub line_sub_pixel_precision_bits: u32,
+    pub max_vertex_attrib_divisor: u32,
+    pub supports_non_zero_first_instance: vk::Bool32,
+    pub max_push_descriptors: u32,
+    pub dynamic_rendering_local_read_depth_stencil_attachments: vk::Bool32,
+    pub dynamic_rendering_local_read_multisampled_attachments: vk::Bool32,
+    pub early_fragment_multisample_coverage_after_sample_counting: vk::Bool32,
+    pub early_fragment_sample_mask_test_before_sample_counting: vk::Bool32,
+    pub depth_stencil_swizzle_one_support: vk::Bool32,
+    pub polygon_mode_point_size: vk::Bool32,
+    pub non_strict_single_pixel_wide_lines_use_parallelogram: vk::Bool32,
+    pub non_strict_wide_lines_use_parallelogram: vk::Bool32,
+    pub block_texel_view_compatible_multiple_layers: vk::Bool32,
+    pub max_combined_image_sampler_descriptor_count: u32,
+    pub fragment_shading_rate_clamp_combiner_inputs: vk::Bool32,
+    pub default_robustness_storage_buffers: vk::PipelineRobustnessBufferBehaviorEXT,
+    pub default_robustness_uniform_buffers: vk::PipelineRobustnessBufferBehaviorEXT,
+    pub default_robustness_vertex_inputs: vk::PipelineRobustnessBufferBehaviorEXT,
+    pub default_robustness_images: vk::PipelineRobustnessImageBehaviorEXT,
+    pub copy_src_layout_count: u32,
+    pub p_copy_src_layouts: *mut vk::ImageLayout,
+    pub copy_dst_layout_count: u32,
+    pub p_copy_dst_layouts: *mut vk::ImageLayout,
+    pub optimal_tiling_layout_uuid: [u8; vk::UUID_SIZE],
+    pub identical_memory_type_requirements: vk::Bool32,
+    pub _marker: PhantomData<&'a ()>,
+}
+
+impl Default for PhysicalDeviceVulkan14Properties<'_> {
+    fn default() -> Self {
+        Self {
+            s_type: STRUCTURE_TYPE_PHYSICAL_DEVICE_VULKAN_1_4_PROPERTIES,
+            p_next: ptr::null_mut(),
+            line_sub_pixel_precision_bits: 0,
+            max_vertex_attrib_divisor: 0,
+            supports_non_zero_first_instance: vk::FALSE,
+            max_push_descriptors: 0,
+            dynamic_rendering_local_read_depth_stencil_attachments: vk::FALSE,
+            dynamic_rendering_local_read_multisampled_attachments: vk::FALSE,
+            early_fragment_multisample_coverage_after_sample_counting: vk::FALSE,
+            early_fragment_sample_mask_test_before_sample_counting: vk::FALSE,
+            depth_stencil_swizzle_one_support: vk::FALSE,
+            polygon_mode_point_size: vk::FALSE,
+            non_strict_single_pixel_wide_lines_use_parallelogram: vk::FALSE,
+            non_strict_wide_lines_use_parallelogram: vk::FALSE,
+            block_texel_view_compatible_multiple_layers: vk::FALSE,
+            max_combined_image_sampler_descriptor_count: 0,
+            fragment_shading_rate_clamp_combiner_inputs: vk::FALSE,
+            default_robustness_storage_buffers: vk::PipelineRobustnessBufferBehaviorEXT::default(),
+            default_robustness_uniform_buffers: vk::PipelineRobustnessBufferBehaviorEXT::default(),
+            default_robustness_vertex_inputs: vk::PipelineRobustnessBufferBehaviorEXT::default(),
+            default_robustness_images: vk::PipelineRobustnessImageBehaviorEXT::default(),
+            copy_src_layout_count: 0,
+            p_copy_src_layouts: ptr::null_mut(),
+            copy_dst_layout_count: 0,
+            p_copy_dst_layouts: ptr::null_mut(),
+            optimal_tiling_layout_uuid: [0; vk::UUID_SIZE],
+            identical_memory_type_requirements: vk::FALSE,
+            _marker: PhantomData,
+        }
+    }
+}
+
+// SAFETY: as for the features above, for `VkPhysicalDeviceProperties2`.
+unsafe impl vk::ExtendsPhysicalDeviceProperties2 for PhysicalDeviceVulkan14Properties<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::mem::{offset_of, size_of};
+
+    #[test]
+    fn test_layout_matches_headers() {
+        // Sizes and offsets of the C structures on 64-bit targets.
+        #[cfg(target_pointer_width = "64")]
+        {
+            assert_eq!(size_of::<PhysicalDeviceVulkan14Features>(), 104);
+            assert_eq!(size_of::<PhysicalDeviceVulkan14Properties>(), 144);
+            assert_eq!(
+                offset_of!(PhysicalDeviceVulkan14Properties, p_copy_src_layouts),
+                96
+            );
+            assert_eq!(
+                offset_of!(PhysicalDeviceVulkan14Properties, optimal_tiling_layout_uuid),
+                120
+            );
+        }
+        assert_eq!(
+            PhysicalDeviceVulkan14Features::default().s_type.as_raw(),
+            55
+        );
+        assert_eq!(vk::api_version_minor(API_VERSION_1_4), 4);
+    }
+}
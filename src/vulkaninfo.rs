@@ -0,0 +1,401 @@
+//! Writes devices in the layout of `vulkaninfo --json`: a Vulkan Profiles
+//! file whose profiles describe the capabilities of each device, as read by
+//! the profiles tooling and gpuinfo.org.
+
+use ash::{Instance, vk};
+use serde_json::{Map, Value, json};
+use std::{
+    error::Error,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use crate::details::{DeviceDetails, PropertyGroup, PropertyValue, flag_names};
+use crate::device::decode_version_number;
+
+const SCHEMA: &str = "https://schema.khronos.org/vulkan/profiles-0.8-latest.json#";
+
+/// Members of `VkPhysicalDeviceProperties` that hold nested structures.
+const NESTED: [(&str, &str); 2] = [
+    ("VkPhysicalDeviceLimits", "limits"),
+    ("VkPhysicalDeviceSparseProperties", "sparseProperties"),
+];
+
+/// Writes the report of every device to `path`, or stdout for `-`.
+pub fn export_devices(
+    instance: &Instance,
+    instance_version: u32,
+    devices: &[vk::PhysicalDevice],
+    path: &Path,
+) -> Result<(), Box<dyn Error>> {
+    let details = devices
+        .iter()
+        .map(|&device| DeviceDetails::query(instance, instance_version, device))
+        .collect::<Vec<_>>();
+    let report = report(&details);
+
+    let mut output: Box<dyn Write> = if path == Path::new("-") {
+        Box::new(io::stdout())
+    } else {
+        Box::new(BufWriter::new(File::create(path)?))
+    };
+    serde_json::to_writer_pretty(&mut output, &report)?;
+    writeln!(output)?;
+    output.flush()?;
+    Ok(())
+}
+
+/// Builds the profiles file, with one capability and profile per device.
+pub fn report(devices: &[DeviceDetails]) -> Value {
+    let mut capabilities = Map::new();
+    let mut profiles = Map::new();
+    for (index, details) in devices.iter().enumerate() {
+        let capability = if devices.len() == 1 {
+            "device".to_string()
+        } else {
+            format!("device{index}")
+        };
+        let mut name = profile_name(details);
+        // Identical GPUs on the same driver would share a name.
+        if profiles.contains_key(&name) {
+            name = format!("{name}_{index}");
+        }
+        let api_version = match details.limit("apiVersion") {
+            Some(PropertyValue::Version(version)) => decode_version_number(*version),
+            _ => "1.0.0".to_string(),
+        };
+        profiles.insert(
+            name.clone(),
+            json!({
+                "version": 1,
+                "api-version": api_version,
+                "label": name,
+                "description": "Exported by vkfetch-rs",
+                "capabilities": [capability],
+            }),
+        );
+        capabilities.insert(capability, capabilities_json(details));
+    }
+    json!({
+        "$schema": SCHEMA,
+        "capabilities": capabilities,
+        "profiles": profiles,
+    })
+}
+
+/// Names the profile like vulkaninfo: `VP_VULKANINFO_` followed by the
+/// device name and driver version.
+fn profile_name(details: &DeviceDetails) -> String {
+    let text = |name| {
+        details
+            .limit(name)
+            .map(ToString::to_string)
+            .unwrap_or_default()
+    };
+    let name = format!(
+        "VP_VULKANINFO_{}_{}",
+        text("deviceName"),
+        text("driverVersion")
+    );
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+fn capabilities_json(details: &DeviceDetails) -> Value {
+    let extensions = details
+        .extensions
+        .iter()
+        .map(|extension| (extension.name.clone(), json!(extension.spec_version)))
+        .collect::<Map<_, _>>();
+
+    let features = details
+        .features
+        .iter()
+        .map(|group| {
+            let members = group
+                .features
+                .iter()
+                .map(|(name, supported)| (name.clone(), json!(supported)))
+                .collect::<Map<_, _>>();
+            (group.name.clone(), Value::Object(members))
+        })
+        .collect::<Map<_, _>>();
+
+    let mut properties = Map::new();
+    for group in &details.properties {
+        if let Some((_, member)) = NESTED.iter().find(|(name, _)| *name == group.name) {
+            let device = properties
+                .entry("VkPhysicalDeviceProperties")
+                .or_insert_with(|| json!({}));
+            device[*member] = members_json(group);
+        } else {
+            let members = members_json(group);
+            match properties.get_mut(&group.name) {
+                // `VkPhysicalDeviceProperties` may already hold `limits`.
+                Some(Value::Object(existing)) => {
+                    existing.extend(members.as_object().cloned().unwrap_or_default())
+                }
+                _ => {
+                    properties.insert(group.name.clone(), members);
+                }
+            }
+        }
+    }
+
+    let formats = details
+        .formats
+        .iter()
+        .map(|support| {
+            let features = |flags: vk::FormatFeatureFlags| {
+                json!(flag_names(
+                    "VK_FORMAT_FEATURE_",
+                    flags.as_raw(),
+                    vk::FormatFeatureFlags::from_raw
+                ))
+            };
+            (
+                format!("VK_FORMAT_{:?}", support.format),
+                json!({
+                    "VkFormatProperties": {
+                        "linearTilingFeatures": features(support.linear_tiling),
+                        "optimalTilingFeatures": features(support.optimal_tiling),
+                        "bufferFeatures": features(support.buffer),
+                    }
+                }),
+            )
+        })
+        .collect::<Map<_, _>>();
+
+    let queue_families = details
+        .queue_families
+        .iter()
+        .map(|family| {
+            let [width, height, depth] = family.min_image_transfer_granularity;
+            json!({
+                "VkQueueFamilyProperties": {
+                    "queueFlags": flag_names("VK_QUEUE_", family.flags.as_raw(), vk::QueueFlags::from_raw),
+                    "queueCount": family.count,
+                    "timestampValidBits": family.timestamp_valid_bits,
+                    "minImageTransferGranularity": {
+                        "width": width,
+                        "height": height,
+                        "depth": depth,
+                    },
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "extensions": extensions,
+        "features": features,
+        "properties": properties,
+        "formats": formats,
+        "queueFamiliesProperties": queue_families,
+    })
+}
+
+fn members_json(group: &PropertyGroup) -> Value {
+    Value::Object(
+        group
+            .properties
+            .iter()
+            .map(|(name, value)| (name.clone(), value_json(name, value)))
+            .collect(),
+    )
+}
+
+fn value_json(name: &str, value: &PropertyValue) -> Value {
+    match value {
+        PropertyValue::Bool(value) => json!(value),
+        PropertyValue::Unsigned(value) => json!(value),
+        PropertyValue::Signed(value) => json!(value),
+        PropertyValue::Float(value) => json!(value),
+        PropertyValue::UnsignedArray(values) => json!(values),
+        PropertyValue::FloatArray(values) => json!(values),
+        PropertyValue::SampleCounts(counts) => json!(
+            (0..7)
+                .map(|bit| 1u32 << bit)
+                .filter(|&count| counts.as_raw() & count != 0)
+                .map(|count| format!("VK_SAMPLE_COUNT_{count}_BIT"))
+                .collect::<Vec<_>>()
+        ),
+        PropertyValue::Version(version) => json!(version),
+        PropertyValue::Flags(names) => json!(names),
+        // Identifiers are byte arrays in the C structures.
+        PropertyValue::Text(hex) if name.ends_with("UUID") || name.ends_with("LUID") => json!(
+            (0..hex.len())
+                .step_by(2)
+                .filter_map(|index| u8::from_str_radix(hex.get(index..index + 2)?, 16).ok())
+                .collect::<Vec<_>>()
+        ),
+        // `VkConformanceVersion` is a structure, shown as `1.3.8.0`.
+        PropertyValue::Text(version) if name == "conformanceVersion" => {
+            let mut parts = version
+                .split('.')
+                .map(|part| part.parse::<u8>().unwrap_or(0));
+            let mut part = || parts.next().unwrap_or(0);
+            json!({
+                "major": part(),
+                "minor": part(),
+                "subminor": part(),
+                "patch": part(),
+            })
+        }
+        PropertyValue::Text(text) => json!(text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::details::{Extension, FeatureGroup, FormatSupport, QueueFamily};
+
+    fn details() -> DeviceDetails {
+        let group = |name: &str, properties: Vec<(&str, PropertyValue)>| PropertyGroup {
+            name: name.to_string(),
+            properties: properties
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value))
+                .collect(),
+        };
+        DeviceDetails {
+            extensions: vec![Extension {
+                name: "VK_KHR_swapchain".to_string(),
+                spec_version: 70,
+            }],
+            features: vec![FeatureGroup {
+                name: "VkPhysicalDeviceFeatures".to_string(),
+                features: vec![("geometryShader".to_string(), true)],
+            }],
+            properties: vec![
+                group(
+                    "VkPhysicalDeviceProperties",
+                    vec![
+                        ("apiVersion", PropertyValue::Version(4206847)),
+                        ("driverVersion", PropertyValue::Unsigned(100679680)),
+                        ("vendorID", PropertyValue::Unsigned(4098)),
+                        (
+                            "deviceType",
+                            PropertyValue::Text("VK_PHYSICAL_DEVICE_TYPE_DISCRETE_GPU".to_string()),
+                        ),
+                        (
+                            "deviceName",
+                            PropertyValue::Text("AMD Radeon RX 7900 XTX (RADV NAVI31)".to_string()),
+                        ),
+                        ("pipelineCacheUUID", PropertyValue::Text("00ff".to_string())),
+                    ],
+                ),
+                group(
+                    "VkPhysicalDeviceLimits",
+                    vec![
+                        ("maxImageDimension2D", PropertyValue::Unsigned(16384)),
+                        (
+                            "framebufferColorSampleCounts",
+                            PropertyValue::SampleCounts(
+                                vk::SampleCountFlags::TYPE_1 | vk::SampleCountFlags::TYPE_8,
+                            ),
+                        ),
+                    ],
+                ),
+                group(
+                    "VkPhysicalDeviceSparseProperties",
+                    vec![("residencyStandard2DBlockShape", PropertyValue::Bool(true))],
+                ),
+                group(
+                    "VkPhysicalDeviceVulkan12Properties",
+                    vec![
+                        (
+                            "driverID",
+                            PropertyValue::Text("VK_DRIVER_ID_MESA_RADV".to_string()),
+                        ),
+                        (
+                            "conformanceVersion",
+                            PropertyValue::Text("1.3.8.0".to_string()),
+                        ),
+                    ],
+                ),
+            ],
+            formats: vec![FormatSupport {
+                format: vk::Format::R8G8B8A8_UNORM,
+                linear_tiling: vk::FormatFeatureFlags::empty(),
+                optimal_tiling: vk::FormatFeatureFlags::SAMPLED_IMAGE
+                    | vk::FormatFeatureFlags::COLOR_ATTACHMENT,
+                buffer: vk::FormatFeatureFlags::VERTEX_BUFFER,
+            }],
+            queue_families: vec![QueueFamily {
+                flags: vk::QueueFlags::GRAPHICS | vk::QueueFlags::COMPUTE,
+                count: 1,
+                timestamp_valid_bits: 64,
+                min_image_transfer_granularity: [1, 1, 1],
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_report() {
+        let report = report(&[details()]);
+        let name = "VP_VULKANINFO_AMD_Radeon_RX_7900_XTX__RADV_NAVI31__100679680";
+        assert_eq!(report["profiles"][name]["api-version"], "1.3.255");
+        assert_eq!(report["profiles"][name]["capabilities"], json!(["device"]));
+
+        let device = &report["capabilities"]["device"];
+        assert_eq!(device["extensions"]["VK_KHR_swapchain"], 70);
+        assert_eq!(
+            device["features"]["VkPhysicalDeviceFeatures"]["geometryShader"],
+            true
+        );
+        let properties = &device["properties"]["VkPhysicalDeviceProperties"];
+        assert_eq!(properties["apiVersion"], 4206847);
+        assert_eq!(properties["pipelineCacheUUID"], json!([0, 255]));
+        assert_eq!(properties["limits"]["maxImageDimension2D"], 16384);
+        assert_eq!(
+            properties["limits"]["framebufferColorSampleCounts"],
+            json!(["VK_SAMPLE_COUNT_1_BIT", "VK_SAMPLE_COUNT_8_BIT"])
+        );
+        assert_eq!(
+            properties["sparseProperties"]["residencyStandard2DBlockShape"],
+            true
+        );
+        assert_eq!(
+            device["properties"]["VkPhysicalDeviceVulkan12Properties"]["conformanceVersion"],
+            json!({ "major": 1, "minor": 3, "subminor": 8, "patch": 0 })
+        );
+        assert_eq!(
+            device["formats"]["VK_FORMAT_R8G8B8A8_UNORM"]["VkFormatProperties"]["optimalTilingFeatures"],
+            json!([
+                "VK_FORMAT_FEATURE_SAMPLED_IMAGE_BIT",
+                "VK_FORMAT_FEATURE_COLOR_ATTACHMENT_BIT"
+            ])
+        );
+        assert_eq!(
+            device["queueFamiliesProperties"][0]["VkQueueFamilyProperties"]["queueFlags"],
+            json!(["VK_QUEUE_GRAPHICS_BIT", "VK_QUEUE_COMPUTE_BIT"])
+        );
+    }
+
+    #[test]
+    fn test_identical_devices() {
+        let report = report(&[details(), details()]);
+        let profiles = report["profiles"].as_object().unwrap();
+        assert_eq!(profiles.len(), 2);
+        let name = "VP_VULKANINFO_AMD_Radeon_RX_7900_XTX__RADV_NAVI31__100679680_1";
+        assert_eq!(profiles[name]["capabilities"], json!(["device1"]));
+    }
+
+    #[test]
+    fn test_import_round_trip() {
+        let report = report(&[details()]).to_string();
+        let devices = crate::import::parse_devices(&report).unwrap();
+        assert_eq!(
+            devices[0].device_name,
+            "AMD Radeon RX 7900 XTX (RADV NAVI31)"
+        );
+        assert_eq!(devices[0].driver_id, vk::DriverId::MESA_RADV);
+        assert_eq!(devices[0].api_version, "1.3.255");
+        assert_eq!(devices[0].characteristics.max_image_dimension_2d, 16384);
+    }
+}
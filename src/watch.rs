@@ -0,0 +1,170 @@
+use ash::{Instance, vk};
+use crossterm::terminal;
+use std::{
+    error::Error,
+    io::{self, Write},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::cli::Options;
+use crate::device::Device;
+use crate::vt::enable_virtual_terminal_processing;
+use crate::{WRAP_OFF, WRAP_ON, is_ansi_supported, render_device};
+
+const HIDE_CURSOR: &str = "\x1B[?25l";
+const SHOW_CURSOR: &str = "\x1B[?25h";
+const CLEAR_LINE: &str = "\x1B[2K";
+const CLEAR_BELOW: &str = "\x1B[J";
+
+/// How often the sleep between refreshes checks for Ctrl-C.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Returns a flag that turns `false` once Ctrl-C is pressed.
+pub(crate) fn running_until_ctrl_c() -> Result<Arc<AtomicBool>, ctrlc::Error> {
+    let running = Arc::new(AtomicBool::new(true));
+    let handler_flag = Arc::clone(&running);
+    ctrlc::set_handler(move || handler_flag.store(false, Ordering::SeqCst))?;
+    Ok(running)
+}
+
+/// Sleeps for `interval`, returning early once `running` turns `false`.
+pub(crate) fn sleep_while_running(running: &AtomicBool, interval: Duration) {
+    let start = Instant::now();
+    while running.load(Ordering::SeqCst) && start.elapsed() < interval {
+        thread::sleep(POLL_INTERVAL.min(interval.saturating_sub(start.elapsed())));
+    }
+}
+
+/// Draws `frame` over the previous one, which was `previous_height` lines
+/// tall, and returns the height drawn. The cursor cannot move above the top
+/// of the screen, so with ANSI support the frame is cut to the terminal's
+/// `rows`; without it the frames are simply appended.
+fn draw_frame(
+    out: &mut impl Write,
+    frame: &[String],
+    previous_height: usize,
+    rows: Option<usize>,
+    use_ansi: bool,
+) -> io::Result<usize> {
+    if !use_ansi {
+        for line in frame {
+            writeln!(out, "{}", line)?;
+        }
+        out.flush()?;
+        return Ok(frame.len());
+    }
+
+    // One row stays free for the cursor below the last line.
+    let height = rows.map_or(frame.len(), |rows| frame.len().min(rows.saturating_sub(1)));
+    if previous_height > 0 {
+        write!(out, "\x1B[{}A", previous_height)?;
+    }
+    for line in &frame[..height] {
+        writeln!(out, "{}{}", CLEAR_LINE, line)?;
+    }
+    write!(out, "{}", CLEAR_BELOW)?;
+    out.flush()?;
+    Ok(height)
+}
+
+/// Redraws every device each `interval` until Ctrl-C is pressed,
+/// re-querying the memory budget and sensors on each tick.
+pub(crate) fn watch_devices(
+    instance: &Instance,
+    instance_version: u32,
+    device_handles: &[vk::PhysicalDevice],
+    options: &Options,
+    interval: Duration,
+) -> Result<(), Box<dyn Error>> {
+    let running = running_until_ctrl_c()?;
+
+    let _ = enable_virtual_terminal_processing();
+    let use_ansi = is_ansi_supported();
+    let mut devices = device_handles
+        .iter()
+        .map(|&handle| Device::new(instance, instance_version, handle))
+        .collect::<Vec<_>>();
+
+    let mut stdout = io::stdout();
+    if use_ansi {
+        write!(stdout, "{}{}", WRAP_OFF, HIDE_CURSOR)?;
+    }
+    let result = (|| -> io::Result<()> {
+        let mut previous_height = 0;
+        while running.load(Ordering::SeqCst) {
+            let frame = devices
+                .iter()
+                .flat_map(|device| {
+                    let mut lines = render_device(device, options, use_ansi);
+                    lines.push(String::new());
+                    lines
+                })
+                .collect::<Vec<_>>();
+
+            let rows = terminal::size().ok().map(|(_, rows)| usize::from(rows));
+            previous_height = draw_frame(&mut stdout, &frame, previous_height, rows, use_ansi)?;
+
+            sleep_while_running(&running, interval);
+            if !running.load(Ordering::SeqCst) {
+                break;
+            }
+            for (device, &handle) in devices.iter_mut().zip(device_handles) {
+                device.refresh(instance, handle);
+            }
+        }
+        Ok(())
+    })();
+
+    // Restore the terminal even if drawing failed.
+    if use_ansi {
+        write!(stdout, "{}{}", SHOW_CURSOR, WRAP_ON)?;
+        stdout.flush()?;
+    }
+    Ok(result?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn test_draw_frame() {
+        let mut out = Vec::new();
+        let height = draw_frame(&mut out, &frame(&["a", "b"]), 0, Some(24), true).unwrap();
+        assert_eq!(height, 2);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\x1B[2Ka\n\x1B[2Kb\n\x1B[J"
+        );
+
+        // The next frame moves back over the previous one.
+        let mut out = Vec::new();
+        draw_frame(&mut out, &frame(&["c"]), 2, Some(24), true).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "\x1B[2A\x1B[2Kc\n\x1B[J");
+    }
+
+    #[test]
+    fn test_draw_frame_taller_than_terminal() {
+        let mut out = Vec::new();
+        let height = draw_frame(&mut out, &frame(&["a", "b", "c", "d"]), 2, Some(3), true).unwrap();
+        assert_eq!(height, 2);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\x1B[2A\x1B[2Ka\n\x1B[2Kb\n\x1B[J"
+        );
+
+        let mut out = Vec::new();
+        let height = draw_frame(&mut out, &frame(&["a", "b", "c"]), 3, Some(3), false).unwrap();
+        assert_eq!(height, 3);
+        assert_eq!(String::from_utf8(out).unwrap(), "a\nb\nc\n");
+    }
+}